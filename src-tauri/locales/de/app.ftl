@@ -0,0 +1,104 @@
+greeting = Hallo, { $name }! Rust lässt grüßen.
+
+greeting-count =
+    { $count ->
+        [one] Du hast eine Person begrüßt.
+       *[other] Du hast { $count } Personen begrüßt.
+    }
+
+greeting-received =
+    { $gender ->
+        [female] { $name } hat dich begrüßt. Sag ihr hallo!
+        [male] { $name } hat dich begrüßt. Sag ihm hallo!
+       *[other] { $name } hat dich begrüßt. Sag hallo!
+    }
+
+notification-greeting-received-title = Neuer Gruß von { $name }
+
+crash-prompt-title = Unerwartet beendet
+
+crash-prompt-message =
+    { $count ->
+        [one] Die App wurde beim letzten Mal unerwartet beendet. Absturzbericht speichern, um ihn uns zu schicken?
+       *[other] Die App wurde { $count } Mal unerwartet beendet. Absturzberichte speichern, um sie uns zu schicken?
+    }
+
+crash-save = Speichern…
+crash-discard = Verwerfen
+crash-later = Später
+
+notification-digest-title =
+    { $count ->
+        [one] Eine Benachrichtigung ist während der Ruhezeit eingegangen
+       *[other] { $count } Benachrichtigungen sind während der Ruhezeit eingegangen
+    }
+
+notification-digest-more = …und { $count } weitere
+
+link-confirm-title = Externen Link öffnen?
+link-confirm-message = { $host } wird in deinem Browser geöffnet.
+link-open = Öffnen
+link-cancel = Abbrechen
+
+document-untitled = Unbenannt
+document-close-title = Ungespeicherte Änderungen
+
+document-close-message =
+    { $count ->
+        [one] { $names } hat ungespeicherte Änderungen. Vor dem Schließen speichern?
+       *[other] { $count } Dokumente haben ungespeicherte Änderungen: { $names }. Vor dem Schließen speichern?
+    }
+
+document-save = Speichern
+document-discard = Nicht speichern
+document-cancel = Abbrechen
+document-save-failed-title = Speichern fehlgeschlagen
+document-save-failed-message = Das Dokument konnte nicht gespeichert werden: { $error }
+
+window-title-main = tauri-app
+window-title-settings = Einstellungen
+window-title-about = Über
+
+tray-show = tauri-app anzeigen
+tray-hide = tauri-app ausblenden
+tray-recent = Zuletzt geöffnet
+
+tray-unread =
+    { $count ->
+        [0] Keine ungelesenen Mitteilungen
+        [one] Eine ungelesene Mitteilung
+       *[other] { $count } ungelesene Mitteilungen
+    }
+
+tray-pause = Mitteilungen pausieren
+tray-quit = Beenden
+
+tray-tooltip =
+    { $count ->
+        [0] tauri-app
+        [one] tauri-app: eine ungelesene Mitteilung
+       *[other] tauri-app: { $count } ungelesene Mitteilungen
+    }
+
+menu-file = Datei
+menu-new = Neu
+menu-open = Öffnen …
+menu-open-recent = Zuletzt geöffnet
+menu-recent-empty = Keine zuletzt geöffneten Dokumente
+menu-clear-recent = Liste löschen
+menu-save = Sichern
+menu-save-as = Sichern unter …
+menu-close-window = Fenster schließen
+menu-quit = Beenden
+menu-edit = Bearbeiten
+menu-undo = Widerrufen
+menu-redo = Wiederholen
+menu-cut = Ausschneiden
+menu-copy = Kopieren
+menu-paste = Einsetzen
+menu-select-all = Alles auswählen
+menu-settings = Einstellungen …
+menu-view = Darstellung
+menu-fullscreen = Vollbild ein/aus
+menu-help = Hilfe
+menu-about = Über tauri-app
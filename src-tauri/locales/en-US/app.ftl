@@ -0,0 +1,104 @@
+greeting = Hello, { $name }! You've been greeted from Rust!
+
+greeting-count =
+    { $count ->
+        [one] You have greeted one person.
+       *[other] You have greeted { $count } people.
+    }
+
+greeting-received =
+    { $gender ->
+        [female] { $name } greeted you. Say hi to her!
+        [male] { $name } greeted you. Say hi to him!
+       *[other] { $name } greeted you. Say hi to them!
+    }
+
+notification-greeting-received-title = New greeting from { $name }
+
+crash-prompt-title = Unexpected shutdown
+
+crash-prompt-message =
+    { $count ->
+        [one] The app closed unexpectedly last time. Save the crash report so you can send it to us?
+       *[other] The app closed unexpectedly { $count } times. Save the crash reports so you can send them to us?
+    }
+
+crash-save = Save…
+crash-discard = Discard
+crash-later = Later
+
+notification-digest-title =
+    { $count ->
+        [one] One notification arrived during quiet hours
+       *[other] { $count } notifications arrived during quiet hours
+    }
+
+notification-digest-more = …and { $count } more
+
+link-confirm-title = Open external link?
+link-confirm-message = This will open { $host } in your browser.
+link-open = Open
+link-cancel = Cancel
+
+document-untitled = Untitled
+document-close-title = Unsaved changes
+
+document-close-message =
+    { $count ->
+        [one] { $names } has unsaved changes. Save before closing?
+       *[other] { $count } documents have unsaved changes: { $names }. Save them before closing?
+    }
+
+document-save = Save
+document-discard = Don’t Save
+document-cancel = Cancel
+document-save-failed-title = Could not save
+document-save-failed-message = The document could not be saved: { $error }
+
+window-title-main = tauri-app
+window-title-settings = Settings
+window-title-about = About
+
+tray-show = Show tauri-app
+tray-hide = Hide tauri-app
+tray-recent = Recent Documents
+
+tray-unread =
+    { $count ->
+        [0] No unread notifications
+        [one] One unread notification
+       *[other] { $count } unread notifications
+    }
+
+tray-pause = Pause Notifications
+tray-quit = Quit
+
+tray-tooltip =
+    { $count ->
+        [0] tauri-app
+        [one] tauri-app: one unread notification
+       *[other] tauri-app: { $count } unread notifications
+    }
+
+menu-file = File
+menu-new = New
+menu-open = Open…
+menu-open-recent = Open Recent
+menu-recent-empty = No Recent Documents
+menu-clear-recent = Clear Recent
+menu-save = Save
+menu-save-as = Save As…
+menu-close-window = Close Window
+menu-quit = Quit
+menu-edit = Edit
+menu-undo = Undo
+menu-redo = Redo
+menu-cut = Cut
+menu-copy = Copy
+menu-paste = Paste
+menu-select-all = Select All
+menu-settings = Settings…
+menu-view = View
+menu-fullscreen = Toggle Full Screen
+menu-help = Help
+menu-about = About tauri-app
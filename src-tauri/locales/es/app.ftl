@@ -0,0 +1,104 @@
+greeting = ¡Hola, { $name }! Te han saludado desde Rust.
+
+greeting-count =
+    { $count ->
+        [one] Has saludado a una persona.
+       *[other] Has saludado a { $count } personas.
+    }
+
+greeting-received =
+    { $gender ->
+        [female] { $name } te ha saludado. ¡Salúdala!
+        [male] { $name } te ha saludado. ¡Salúdalo!
+       *[other] { $name } te ha saludado. ¡Devuélvele el saludo!
+    }
+
+notification-greeting-received-title = Nuevo saludo de { $name }
+
+crash-prompt-title = Cierre inesperado
+
+crash-prompt-message =
+    { $count ->
+        [one] La aplicación se cerró inesperadamente la última vez. ¿Quieres guardar el informe de error para enviárnoslo?
+       *[other] La aplicación se cerró inesperadamente { $count } veces. ¿Quieres guardar los informes de error para enviárnoslos?
+    }
+
+crash-save = Guardar…
+crash-discard = Descartar
+crash-later = Más tarde
+
+notification-digest-title =
+    { $count ->
+        [one] Llegó una notificación durante las horas de silencio
+       *[other] Llegaron { $count } notificaciones durante las horas de silencio
+    }
+
+notification-digest-more = …y { $count } más
+
+link-confirm-title = ¿Abrir enlace externo?
+link-confirm-message = Se abrirá { $host } en tu navegador.
+link-open = Abrir
+link-cancel = Cancelar
+
+document-untitled = Sin título
+document-close-title = Cambios sin guardar
+
+document-close-message =
+    { $count ->
+        [one] { $names } tiene cambios sin guardar. ¿Guardar antes de cerrar?
+       *[other] { $count } documentos tienen cambios sin guardar: { $names }. ¿Guardarlos antes de cerrar?
+    }
+
+document-save = Guardar
+document-discard = No guardar
+document-cancel = Cancelar
+document-save-failed-title = No se pudo guardar
+document-save-failed-message = No se pudo guardar el documento: { $error }
+
+window-title-main = tauri-app
+window-title-settings = Ajustes
+window-title-about = Acerca de
+
+tray-show = Mostrar tauri-app
+tray-hide = Ocultar tauri-app
+tray-recent = Documentos recientes
+
+tray-unread =
+    { $count ->
+        [0] No hay notificaciones sin leer
+        [one] Una notificación sin leer
+       *[other] { $count } notificaciones sin leer
+    }
+
+tray-pause = Pausar notificaciones
+tray-quit = Salir
+
+tray-tooltip =
+    { $count ->
+        [0] tauri-app
+        [one] tauri-app: una notificación sin leer
+       *[other] tauri-app: { $count } notificaciones sin leer
+    }
+
+menu-file = Archivo
+menu-new = Nuevo
+menu-open = Abrir…
+menu-open-recent = Abrir reciente
+menu-recent-empty = No hay documentos recientes
+menu-clear-recent = Borrar recientes
+menu-save = Guardar
+menu-save-as = Guardar como…
+menu-close-window = Cerrar ventana
+menu-quit = Salir
+menu-edit = Edición
+menu-undo = Deshacer
+menu-redo = Rehacer
+menu-cut = Cortar
+menu-copy = Copiar
+menu-paste = Pegar
+menu-select-all = Seleccionar todo
+menu-settings = Ajustes…
+menu-view = Ver
+menu-fullscreen = Pantalla completa
+menu-help = Ayuda
+menu-about = Acerca de tauri-app
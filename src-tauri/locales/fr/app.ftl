@@ -0,0 +1,104 @@
+greeting = Bonjour, { $name } ! Rust vous salue.
+
+greeting-count =
+    { $count ->
+        [one] Vous avez salué une personne.
+       *[other] Vous avez salué { $count } personnes.
+    }
+
+greeting-received =
+    { $gender ->
+        [female] { $name } vous a salué. Elle attend votre réponse !
+        [male] { $name } vous a salué. Il attend votre réponse !
+       *[other] { $name } vous a salué. Répondez-lui !
+    }
+
+notification-greeting-received-title = Nouvelle salutation de { $name }
+
+crash-prompt-title = Fermeture inattendue
+
+crash-prompt-message =
+    { $count ->
+        [one] L’application s’est fermée de manière inattendue la dernière fois. Enregistrer le rapport de plantage pour nous l’envoyer ?
+       *[other] L’application s’est fermée de manière inattendue { $count } fois. Enregistrer les rapports de plantage pour nous les envoyer ?
+    }
+
+crash-save = Enregistrer…
+crash-discard = Ignorer
+crash-later = Plus tard
+
+notification-digest-title =
+    { $count ->
+        [one] Une notification est arrivée pendant les heures calmes
+       *[other] { $count } notifications sont arrivées pendant les heures calmes
+    }
+
+notification-digest-more = …et { $count } de plus
+
+link-confirm-title = Ouvrir le lien externe ?
+link-confirm-message = { $host } va s’ouvrir dans votre navigateur.
+link-open = Ouvrir
+link-cancel = Annuler
+
+document-untitled = Sans titre
+document-close-title = Modifications non enregistrées
+
+document-close-message =
+    { $count ->
+        [one] { $names } contient des modifications non enregistrées. Enregistrer avant de fermer ?
+       *[other] { $count } documents contiennent des modifications non enregistrées : { $names }. Les enregistrer avant de fermer ?
+    }
+
+document-save = Enregistrer
+document-discard = Ne pas enregistrer
+document-cancel = Annuler
+document-save-failed-title = Échec de l’enregistrement
+document-save-failed-message = Le document n’a pas pu être enregistré : { $error }
+
+window-title-main = tauri-app
+window-title-settings = Réglages
+window-title-about = À propos
+
+tray-show = Afficher tauri-app
+tray-hide = Masquer tauri-app
+tray-recent = Documents récents
+
+tray-unread =
+    { $count ->
+        [0] Aucune notification non lue
+        [one] Une notification non lue
+       *[other] { $count } notifications non lues
+    }
+
+tray-pause = Suspendre les notifications
+tray-quit = Quitter
+
+tray-tooltip =
+    { $count ->
+        [0] tauri-app
+        [one] tauri-app : une notification non lue
+       *[other] tauri-app : { $count } notifications non lues
+    }
+
+menu-file = Fichier
+menu-new = Nouveau
+menu-open = Ouvrir…
+menu-open-recent = Ouvrir un document récent
+menu-recent-empty = Aucun document récent
+menu-clear-recent = Effacer la liste
+menu-save = Enregistrer
+menu-save-as = Enregistrer sous…
+menu-close-window = Fermer la fenêtre
+menu-quit = Quitter
+menu-edit = Édition
+menu-undo = Annuler
+menu-redo = Rétablir
+menu-cut = Couper
+menu-copy = Copier
+menu-paste = Coller
+menu-select-all = Tout sélectionner
+menu-settings = Réglages…
+menu-view = Présentation
+menu-fullscreen = Plein écran
+menu-help = Aide
+menu-about = À propos de tauri-app
@@ -0,0 +1,10 @@
+CREATE TABLE greetings (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    name        TEXT    NOT NULL,
+    locale      TEXT    NOT NULL,
+    window      TEXT    NOT NULL,
+    created_at  TEXT    NOT NULL
+);
+
+CREATE INDEX greetings_created_at ON greetings (created_at);
+CREATE INDEX greetings_name ON greetings (name COLLATE NOCASE);
@@ -0,0 +1,11 @@
+CREATE TABLE scheduled_notifications (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    title       TEXT    NOT NULL,
+    body        TEXT,
+    -- JSON-encoded `Trigger`.
+    trigger     TEXT    NOT NULL,
+    next_run    TEXT    NOT NULL,
+    created_at  TEXT    NOT NULL
+);
+
+CREATE INDEX scheduled_notifications_next_run ON scheduled_notifications (next_run);
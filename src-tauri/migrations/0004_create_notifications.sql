@@ -0,0 +1,20 @@
+CREATE TABLE notifications (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    title       TEXT    NOT NULL,
+    body        TEXT,
+    category    TEXT,
+    urgent      INTEGER NOT NULL DEFAULT 0,
+    -- One of `DeliveryStatus`.
+    status      TEXT    NOT NULL,
+    error       TEXT,
+    -- The digest a deferred notification was finally shown in.
+    digest_id   INTEGER REFERENCES notifications (id) ON DELETE SET NULL,
+    created_at  TEXT    NOT NULL
+);
+
+CREATE INDEX notifications_created_at ON notifications (created_at);
+CREATE INDEX notifications_category ON notifications (category, created_at);
+CREATE INDEX notifications_status ON notifications (status);
+
+ALTER TABLE scheduled_notifications ADD COLUMN category TEXT;
+ALTER TABLE scheduled_notifications ADD COLUMN urgent INTEGER NOT NULL DEFAULT 0;
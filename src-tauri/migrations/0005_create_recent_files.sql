@@ -0,0 +1,6 @@
+CREATE TABLE recent_files (
+    path        TEXT PRIMARY KEY NOT NULL,
+    opened_at   TEXT NOT NULL
+);
+
+CREATE INDEX recent_files_opened_at ON recent_files (opened_at);
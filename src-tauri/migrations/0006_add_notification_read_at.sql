@@ -0,0 +1,4 @@
+-- When the user saw a delivered notification in the app; NULL while unread.
+ALTER TABLE notifications ADD COLUMN read_at TEXT;
+
+CREATE INDEX notifications_unread ON notifications (status, read_at);
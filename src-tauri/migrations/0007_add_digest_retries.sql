@@ -0,0 +1,4 @@
+-- How often a failed digest was tried, and when to try it again; NULL once
+-- it was shown or for anything that is not a digest.
+ALTER TABLE notifications ADD COLUMN attempts INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE notifications ADD COLUMN retry_at TEXT;
@@ -0,0 +1,3 @@
+-- Why a schedule could not be moved on to its next run. Set, the schedule
+-- is stopped until it is given a new trigger.
+ALTER TABLE scheduled_notifications ADD COLUMN error TEXT;
@@ -0,0 +1,386 @@
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tauri::{App, Builder, Context, Manager, RunEvent, Runtime, Wry};
+
+use crate::bindings;
+use crate::crash::{self, CrashReporter};
+use crate::db::{self, greetings, notifications, Database};
+use crate::documents::{self, Documents};
+use crate::error::AppResult;
+use crate::events::AppEvent;
+use crate::i18n::I18n;
+use crate::links::{self, LinkPolicy};
+use crate::logging::Logging;
+#[cfg(desktop)]
+use crate::menu;
+use crate::notifications::clock::SystemClock;
+use crate::notifications::dispatch::Dispatcher;
+use crate::notifications::scheduler::{self, Scheduler};
+use crate::notifications::templates::TemplateRegistry;
+use crate::notifications::PluginNotifier;
+use crate::process::manager::ProcessManager;
+use crate::process::runner::ProcessRunner;
+use crate::process::{self, Allowlist};
+use crate::settings::{self, SettingsStore};
+use crate::streaming::Requests;
+#[cfg(desktop)]
+use crate::tray;
+use crate::windows::geometry::{self, GeometryStore};
+use crate::windows::{self, WindowManager};
+
+/// Which bundled plugins to register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Plugins {
+    pub notification: bool,
+    pub dialog: bool,
+    pub shell: bool,
+    pub fs: bool,
+}
+
+impl Plugins {
+    pub const ALL: Self = Self {
+        notification: true,
+        dialog: true,
+        shell: true,
+        fs: true,
+    };
+
+    pub const NONE: Self = Self {
+        notification: false,
+        dialog: false,
+        shell: false,
+        fs: false,
+    };
+}
+
+impl Default for Plugins {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+/// Manages one value passed to [`AppBuilder::with_state`].
+type ManageState<R> = Box<dyn FnOnce(Builder<R>) -> Builder<R>>;
+
+/// Assembles the application: plugins, managed state and commands.
+///
+/// Used by [`crate::run`] on desktop and mobile, and by the integration tests
+/// with `tauri::test::mock_builder()`:
+///
+/// ```no_run
+/// # use tauri_app_lib::{db::Database, AppBuilder, Plugins};
+/// # fn main() -> tauri_app_lib::error::AppResult<()> {
+/// let app = AppBuilder::with_builder(tauri::test::mock_builder())
+///     .with_plugins(Plugins::NONE)
+///     .with_state(Database::open_in_memory()?)
+///     .build(tauri::test::mock_context(tauri::test::noop_assets()))?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Any of [`I18n`], [`SettingsStore`], [`Database`], [`Logging`],
+/// [`CrashReporter`], [`Dispatcher`], [`Scheduler`], [`TemplateRegistry`],
+/// [`ProcessRunner`], [`ProcessManager`], [`LinkPolicy`], [`Documents`],
+/// [`WindowManager`] and [`GeometryStore`] passed to
+/// [`AppBuilder::with_state`] replaces the instance [`AppBuilder::build`]
+/// would otherwise create from the app's config, data and log directories.
+/// Passing the same type twice keeps the last one.
+/// Only [`AppBuilder::run`] installs the global log subscriber and panic hook,
+/// and starts the notification scheduler's background task.
+pub struct AppBuilder<R: Runtime = Wry> {
+    builder: Builder<R>,
+    /// What [`AppBuilder::with_state`] was given, by type, so a later value
+    /// replaces an earlier one; only managed once the app is built.
+    state: HashMap<TypeId, ManageState<R>>,
+    plugins: Plugins,
+    #[cfg_attr(mobile, allow(dead_code))]
+    menu: bool,
+    #[cfg_attr(mobile, allow(dead_code))]
+    tray: bool,
+}
+
+impl AppBuilder<Wry> {
+    pub fn new() -> Self {
+        Self::with_builder(Builder::default())
+            .with_menu(true)
+            .with_tray(true)
+    }
+}
+
+impl Default for AppBuilder<Wry> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R: Runtime> AppBuilder<R> {
+    /// Starts from an existing Tauri builder, e.g. the mock runtime's.
+    pub fn with_builder(builder: Builder<R>) -> Self {
+        Self {
+            builder,
+            state: HashMap::new(),
+            plugins: Plugins::default(),
+            menu: false,
+            tray: false,
+        }
+    }
+
+    pub fn with_plugins(mut self, plugins: Plugins) -> Self {
+        self.plugins = plugins;
+        self
+    }
+
+    /// Whether to show the application menu bar once the app is running. On
+    /// with [`AppBuilder::new`], off for other builders such as the mock
+    /// runtime's, which has no menus. Mobile platforms have no menu bar either.
+    pub fn with_menu(mut self, menu: bool) -> Self {
+        self.menu = menu;
+        self
+    }
+
+    /// Whether to show a tray icon once the app is running. On with
+    /// [`AppBuilder::new`], off for other builders such as the mock
+    /// runtime's, which has no tray. Mobile platforms have no tray either.
+    pub fn with_tray(mut self, tray: bool) -> Self {
+        self.tray = tray;
+        self
+    }
+
+    /// Adds managed state. Adding a type again replaces the earlier value,
+    /// so the last one wins.
+    pub fn with_state<T: Send + Sync + 'static>(mut self, state: T) -> Self {
+        self.state.insert(
+            TypeId::of::<T>(),
+            Box::new(move |builder| builder.manage(state)),
+        );
+        self
+    }
+
+    fn into_builder(self) -> Builder<R> {
+        let mut builder = self.builder;
+        for manage in self.state.into_values() {
+            builder = manage(builder);
+        }
+        if self.plugins.notification {
+            builder = builder.plugin(tauri_plugin_notification::init());
+        }
+        if self.plugins.dialog {
+            builder = builder.plugin(tauri_plugin_dialog::init());
+        }
+        if self.plugins.shell {
+            builder = builder.plugin(tauri_plugin_shell::init());
+        }
+        if self.plugins.fs {
+            builder = builder.plugin(tauri_plugin_fs::init());
+        }
+
+        let handler = bindings::builder::<R>().invoke_handler();
+        #[cfg(desktop)]
+        let (show_menu, show_tray) = (self.menu, self.tray);
+        let builder = builder
+            // Tauri creates the windows from the config just before this runs.
+            .setup(move |app| {
+                show_windows(app);
+                #[cfg(desktop)]
+                if show_menu {
+                    if let Err(err) = menu::create(app.handle()) {
+                        tracing::warn!("could not create the menu bar: {err}");
+                    }
+                }
+                #[cfg(desktop)]
+                if show_tray {
+                    // A missing tray must not keep the app from starting.
+                    if let Err(err) = tray::create(app.handle()) {
+                        tracing::warn!("could not create the tray icon: {err}");
+                    }
+                }
+                Ok(())
+            })
+            .on_window_event(|window, event| {
+                // Closing to the tray is not closing at all.
+                #[cfg(desktop)]
+                let hidden = tray::on_window_event(window, event);
+                #[cfg(not(desktop))]
+                let hidden = false;
+                if !hidden {
+                    documents::on_window_event(window, event);
+                }
+                windows::on_window_event(window, event);
+                geometry::on_window_event(window, event);
+                #[cfg(desktop)]
+                menu::on_window_event(window, event);
+            })
+            // Each window's role decides which commands it may call.
+            .invoke_handler(move |invoke| windows::authorize(invoke).is_none_or(&handler));
+        // The menu bar and the tray share these items.
+        #[cfg(desktop)]
+        let builder = builder.on_menu_event(menu::on_menu_event);
+        builder
+    }
+
+    /// Builds the app and creates its managed state. Nothing runs until the
+    /// returned app's event loop is started.
+    pub fn build(self, context: Context<R>) -> AppResult<App<R>> {
+        self.build_with(context, false)
+    }
+
+    /// Builds the app, starts logging and crash reporting, and runs its
+    /// event loop until it exits. Reports left by a previous crash are
+    /// offered to the user once the app is ready; managed processes are
+    /// killed on exit.
+    pub fn run(self, context: Context<R>) -> AppResult<()> {
+        let prompt_crashes = self.plugins.dialog;
+        let app = self.build_with(context, true)?;
+        crash::install_panic_hook(app.handle());
+        scheduler::spawn(app.handle());
+
+        app.run(move |app, event| match event {
+            RunEvent::Ready if prompt_crashes => {
+                if let Err(err) = crash::prompt_pending(app) {
+                    tracing::warn!("could not check for crash reports: {err}");
+                }
+            }
+            RunEvent::Exit => {
+                tauri::async_runtime::block_on(app.state::<ProcessManager>().kill_all());
+                app.state::<Logging>().flush();
+            }
+            _ => {}
+        });
+        Ok(())
+    }
+
+    fn build_with(self, context: Context<R>, install_logging: bool) -> AppResult<App<R>> {
+        let app = self.into_builder().build(context)?;
+        init_state(&app, install_logging)?;
+        Ok(app)
+    }
+}
+
+/// Creates whatever managed state the caller did not inject.
+fn init_state<R: Runtime>(app: &App<R>, install_logging: bool) -> AppResult<()> {
+    if app.try_state::<I18n>().is_none() {
+        app.manage(I18n::from_system());
+    }
+
+    if app.try_state::<SettingsStore>().is_none() {
+        let config_dir = app.path().app_config_dir()?;
+        app.manage(SettingsStore::load(
+            config_dir.join(settings::SETTINGS_FILE),
+        )?);
+    }
+    let settings = app.state::<SettingsStore>();
+
+    if app.try_state::<Logging>().is_none() {
+        app.manage(Logging::new(app.path().app_log_dir()?));
+    }
+    if install_logging {
+        app.state::<Logging>().install(&settings.get().logging)?;
+    }
+    if app.try_state::<CrashReporter>().is_none() {
+        let log_dir = app.state::<Logging>().dir().to_owned();
+        app.manage(CrashReporter::new(log_dir.join(crash::CRASH_DIR)));
+    }
+
+    // A locale we no longer ship must not keep the app from starting.
+    let _ = app
+        .state::<I18n>()
+        .set_preferred_locale(settings.get().locale.as_deref());
+
+    if app.try_state::<Database>().is_none() {
+        let data_dir = app.path().app_data_dir()?;
+        app.manage(Database::open(&data_dir.join(db::DATABASE_FILE))?);
+    }
+    greetings::apply_retention(&app.state::<Database>(), &settings)?;
+
+    if app.try_state::<Dispatcher>().is_none() {
+        let handle = app.handle().clone();
+        app.manage(
+            Dispatcher::new(
+                Arc::new(SystemClock),
+                Arc::new(PluginNotifier::new(app.handle().clone())),
+            )
+            .with_listener(Box::new(move |conn, _| {
+                let emitted = notifications::unread_count(conn)
+                    .and_then(|unread| AppEvent::NotificationsChanged { unread }.emit(&handle));
+                if let Err(err) = emitted {
+                    tracing::warn!("could not emit unread notifications: {err}");
+                }
+            })),
+        );
+    }
+    if app.try_state::<Scheduler>().is_none() {
+        app.manage(Scheduler::new(Arc::new(SystemClock)));
+    }
+    if app.try_state::<TemplateRegistry>().is_none() {
+        app.manage(TemplateRegistry::builtin());
+    }
+
+    if app.try_state::<Requests>().is_none() {
+        app.manage(Requests::new());
+    }
+    if app.try_state::<LinkPolicy>().is_none() {
+        let path = app.path().app_config_dir()?.join(links::LINKS_FILE);
+        // A broken policy falls back to `https` links only.
+        let policy = LinkPolicy::load(&path).unwrap_or_else(|err| {
+            tracing::error!(path = %path.display(), "ignoring link policy: {err}");
+            LinkPolicy::default()
+        });
+        app.manage(policy);
+    }
+    if app.try_state::<Documents>().is_none() {
+        let handle = app.handle().clone();
+        app.manage(Documents::new().with_listener(Box::new(move |_| {
+            if let Err(err) = AppEvent::DocumentsChanged.emit(&handle) {
+                tracing::warn!("could not emit document changes: {err}");
+            }
+        })));
+    }
+    if app.try_state::<WindowManager>().is_none() {
+        app.manage(WindowManager::new());
+    }
+    if app.try_state::<GeometryStore>().is_none() {
+        let config_dir = app.path().app_config_dir()?;
+        app.manage(GeometryStore::load(
+            config_dir.join(geometry::WINDOW_STATE_FILE),
+        ));
+    }
+
+    if app.try_state::<ProcessRunner>().is_none() || app.try_state::<ProcessManager>().is_none() {
+        let path = app.path().app_config_dir()?.join(process::ALLOWLIST_FILE);
+        // A broken allowlist allows nothing rather than keeping the app from
+        // starting.
+        let allowlist = Allowlist::load(&path).unwrap_or_else(|err| {
+            tracing::error!(path = %path.display(), "ignoring command allowlist: {err}");
+            Allowlist::default()
+        });
+        if app.try_state::<ProcessRunner>().is_none() {
+            app.manage(ProcessRunner::new(allowlist.clone()));
+        }
+        if app.try_state::<ProcessManager>().is_none() {
+            let handle = app.handle().clone();
+            app.manage(ProcessManager::new(
+                allowlist,
+                Box::new(move |info| {
+                    if let Err(err) = AppEvent::ProcessStatus(info.clone()).emit(&handle) {
+                        tracing::warn!("could not emit process status: {err}");
+                    }
+                }),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Tracks the windows created from the config and shows them where they were
+/// last time. They start hidden so they never flash at their default place.
+fn show_windows<R: Runtime>(app: &App<R>) {
+    let manager = app.state::<WindowManager>();
+    for (label, window) in app.webview_windows() {
+        if let Err(err) = manager.track(&label, None) {
+            tracing::warn!(label = %label, "not tracking window: {err}");
+        }
+        windows::restore_and_show(&window);
+    }
+}
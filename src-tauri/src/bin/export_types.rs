@@ -0,0 +1,26 @@
+//! Writes the TypeScript the frontend is built against: the command
+//! bindings and the event definitions. Run it after changing any command or
+//! event; `tests/bindings.rs` and `tests/events.rs` fail until you do.
+
+use std::fs;
+use std::path::Path;
+
+use tauri_app_lib::error::AppResult;
+use tauri_app_lib::{bindings, events};
+
+type Generate = fn() -> AppResult<String>;
+
+fn main() {
+    let outputs: [(&str, Generate); 2] = [
+        (bindings::BINDINGS_FILE, bindings::typescript),
+        (events::TYPESCRIPT_FILE, events::typescript),
+    ];
+    for (file, generate) in outputs {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(file);
+        if let Err(err) = generate().and_then(|ts| Ok(fs::write(&path, ts)?)) {
+            eprintln!("could not write {}: {err}", path.display());
+            std::process::exit(1);
+        }
+        println!("wrote {}", path.display());
+    }
+}
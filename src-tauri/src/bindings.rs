@@ -0,0 +1,91 @@
+use specta_typescript::{BigIntExportBehavior, Typescript};
+use tauri::{Runtime, Wry};
+use tauri_specta::{collect_commands, Builder};
+
+use crate::db::{contacts, greetings, notifications};
+use crate::error::{AppError, AppResult, ErrorCode};
+use crate::notifications::{dispatch, scheduler, templates};
+use crate::process::{manager, runner};
+use crate::{crash, documents, files, i18n, links, logging, settings, streaming, windows};
+
+/// Where [`typescript`] is checked in, relative to the crate root.
+pub const BINDINGS_FILE: &str = "../src/bindings.ts";
+
+/// Every command the frontend can call. Both the invoke handler and the
+/// TypeScript bindings come from this list, so they cannot drift apart.
+/// Generic commands name a runtime only for their types; the handler works
+/// with any `R`.
+pub fn builder<R: Runtime>() -> Builder<R> {
+    Builder::new().commands(collect_commands![
+        crate::greet::<Wry>,
+        crate::greet_many,
+        streaming::cancel_request,
+        i18n::translate,
+        settings::get_settings,
+        settings::update_settings::<Wry>,
+        logging::log_from_frontend::<Wry>,
+        logging::read_logs,
+        crash::get_pending_crash_reports,
+        crash::discard_crash_report,
+        scheduler::schedule_notification,
+        scheduler::list_scheduled_notifications,
+        scheduler::reschedule_notification,
+        scheduler::cancel_scheduled_notification,
+        dispatch::send_notification,
+        templates::send_template_notification::<Wry>,
+        runner::list_allowed_commands,
+        runner::run_command::<Wry>,
+        runner::cancel_command,
+        manager::spawn_process::<Wry>,
+        manager::list_processes,
+        manager::get_process_output,
+        manager::restart_process::<Wry>,
+        manager::kill_process,
+        links::open_external::<Wry>,
+        files::open_file_dialog::<Wry>,
+        files::save_file_dialog::<Wry>,
+        files::list_recent_files,
+        files::clear_recent_files::<Wry>,
+        documents::new_document,
+        documents::open_document::<Wry>,
+        documents::list_documents,
+        documents::get_document,
+        documents::edit_document,
+        documents::save_document::<Wry>,
+        documents::save_document_as::<Wry>,
+        documents::revert_document,
+        documents::close_document,
+        windows::open_window::<Wry>,
+        windows::list_windows,
+        notifications::list_notifications,
+        notifications::list_notification_groups,
+        notifications::mark_notifications_read::<Wry>,
+        contacts::create_contact,
+        contacts::get_contact,
+        contacts::list_contacts,
+        contacts::update_contact,
+        contacts::delete_contact,
+        greetings::list_greetings,
+        greetings::search_greetings,
+        greetings::delete_greeting,
+        greetings::clear_history,
+    ])
+}
+
+/// Typed wrappers for every command and the types they take and return,
+/// checked in at [`BINDINGS_FILE`]. Commands resolve to a `Result` whose
+/// error is an `AppError`, rather than throwing.
+pub fn typescript() -> AppResult<String> {
+    builder::<Wry>()
+        .export_str(
+            Typescript::default()
+                .bigint(BigIntExportBehavior::Number)
+                .header(HEADER),
+        )
+        .map_err(|err| AppError::new(ErrorCode::Internal, err.to_string()))
+}
+
+const HEADER: &str =
+    "// From src-tauri/src/bindings.rs; regenerate with `cargo run --bin export_types`.
+// @ts-nocheck
+/* eslint-disable */";
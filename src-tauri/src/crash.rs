@@ -0,0 +1,334 @@
+use std::backtrace::Backtrace;
+use std::fs;
+use std::panic::{self, PanicHookInfo};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use chrono::{DateTime, Utc};
+use fluent_bundle::FluentArgs;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Manager, Runtime, State};
+use tauri_plugin_dialog::{
+    DialogExt, MessageDialogButtons, MessageDialogKind, MessageDialogResult,
+};
+
+use crate::error::{AppError, AppResult, ErrorCode};
+use crate::i18n::I18n;
+use crate::logging::{LogEntry, Logging};
+use crate::windows::WindowManager;
+
+/// Subdirectory of the app log directory that holds crash reports.
+pub const CRASH_DIR: &str = "crashes";
+
+/// How many of the most recent log entries a report includes.
+pub const RECENT_LOG_ENTRIES: usize = 50;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct OsInfo {
+    pub name: String,
+    pub family: String,
+    pub arch: String,
+}
+
+impl OsInfo {
+    pub fn current() -> Self {
+        Self {
+            name: std::env::consts::OS.into(),
+            family: std::env::consts::FAMILY.into(),
+            arch: std::env::consts::ARCH.into(),
+        }
+    }
+}
+
+/// Everything recorded about a panic. Written as `<id>.json` to the crash
+/// directory, where it stays until saved or discarded.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReport {
+    pub id: String,
+    pub created_at: DateTime<Utc>,
+    pub app_version: String,
+    pub tauri_version: String,
+    pub os: OsInfo,
+    /// Name of the panicking thread, if it had one.
+    pub thread: Option<String>,
+    /// Whether that was the main thread, so the panic ended the app.
+    /// Earlier versions only reported those.
+    #[serde(default = "from_main_thread")]
+    pub main_thread: bool,
+    pub message: String,
+    /// `file:line:column` of the panic.
+    pub location: Option<String>,
+    pub backtrace: String,
+    /// Empty if another thread was logging at the time.
+    pub recent_logs: Vec<LogEntry>,
+    /// Labels of the windows open at the time; empty if one was just
+    /// opening or closing.
+    pub windows: Vec<String>,
+}
+
+fn from_main_thread() -> bool {
+    true
+}
+
+/// Tells apart reports from panics in the same millisecond.
+static REPORTS: AtomicU64 = AtomicU64::new(0);
+
+impl CrashReport {
+    /// A report for a panic happening now on the main thread, without logs
+    /// or windows.
+    pub fn new(app_version: &str, message: String, location: Option<String>) -> Self {
+        let created_at = Utc::now();
+        Self {
+            id: format!(
+                "{}-{}-{}",
+                created_at.format("%Y%m%dT%H%M%S%.3fZ"),
+                std::process::id(),
+                REPORTS.fetch_add(1, Ordering::Relaxed)
+            ),
+            created_at,
+            app_version: app_version.into(),
+            tauri_version: tauri::VERSION.into(),
+            os: OsInfo::current(),
+            thread: std::thread::current().name().map(Into::into),
+            main_thread: true,
+            message,
+            location,
+            backtrace: Backtrace::force_capture().to_string(),
+            recent_logs: Vec::new(),
+            windows: Vec::new(),
+        }
+    }
+}
+
+/// The crash directory, held in managed state.
+#[derive(Debug, Clone)]
+pub struct CrashReporter {
+    dir: PathBuf,
+}
+
+impl CrashReporter {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn write(&self, report: &CrashReport) -> AppResult<PathBuf> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.dir.join(format!("{}.json", report.id));
+        fs::write(&path, serde_json::to_vec_pretty(report)?)?;
+        Ok(path)
+    }
+
+    /// Reports left by earlier sessions, oldest first. Files that cannot be
+    /// read back are skipped.
+    pub fn pending(&self) -> AppResult<Vec<CrashReport>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut reports = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            let report = fs::read(&path).map_err(AppError::from).and_then(|bytes| {
+                serde_json::from_slice::<CrashReport>(&bytes).map_err(AppError::from)
+            });
+            match report {
+                Ok(report) => reports.push(report),
+                Err(err) => {
+                    tracing::warn!(path = %path.display(), "skipping crash report: {err}")
+                }
+            }
+        }
+        reports.sort_by(|a, b| (a.created_at, &a.id).cmp(&(b.created_at, &b.id)));
+        Ok(reports)
+    }
+
+    /// Copies report `id` into `dir` and removes it from the pending list.
+    pub fn save_to(&self, id: &str, dir: &Path) -> AppResult<PathBuf> {
+        let dest = dir.join(format!("crash-{id}.json"));
+        fs::copy(self.path_of(id)?, &dest)?;
+        self.discard(id)?;
+        Ok(dest)
+    }
+
+    pub fn discard(&self, id: &str) -> AppResult<()> {
+        fs::remove_file(self.path_of(id)?)?;
+        Ok(())
+    }
+
+    fn path_of(&self, id: &str) -> AppResult<PathBuf> {
+        // Ids come from the frontend, so they must not be able to name a file
+        // outside the crash directory.
+        let valid = !id.is_empty()
+            && id
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '.'))
+            && !id.starts_with('.');
+        if !valid {
+            return Err(AppError::new(
+                ErrorCode::InvalidInput,
+                format!("invalid crash report id `{id}`"),
+            ));
+        }
+
+        let path = self.dir.join(format!("{id}.json"));
+        if !path.exists() {
+            return Err(AppError::new(
+                ErrorCode::NotFound,
+                format!("crash report {id} does not exist"),
+            )
+            .with_details(serde_json::json!({ "id": id })));
+        }
+        Ok(path)
+    }
+}
+
+/// Installs a process-wide panic hook that writes a [`CrashReport`] for `app`
+/// before running the previously installed hook. The report's logs and
+/// windows are read without waiting for their locks, since the panicking
+/// thread may hold them, and the report is written before the panic is
+/// logged, in case logging blocks.
+///
+/// Every panic is reported, including those on other threads, such as one in
+/// an async task, that are caught where they happen and so do not end the
+/// app. The thread installing the hook counts as the main thread.
+pub fn install_panic_hook<R: Runtime>(app: &AppHandle<R>) {
+    let app = app.clone();
+    let main = std::thread::current().id();
+    let version = app.package_info().version.to_string();
+    let reporter = app
+        .try_state::<CrashReporter>()
+        .map(|state| state.inner().clone());
+    let recent = app
+        .try_state::<Logging>()
+        .map(|logging| logging.recent().clone());
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let mut report = report_for(&version, info);
+        report.main_thread = std::thread::current().id() == main;
+        if let Some(recent) = &recent {
+            report.recent_logs = recent.try_entries().unwrap_or_default();
+        }
+        if let Some(windows) = app.try_state::<WindowManager>() {
+            report.windows = windows.try_labels().unwrap_or_default();
+        }
+        if let Some(reporter) = &reporter {
+            if let Err(err) = reporter.write(&report) {
+                eprintln!("could not write crash report: {err}");
+            }
+        }
+        tracing::error!(
+            id = %report.id,
+            thread = report.thread.as_deref(),
+            main_thread = report.main_thread,
+            location = report.location.as_deref(),
+            "panic: {}",
+            report.message
+        );
+        previous(info);
+    }));
+}
+
+fn report_for(version: &str, info: &PanicHookInfo<'_>) -> CrashReport {
+    let location = info.location().map(|location| location.to_string());
+    CrashReport::new(version, message_of(info), location)
+}
+
+fn message_of(info: &PanicHookInfo<'_>) -> String {
+    let payload = info.payload();
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Box<dyn Any>".into())
+}
+
+/// Asks whether to save or discard the reports left by earlier sessions.
+/// Saved reports are copied to a folder the user picks. Reports are only
+/// removed once saved or explicitly discarded; closing the dialog or the
+/// folder picker keeps them for the next launch.
+pub fn prompt_pending<R: Runtime>(app: &AppHandle<R>) -> AppResult<()> {
+    let reporter = app.state::<CrashReporter>().inner().clone();
+    let pending = reporter.pending()?;
+    if pending.is_empty() {
+        return Ok(());
+    }
+
+    let i18n = app.state::<I18n>();
+    let mut args = FluentArgs::new();
+    args.set("count", pending.len());
+    let title = i18n.translate(None, "crash-prompt-title", None)?;
+    let message = i18n.translate(None, "crash-prompt-message", Some(&args))?;
+    let save = i18n.translate(None, "crash-save", None)?;
+    let discard = i18n.translate(None, "crash-discard", None)?;
+    let later = i18n.translate(None, "crash-later", None)?;
+
+    let handle = app.clone();
+    app.dialog()
+        .message(message)
+        .title(title)
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::YesNoCancelCustom(
+            save.clone(),
+            discard.clone(),
+            later,
+        ))
+        .show_with_result(move |choice| {
+            match choice {
+                MessageDialogResult::Yes => {}
+                MessageDialogResult::Custom(label) if label == save => {}
+                MessageDialogResult::No => return discard_all(&reporter, &pending),
+                MessageDialogResult::Custom(label) if label == discard => {
+                    return discard_all(&reporter, &pending)
+                }
+                _ => return,
+            }
+            handle.dialog().file().pick_folder(move |folder| {
+                let Some(folder) = folder.as_ref().and_then(|folder| folder.as_path()) else {
+                    return;
+                };
+                for report in &pending {
+                    match reporter.save_to(&report.id, folder) {
+                        Ok(path) => tracing::info!(path = %path.display(), "saved crash report"),
+                        Err(err) => {
+                            tracing::warn!(id = %report.id, "could not save crash report: {err}")
+                        }
+                    }
+                }
+            });
+        });
+    Ok(())
+}
+
+fn discard_all(reporter: &CrashReporter, reports: &[CrashReport]) {
+    for report in reports {
+        if let Err(err) = reporter.discard(&report.id) {
+            tracing::warn!(id = %report.id, "could not discard crash report: {err}");
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_pending_crash_reports(
+    reporter: State<'_, CrashReporter>,
+) -> AppResult<Vec<CrashReport>> {
+    reporter.pending()
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn discard_crash_report(reporter: State<'_, CrashReporter>, id: &str) -> AppResult<()> {
+    reporter.discard(id)
+}
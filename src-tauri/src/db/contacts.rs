@@ -0,0 +1,206 @@
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::State;
+
+use super::{escape_like, Database};
+use crate::error::{AppError, AppResult, ErrorCode};
+use crate::validation::{self, TextRule};
+
+const EMAIL: TextRule = TextRule::new(3, 254);
+const NOTES: TextRule = TextRule::new(0, 2000).allow_newlines();
+
+#[derive(Debug, Clone, PartialEq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Contact {
+    pub id: i64,
+    pub name: String,
+    pub email: Option<String>,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct NewContact {
+    pub name: String,
+    pub email: Option<String>,
+    pub notes: Option<String>,
+}
+
+/// Fields to change on an existing contact. `None` leaves a field as is; an
+/// empty string clears an optional field.
+#[derive(Debug, Clone, Default, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ContactPatch {
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub notes: Option<String>,
+}
+
+const COLUMNS: &str = "id, name, email, notes, created_at, updated_at";
+
+fn from_row(row: &Row<'_>) -> rusqlite::Result<Contact> {
+    Ok(Contact {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        email: row.get(2)?,
+        notes: row.get(3)?,
+        created_at: row.get(4)?,
+        updated_at: row.get(5)?,
+    })
+}
+
+fn not_found(id: i64) -> AppError {
+    AppError::new(ErrorCode::NotFound, format!("contact {id} does not exist"))
+        .with_details(serde_json::json!({ "id": id }))
+}
+
+fn clean_email(email: &str) -> AppResult<Option<String>> {
+    if email.trim().is_empty() {
+        return Ok(None);
+    }
+    let email = EMAIL.apply("email", email)?;
+    if !email.contains('@') {
+        return Err(
+            AppError::new(ErrorCode::InvalidInput, "`email` must be an email address")
+                .with_details(serde_json::json!({ "field": "email", "reason": "invalid_email" })),
+        );
+    }
+    Ok(Some(email))
+}
+
+fn clean_notes(notes: &str) -> AppResult<Option<String>> {
+    let notes = NOTES.apply("notes", notes)?;
+    Ok((!notes.is_empty()).then_some(notes))
+}
+
+pub fn insert(conn: &Connection, contact: NewContact) -> AppResult<Contact> {
+    let name = validation::NAME.apply("name", &contact.name)?;
+    let email = contact
+        .email
+        .as_deref()
+        .map(clean_email)
+        .transpose()?
+        .flatten();
+    let notes = contact
+        .notes
+        .as_deref()
+        .map(clean_notes)
+        .transpose()?
+        .flatten();
+    let now = Utc::now();
+
+    conn.execute(
+        "INSERT INTO contacts (name, email, notes, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?4)",
+        params![name, email, notes, now],
+    )?;
+    get(conn, conn.last_insert_rowid())
+}
+
+pub fn find(conn: &Connection, id: i64) -> AppResult<Option<Contact>> {
+    Ok(conn
+        .query_row(
+            &format!("SELECT {COLUMNS} FROM contacts WHERE id = ?1"),
+            [id],
+            from_row,
+        )
+        .optional()?)
+}
+
+pub fn get(conn: &Connection, id: i64) -> AppResult<Contact> {
+    find(conn, id)?.ok_or_else(|| not_found(id))
+}
+
+/// Contacts ordered by name, optionally restricted to names containing
+/// `query` (case-insensitive).
+pub fn list(conn: &Connection, query: Option<&str>) -> AppResult<Vec<Contact>> {
+    let pattern = query.map(|query| format!("%{}%", escape_like(query.trim())));
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {COLUMNS} FROM contacts
+         WHERE ?1 IS NULL OR name LIKE ?1 ESCAPE '\\'
+         ORDER BY name COLLATE NOCASE, id"
+    ))?;
+    let contacts = stmt
+        .query_map([pattern], from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(contacts)
+}
+
+pub fn update(conn: &Connection, id: i64, patch: ContactPatch) -> AppResult<Contact> {
+    let mut contact = get(conn, id)?;
+    if let Some(name) = patch.name {
+        contact.name = validation::NAME.apply("name", &name)?;
+    }
+    if let Some(email) = patch.email {
+        contact.email = clean_email(&email)?;
+    }
+    if let Some(notes) = patch.notes {
+        contact.notes = clean_notes(&notes)?;
+    }
+    contact.updated_at = Utc::now();
+
+    conn.execute(
+        "UPDATE contacts SET name = ?2, email = ?3, notes = ?4, updated_at = ?5 WHERE id = ?1",
+        params![
+            id,
+            contact.name,
+            contact.email,
+            contact.notes,
+            contact.updated_at
+        ],
+    )?;
+    Ok(contact)
+}
+
+pub fn delete(conn: &Connection, id: i64) -> AppResult<()> {
+    match conn.execute("DELETE FROM contacts WHERE id = ?1", [id])? {
+        0 => Err(not_found(id)),
+        _ => Ok(()),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn create_contact(db: State<'_, Database>, contact: NewContact) -> AppResult<Contact> {
+    let conn = db.conn()?;
+    insert(&conn, contact)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_contact(db: State<'_, Database>, id: i64) -> AppResult<Contact> {
+    let conn = db.conn()?;
+    get(&conn, id)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_contacts(
+    db: State<'_, Database>,
+    query: Option<String>,
+) -> AppResult<Vec<Contact>> {
+    let conn = db.conn()?;
+    list(&conn, query.as_deref())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn update_contact(
+    db: State<'_, Database>,
+    id: i64,
+    patch: ContactPatch,
+) -> AppResult<Contact> {
+    let conn = db.conn()?;
+    update(&conn, id, patch)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_contact(db: State<'_, Database>, id: i64) -> AppResult<()> {
+    let conn = db.conn()?;
+    delete(&conn, id)
+}
@@ -0,0 +1,241 @@
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::{params, Connection, Row};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::State;
+
+use super::{escape_like, Database};
+use crate::error::{AppError, AppResult, ErrorCode};
+use crate::settings::{HistorySettings, SettingsStore};
+
+pub const DEFAULT_PAGE_SIZE: u32 = 50;
+pub const MAX_PAGE_SIZE: u32 = 500;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Greeting {
+    pub id: i64,
+    pub name: String,
+    pub locale: String,
+    /// Label of the window the greeting was sent from.
+    pub window: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewGreeting<'a> {
+    pub name: &'a str,
+    pub locale: &'a str,
+    pub window: &'a str,
+}
+
+/// Filters and paging for [`list`]. Every filter is optional.
+#[derive(Debug, Clone, Default, Deserialize, Type)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GreetingQuery {
+    pub offset: u32,
+    /// Defaults to [`DEFAULT_PAGE_SIZE`], capped at [`MAX_PAGE_SIZE`].
+    pub limit: Option<u32>,
+    /// Case-insensitive name prefix.
+    pub name: Option<String>,
+    pub locale: Option<String>,
+    pub window: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct GreetingPage {
+    pub items: Vec<Greeting>,
+    /// Number of greetings matching the filters, across all pages.
+    pub total: u64,
+    pub offset: u32,
+    pub limit: u32,
+}
+
+const COLUMNS: &str = "id, name, locale, window, created_at";
+
+/// Shared by `list` and its count query; see [`GreetingQuery`].
+const FILTER: &str = "(?1 IS NULL OR name LIKE ?1 ESCAPE '\\')
+    AND (?2 IS NULL OR locale = ?2)
+    AND (?3 IS NULL OR window = ?3)
+    AND (?4 IS NULL OR created_at >= ?4)
+    AND (?5 IS NULL OR created_at < ?5)";
+
+fn from_row(row: &Row<'_>) -> rusqlite::Result<Greeting> {
+    Ok(Greeting {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        locale: row.get(2)?,
+        window: row.get(3)?,
+        created_at: row.get(4)?,
+    })
+}
+
+fn prefix_pattern(prefix: &str) -> String {
+    format!("{}%", escape_like(prefix.trim()))
+}
+
+/// Stores a greeting sent at `at`, then prunes history per `retention`.
+pub fn record(
+    conn: &Connection,
+    greeting: NewGreeting<'_>,
+    at: DateTime<Utc>,
+    retention: &HistorySettings,
+) -> AppResult<Greeting> {
+    conn.execute(
+        "INSERT INTO greetings (name, locale, window, created_at) VALUES (?1, ?2, ?3, ?4)",
+        params![greeting.name, greeting.locale, greeting.window, at],
+    )?;
+    let id = conn.last_insert_rowid();
+    prune(conn, retention, at)?;
+
+    Ok(Greeting {
+        id,
+        name: greeting.name.to_owned(),
+        locale: greeting.locale.to_owned(),
+        window: greeting.window.to_owned(),
+        created_at: at,
+    })
+}
+
+/// Deletes greetings beyond the configured age and count. Returns how many
+/// were removed.
+pub fn prune(
+    conn: &Connection,
+    retention: &HistorySettings,
+    now: DateTime<Utc>,
+) -> AppResult<usize> {
+    let mut removed = 0;
+    if let Some(days) = retention.max_age_days {
+        let cutoff = now - Duration::days(days.into());
+        removed += conn.execute("DELETE FROM greetings WHERE created_at < ?1", [cutoff])?;
+    }
+    removed += conn.execute(
+        "DELETE FROM greetings WHERE id NOT IN (
+             SELECT id FROM greetings ORDER BY created_at DESC, id DESC LIMIT ?1
+         )",
+        [retention.max_entries],
+    )?;
+    Ok(removed)
+}
+
+/// Greetings matching `query`, newest first.
+pub fn list(conn: &Connection, query: &GreetingQuery) -> AppResult<GreetingPage> {
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+    let name = query.name.as_deref().map(prefix_pattern);
+
+    let total = conn.query_row(
+        &format!("SELECT COUNT(*) FROM greetings WHERE {FILTER}"),
+        params![name, query.locale, query.window, query.since, query.until],
+        |row| row.get(0),
+    )?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {COLUMNS} FROM greetings WHERE {FILTER}
+         ORDER BY created_at DESC, id DESC
+         LIMIT ?6 OFFSET ?7"
+    ))?;
+    let items = stmt
+        .query_map(
+            params![
+                name,
+                query.locale,
+                query.window,
+                query.since,
+                query.until,
+                limit,
+                query.offset,
+            ],
+            from_row,
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(GreetingPage {
+        items,
+        total,
+        offset: query.offset,
+        limit,
+    })
+}
+
+/// Most recent greetings whose name starts with `prefix`.
+pub fn search(conn: &Connection, prefix: &str, limit: Option<u32>) -> AppResult<Vec<Greeting>> {
+    let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {COLUMNS} FROM greetings
+         WHERE name LIKE ?1 ESCAPE '\\'
+         ORDER BY created_at DESC, id DESC
+         LIMIT ?2"
+    ))?;
+    let greetings = stmt
+        .query_map(params![prefix_pattern(prefix), limit], from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(greetings)
+}
+
+pub fn delete(conn: &Connection, id: i64) -> AppResult<()> {
+    match conn.execute("DELETE FROM greetings WHERE id = ?1", [id])? {
+        0 => Err(
+            AppError::new(ErrorCode::NotFound, format!("greeting {id} does not exist"))
+                .with_details(serde_json::json!({ "id": id })),
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// Removes all history. Returns how many greetings were deleted.
+pub fn clear(conn: &Connection) -> AppResult<usize> {
+    Ok(conn.execute("DELETE FROM greetings", [])?)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_greetings(
+    db: State<'_, Database>,
+    query: Option<GreetingQuery>,
+) -> AppResult<GreetingPage> {
+    let conn = db.conn()?;
+    list(&conn, &query.unwrap_or_default())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn search_greetings(
+    db: State<'_, Database>,
+    prefix: String,
+    limit: Option<u32>,
+) -> AppResult<Vec<Greeting>> {
+    let conn = db.conn()?;
+    search(&conn, &prefix, limit)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_greeting(db: State<'_, Database>, id: i64) -> AppResult<()> {
+    let conn = db.conn()?;
+    delete(&conn, id)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn clear_history(db: State<'_, Database>) -> AppResult<usize> {
+    let conn = db.conn()?;
+    clear(&conn)
+}
+
+/// Prunes history per the current settings. Run at startup and whenever the
+/// settings change it, so a lowered limit takes effect before the next
+/// greeting is recorded.
+pub fn apply_retention(db: &Database, settings: &SettingsStore) -> AppResult<usize> {
+    let conn = db.conn()?;
+    let removed = prune(&conn, &settings.get().history, Utc::now())?;
+    if removed > 0 {
+        tracing::info!(removed, "pruned greeting history");
+    }
+    Ok(removed)
+}
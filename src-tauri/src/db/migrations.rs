@@ -0,0 +1,102 @@
+use rusqlite::Connection;
+
+use crate::error::{AppError, AppResult, ErrorCode};
+
+pub struct Migration {
+    pub version: u32,
+    pub name: &'static str,
+    pub sql: &'static str,
+}
+
+/// Schema migrations, applied in order. The database's `user_version` pragma
+/// records the last one applied, so entries must never be edited or
+/// reordered once released.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "create_contacts",
+        sql: include_str!("../../migrations/0001_create_contacts.sql"),
+    },
+    Migration {
+        version: 2,
+        name: "create_greetings",
+        sql: include_str!("../../migrations/0002_create_greetings.sql"),
+    },
+    Migration {
+        version: 3,
+        name: "create_scheduled_notifications",
+        sql: include_str!("../../migrations/0003_create_scheduled_notifications.sql"),
+    },
+    Migration {
+        version: 4,
+        name: "create_notifications",
+        sql: include_str!("../../migrations/0004_create_notifications.sql"),
+    },
+    Migration {
+        version: 5,
+        name: "create_recent_files",
+        sql: include_str!("../../migrations/0005_create_recent_files.sql"),
+    },
+    Migration {
+        version: 6,
+        name: "add_notification_read_at",
+        sql: include_str!("../../migrations/0006_add_notification_read_at.sql"),
+    },
+    Migration {
+        version: 7,
+        name: "add_digest_retries",
+        sql: include_str!("../../migrations/0007_add_digest_retries.sql"),
+    },
+    Migration {
+        version: 8,
+        name: "add_schedule_errors",
+        sql: include_str!("../../migrations/0008_add_schedule_errors.sql"),
+    },
+];
+
+pub fn schema_version(conn: &Connection) -> AppResult<u32> {
+    Ok(conn.pragma_query_value(None, "user_version", |row| row.get(0))?)
+}
+
+/// Applies every migration newer than the database's schema version, each in
+/// its own transaction. Returns the number applied.
+pub fn run(conn: &mut Connection) -> AppResult<usize> {
+    run_migrations(conn, MIGRATIONS)
+}
+
+pub fn run_migrations(conn: &mut Connection, migrations: &[Migration]) -> AppResult<usize> {
+    let current = schema_version(conn)?;
+    let latest = migrations.last().map_or(0, |migration| migration.version);
+    if current > latest {
+        return Err(AppError::new(
+            ErrorCode::Database,
+            format!("database schema version {current} is newer than this app supports ({latest})"),
+        ));
+    }
+
+    let mut applied = 0;
+    for migration in migrations
+        .iter()
+        .filter(|migration| migration.version > current)
+    {
+        let tx = conn.transaction()?;
+        tx.execute_batch(migration.sql).map_err(|err| {
+            AppError::new(
+                ErrorCode::Database,
+                format!(
+                    "migration {} ({}) failed: {err}",
+                    migration.version, migration.name
+                ),
+            )
+        })?;
+        tx.pragma_update(None, "user_version", migration.version)?;
+        tx.commit()?;
+        tracing::info!(
+            version = migration.version,
+            name = migration.name,
+            "applied database migration"
+        );
+        applied += 1;
+    }
+    Ok(applied)
+}
@@ -0,0 +1,80 @@
+pub mod contacts;
+pub mod greetings;
+pub mod migrations;
+pub mod notifications;
+pub mod recent_files;
+pub mod schedules;
+
+use std::path::Path;
+use std::time::Duration;
+
+use r2d2::PooledConnection;
+use r2d2_sqlite::SqliteConnectionManager;
+
+use crate::error::{AppError, AppResult, ErrorCode};
+
+pub const DATABASE_FILE: &str = "app.db";
+
+pub type Pool = r2d2::Pool<SqliteConnectionManager>;
+pub type Connection = PooledConnection<SqliteConnectionManager>;
+
+/// The SQLite connection pool, held in managed state.
+pub struct Database {
+    pool: Pool,
+}
+
+impl Database {
+    /// Opens (creating if needed) the database at `path` and brings its
+    /// schema up to date.
+    pub fn open(path: &Path) -> AppResult<Self> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let manager = SqliteConnectionManager::file(path).with_init(|conn| {
+            conn.busy_timeout(Duration::from_secs(5))?;
+            conn.execute_batch("PRAGMA journal_mode = WAL; PRAGMA foreign_keys = ON;")
+        });
+        Self::from_manager(manager)
+    }
+
+    /// Opens a private in-memory database, shared by every connection in the
+    /// pool. Used by tests.
+    pub fn open_in_memory() -> AppResult<Self> {
+        let manager = SqliteConnectionManager::memory()
+            .with_init(|conn| conn.execute_batch("PRAGMA foreign_keys = ON;"));
+        Self::from_manager(manager)
+    }
+
+    fn from_manager(manager: SqliteConnectionManager) -> AppResult<Self> {
+        let pool = r2d2::Pool::builder().max_size(4).build(manager)?;
+        let mut conn = pool.get()?;
+        migrations::run(&mut conn)?;
+        drop(conn);
+        Ok(Self { pool })
+    }
+
+    pub fn conn(&self) -> AppResult<Connection> {
+        Ok(self.pool.get()?)
+    }
+}
+
+/// Escapes `LIKE` wildcards so user input only ever matches literally. Use
+/// with `ESCAPE '\'`.
+pub(crate) fn escape_like(input: &str) -> String {
+    input
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+impl From<rusqlite::Error> for AppError {
+    fn from(err: rusqlite::Error) -> Self {
+        AppError::new(ErrorCode::Database, err.to_string())
+    }
+}
+
+impl From<r2d2::Error> for AppError {
+    fn from(err: r2d2::Error) -> Self {
+        AppError::new(ErrorCode::Database, err.to_string())
+    }
+}
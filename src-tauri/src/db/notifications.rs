@@ -0,0 +1,382 @@
+use chrono::{DateTime, Utc};
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Runtime, State};
+
+use super::Database;
+use crate::error::AppResult;
+use crate::events::AppEvent;
+use crate::notifications::Notification;
+
+pub const DEFAULT_PAGE_SIZE: u32 = 50;
+pub const MAX_PAGE_SIZE: u32 = 500;
+
+/// What happened to a notification the app tried to show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    Delivered,
+    /// The notification plugin reported an error; see `error`.
+    Failed,
+    /// Held back by quiet hours, waiting for the next digest.
+    Deferred,
+    /// Dropped by quiet hours.
+    Suppressed,
+    /// Dropped because the user turned its category off.
+    Disabled,
+    /// Deferred, then shown as part of the digest `digest_id`.
+    Digested,
+}
+
+impl DeliveryStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Delivered => "delivered",
+            Self::Failed => "failed",
+            Self::Deferred => "deferred",
+            Self::Suppressed => "suppressed",
+            Self::Disabled => "disabled",
+            Self::Digested => "digested",
+        }
+    }
+}
+
+impl ToSql for DeliveryStatus {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(self.as_str().into())
+    }
+}
+
+impl FromSql for DeliveryStatus {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let status = value.as_str()?;
+        [
+            Self::Delivered,
+            Self::Failed,
+            Self::Deferred,
+            Self::Suppressed,
+            Self::Disabled,
+            Self::Digested,
+        ]
+        .into_iter()
+        .find(|candidate| candidate.as_str() == status)
+        .ok_or_else(|| FromSqlError::Other(format!("unknown delivery status `{status}`").into()))
+    }
+}
+
+/// A notification in the history.
+#[derive(Debug, Clone, PartialEq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationRecord {
+    pub id: i64,
+    #[serde(flatten)]
+    pub notification: Notification,
+    pub status: DeliveryStatus,
+    pub error: Option<String>,
+    pub digest_id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Filters and paging for [`list`]. Every filter is optional.
+#[derive(Debug, Clone, Default, Deserialize, Type)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NotificationQuery {
+    pub offset: u32,
+    /// Defaults to [`DEFAULT_PAGE_SIZE`], capped at [`MAX_PAGE_SIZE`].
+    pub limit: Option<u32>,
+    pub category: Option<String>,
+    pub status: Option<DeliveryStatus>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationPage {
+    pub items: Vec<NotificationRecord>,
+    /// Number of notifications matching the filters, across all pages.
+    pub total: u64,
+    pub offset: u32,
+    pub limit: u32,
+}
+
+/// Notifications sharing a category, summarized for a grouped view.
+#[derive(Debug, Clone, PartialEq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationGroup {
+    /// `None` for notifications sent without a category.
+    pub category: Option<String>,
+    pub count: u64,
+    pub latest_at: DateTime<Utc>,
+}
+
+const COLUMNS: &str = "id, title, body, category, urgent, status, error, digest_id, created_at";
+
+/// Shared by `list` and its count query; see [`NotificationQuery`].
+const FILTER: &str = "(?1 IS NULL OR category = ?1)
+    AND (?2 IS NULL OR status = ?2)
+    AND (?3 IS NULL OR created_at >= ?3)
+    AND (?4 IS NULL OR created_at < ?4)";
+
+fn from_row(row: &Row<'_>) -> rusqlite::Result<NotificationRecord> {
+    Ok(NotificationRecord {
+        id: row.get(0)?,
+        notification: Notification {
+            title: row.get(1)?,
+            body: row.get(2)?,
+            category: row.get(3)?,
+            urgent: row.get(4)?,
+        },
+        status: row.get(5)?,
+        error: row.get(6)?,
+        digest_id: row.get(7)?,
+        created_at: row.get(8)?,
+    })
+}
+
+/// Adds `notification` to the history.
+pub fn record(
+    conn: &Connection,
+    notification: &Notification,
+    status: DeliveryStatus,
+    error: Option<&str>,
+    at: DateTime<Utc>,
+) -> AppResult<NotificationRecord> {
+    conn.execute(
+        "INSERT INTO notifications (title, body, category, urgent, status, error, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            notification.title,
+            notification.body,
+            notification.category,
+            notification.urgent,
+            status,
+            error,
+            at,
+        ],
+    )?;
+    Ok(NotificationRecord {
+        id: conn.last_insert_rowid(),
+        notification: notification.clone(),
+        status,
+        error: error.map(Into::into),
+        digest_id: None,
+        created_at: at,
+    })
+}
+
+/// Notifications matching `query`, newest first.
+pub fn list(conn: &Connection, query: &NotificationQuery) -> AppResult<NotificationPage> {
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+
+    let total = conn.query_row(
+        &format!("SELECT COUNT(*) FROM notifications WHERE {FILTER}"),
+        params![query.category, query.status, query.since, query.until],
+        |row| row.get(0),
+    )?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {COLUMNS} FROM notifications WHERE {FILTER}
+         ORDER BY created_at DESC, id DESC
+         LIMIT ?5 OFFSET ?6"
+    ))?;
+    let items = stmt
+        .query_map(
+            params![
+                query.category,
+                query.status,
+                query.since,
+                query.until,
+                limit,
+                query.offset,
+            ],
+            from_row,
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(NotificationPage {
+        items,
+        total,
+        offset: query.offset,
+        limit,
+    })
+}
+
+/// One entry per category, most recently active first.
+pub fn groups(conn: &Connection) -> AppResult<Vec<NotificationGroup>> {
+    let mut stmt = conn.prepare(
+        "SELECT category, COUNT(*), MAX(created_at) FROM notifications
+         GROUP BY category
+         ORDER BY MAX(created_at) DESC",
+    )?;
+    let groups = stmt
+        .query_map([], |row| {
+            Ok(NotificationGroup {
+                category: row.get(0)?,
+                count: row.get(1)?,
+                latest_at: row.get(2)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(groups)
+}
+
+/// Notifications waiting for a digest, oldest first.
+pub fn deferred(conn: &Connection) -> AppResult<Vec<NotificationRecord>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {COLUMNS} FROM notifications WHERE status = ?1 ORDER BY created_at, id"
+    ))?;
+    let records = stmt
+        .query_map([DeliveryStatus::Deferred], from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(records)
+}
+
+/// Marks the notifications `ids` as shown in digest `digest_id`.
+pub fn mark_digested(conn: &Connection, ids: &[i64], digest_id: i64) -> AppResult<usize> {
+    if ids.is_empty() {
+        return Ok(0);
+    }
+    let placeholders: Vec<String> = (2..ids.len() + 2).map(|n| format!("?{n}")).collect();
+    let sql = format!(
+        "UPDATE notifications SET status = 'digested', digest_id = ?1 WHERE id IN ({})",
+        placeholders.join(", ")
+    );
+    let values = std::iter::once(digest_id).chain(ids.iter().copied());
+    Ok(conn.execute(&sql, params_from_iter(values))?)
+}
+
+/// A digest that could not be shown, waiting to be tried again.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailedDigest {
+    pub id: i64,
+    /// How many times showing it failed.
+    pub attempts: u32,
+    pub retry_at: DateTime<Utc>,
+}
+
+/// The digest waiting to be tried again, if any.
+pub fn failed_digest(conn: &Connection) -> AppResult<Option<FailedDigest>> {
+    Ok(conn
+        .query_row(
+            "SELECT id, attempts, retry_at FROM notifications
+             WHERE status = ?1 AND retry_at IS NOT NULL
+             ORDER BY id DESC LIMIT 1",
+            [DeliveryStatus::Failed],
+            |row| {
+                Ok(FailedDigest {
+                    id: row.get(0)?,
+                    attempts: row.get(1)?,
+                    retry_at: row.get(2)?,
+                })
+            },
+        )
+        .optional()?)
+}
+
+/// Reuses the failed digest `digest_id` for another attempt to show
+/// `notification`, which lists what is deferred now.
+pub fn retry_digest(
+    conn: &Connection,
+    digest_id: i64,
+    notification: &Notification,
+    status: DeliveryStatus,
+    at: DateTime<Utc>,
+) -> AppResult<NotificationRecord> {
+    conn.execute(
+        "UPDATE notifications
+         SET title = ?2, body = ?3, status = ?4, error = NULL, retry_at = NULL, created_at = ?5
+         WHERE id = ?1",
+        params![digest_id, notification.title, notification.body, status, at],
+    )?;
+    Ok(NotificationRecord {
+        id: digest_id,
+        notification: notification.clone(),
+        status,
+        error: None,
+        digest_id: None,
+        created_at: at,
+    })
+}
+
+/// Records that digest `digest_id` could not be shown because of `error`,
+/// and defers the notifications it held again so the digest can be tried
+/// again at `retry_at`.
+pub fn fail_digest(
+    conn: &Connection,
+    digest_id: i64,
+    error: Option<&str>,
+    retry_at: DateTime<Utc>,
+) -> AppResult<()> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "UPDATE notifications SET status = ?2, digest_id = NULL WHERE digest_id = ?1",
+        params![digest_id, DeliveryStatus::Deferred],
+    )?;
+    tx.execute(
+        "UPDATE notifications
+         SET status = ?2, error = ?3, attempts = attempts + 1, retry_at = ?4
+         WHERE id = ?1",
+        params![digest_id, DeliveryStatus::Failed, error, retry_at],
+    )?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// Delivered notifications the user has not seen in the app yet.
+pub fn unread_count(conn: &Connection) -> AppResult<u64> {
+    Ok(conn.query_row(
+        "SELECT COUNT(*) FROM notifications WHERE status = ?1 AND read_at IS NULL",
+        [DeliveryStatus::Delivered],
+        |row| row.get(0),
+    )?)
+}
+
+/// Marks every unread notification as read at `at`. Returns how many were
+/// unread.
+pub fn mark_read(conn: &Connection, at: DateTime<Utc>) -> AppResult<usize> {
+    Ok(conn.execute(
+        "UPDATE notifications SET read_at = ?1 WHERE status = ?2 AND read_at IS NULL",
+        params![at, DeliveryStatus::Delivered],
+    )?)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_notifications(
+    db: State<'_, Database>,
+    query: Option<NotificationQuery>,
+) -> AppResult<NotificationPage> {
+    let conn = db.conn()?;
+    list(&conn, &query.unwrap_or_default())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_notification_groups(
+    db: State<'_, Database>,
+) -> AppResult<Vec<NotificationGroup>> {
+    let conn = db.conn()?;
+    groups(&conn)
+}
+
+/// Marks every notification as seen. Returns how many were unread.
+#[tauri::command]
+#[specta::specta]
+pub async fn mark_notifications_read<R: Runtime>(
+    app: AppHandle<R>,
+    db: State<'_, Database>,
+) -> AppResult<usize> {
+    let conn = db.conn()?;
+    let marked = mark_read(&conn, Utc::now())?;
+    AppEvent::NotificationsChanged {
+        unread: unread_count(&conn)?,
+    }
+    .emit(&app)?;
+    Ok(marked)
+}
@@ -0,0 +1,81 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use specta::Type;
+
+use crate::error::{AppError, AppResult, ErrorCode};
+
+/// How many files the most-recently-used list keeps.
+pub const MAX_RECENT_FILES: u32 = 10;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentFile {
+    pub path: PathBuf,
+    pub opened_at: DateTime<Utc>,
+}
+
+/// `path` as stored. Paths are stored as text, and one that is not valid
+/// UTF-8 would come back as a different file, so those are refused.
+fn stored(path: &Path) -> AppResult<&str> {
+    path.to_str().ok_or_else(|| {
+        AppError::new(
+            ErrorCode::InvalidInput,
+            format!("`{}` is not valid UTF-8", path.display()),
+        )
+        .with_details(serde_json::json!({ "field": "path", "reason": "encoding" }))
+    })
+}
+
+/// Moves `path` to the top of the list, dropping the oldest entries beyond
+/// [`MAX_RECENT_FILES`]. Fails with `invalid_input` if `path` is not valid
+/// UTF-8.
+pub fn touch(conn: &Connection, path: &Path, at: DateTime<Utc>) -> AppResult<()> {
+    conn.execute(
+        "INSERT INTO recent_files (path, opened_at) VALUES (?1, ?2)
+         ON CONFLICT (path) DO UPDATE SET opened_at = excluded.opened_at",
+        params![stored(path)?, at],
+    )?;
+    conn.execute(
+        "DELETE FROM recent_files WHERE path NOT IN (
+             SELECT path FROM recent_files ORDER BY opened_at DESC, path LIMIT ?1
+         )",
+        [MAX_RECENT_FILES],
+    )?;
+    Ok(())
+}
+
+/// Recent files, most recent first. Files that no longer exist are dropped
+/// from the list.
+pub fn list(conn: &Connection) -> AppResult<Vec<RecentFile>> {
+    let mut stmt =
+        conn.prepare("SELECT path, opened_at FROM recent_files ORDER BY opened_at DESC, path")?;
+    let files = stmt
+        .query_map([], |row| {
+            Ok(RecentFile {
+                path: PathBuf::from(row.get::<_, String>(0)?),
+                opened_at: row.get(1)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let (present, missing): (Vec<_>, Vec<_>) =
+        files.into_iter().partition(|file| file.path.exists());
+    for file in &missing {
+        conn.execute(
+            "DELETE FROM recent_files WHERE path = ?1",
+            [stored(&file.path)?],
+        )?;
+    }
+    if !missing.is_empty() {
+        tracing::debug!(count = missing.len(), "pruned missing recent files");
+    }
+    Ok(present)
+}
+
+/// Empties the list, returning how many entries were removed.
+pub fn clear(conn: &Connection) -> AppResult<usize> {
+    Ok(conn.execute("DELETE FROM recent_files", [])?)
+}
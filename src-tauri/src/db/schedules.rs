@@ -0,0 +1,173 @@
+use chrono::{DateTime, Utc};
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::Serialize;
+
+use crate::error::{AppError, AppResult, ErrorCode};
+use crate::notifications::scheduler::Trigger;
+use crate::notifications::Notification;
+
+/// A notification waiting to be shown by the scheduler.
+#[derive(Debug, Clone, PartialEq, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledNotification {
+    pub id: i64,
+    #[serde(flatten)]
+    pub notification: Notification,
+    pub trigger: Trigger,
+    pub next_run: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub error: Option<String>,
+}
+
+const COLUMNS: &str = "id, title, body, category, urgent, trigger, next_run, created_at, error";
+
+fn from_row(row: &Row<'_>) -> rusqlite::Result<ScheduledNotification> {
+    let trigger: String = row.get(5)?;
+    Ok(ScheduledNotification {
+        id: row.get(0)?,
+        notification: Notification {
+            title: row.get(1)?,
+            body: row.get(2)?,
+            category: row.get(3)?,
+            urgent: row.get(4)?,
+        },
+        trigger: serde_json::from_str(&trigger)
+            .map_err(|err| rusqlite::Error::FromSqlConversionFailure(5, Type::Text, err.into()))?,
+        next_run: row.get(6)?,
+        created_at: row.get(7)?,
+        error: row.get(8)?,
+    })
+}
+
+fn not_found(id: i64) -> AppError {
+    AppError::new(
+        ErrorCode::NotFound,
+        format!("scheduled notification {id} does not exist"),
+    )
+    .with_details(serde_json::json!({ "id": id }))
+}
+
+pub fn insert(
+    conn: &Connection,
+    notification: &Notification,
+    trigger: &Trigger,
+    next_run: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> AppResult<ScheduledNotification> {
+    conn.execute(
+        "INSERT INTO scheduled_notifications
+             (title, body, category, urgent, trigger, next_run, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            notification.title,
+            notification.body,
+            notification.category,
+            notification.urgent,
+            serde_json::to_string(trigger)?,
+            next_run,
+            now,
+        ],
+    )?;
+    Ok(ScheduledNotification {
+        id: conn.last_insert_rowid(),
+        notification: notification.clone(),
+        trigger: trigger.clone(),
+        next_run,
+        created_at: now,
+        error: None,
+    })
+}
+
+pub fn find(conn: &Connection, id: i64) -> AppResult<Option<ScheduledNotification>> {
+    Ok(conn
+        .query_row(
+            &format!("SELECT {COLUMNS} FROM scheduled_notifications WHERE id = ?1"),
+            [id],
+            from_row,
+        )
+        .optional()?)
+}
+
+pub fn get(conn: &Connection, id: i64) -> AppResult<ScheduledNotification> {
+    find(conn, id)?.ok_or_else(|| not_found(id))
+}
+
+/// Every schedule, soonest first.
+pub fn list(conn: &Connection) -> AppResult<Vec<ScheduledNotification>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {COLUMNS} FROM scheduled_notifications ORDER BY next_run, id"
+    ))?;
+    let schedules = stmt
+        .query_map([], from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(schedules)
+}
+
+/// Schedules whose next run is at or before `now`, soonest first. Stopped
+/// schedules are never due.
+pub fn due(conn: &Connection, now: DateTime<Utc>) -> AppResult<Vec<ScheduledNotification>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {COLUMNS} FROM scheduled_notifications
+         WHERE error IS NULL AND next_run <= ?1
+         ORDER BY next_run, id"
+    ))?;
+    let schedules = stmt
+        .query_map([now], from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(schedules)
+}
+
+/// When the soonest schedule that is not stopped is due, if there is one.
+pub fn next_run(conn: &Connection) -> AppResult<Option<DateTime<Utc>>> {
+    Ok(conn.query_row(
+        "SELECT MIN(next_run) FROM scheduled_notifications WHERE error IS NULL",
+        [],
+        |row| row.get(0),
+    )?)
+}
+
+pub fn update_trigger(
+    conn: &Connection,
+    id: i64,
+    trigger: &Trigger,
+    next_run: DateTime<Utc>,
+) -> AppResult<()> {
+    match conn.execute(
+        "UPDATE scheduled_notifications SET trigger = ?2, next_run = ?3, error = NULL
+         WHERE id = ?1",
+        params![id, serde_json::to_string(trigger)?, next_run],
+    )? {
+        0 => Err(not_found(id)),
+        _ => Ok(()),
+    }
+}
+
+pub fn set_next_run(conn: &Connection, id: i64, next_run: DateTime<Utc>) -> AppResult<()> {
+    match conn.execute(
+        "UPDATE scheduled_notifications SET next_run = ?2 WHERE id = ?1",
+        params![id, next_run],
+    )? {
+        0 => Err(not_found(id)),
+        _ => Ok(()),
+    }
+}
+
+/// Stops schedule `id`, which could not be moved on because of `error`,
+/// until its trigger is replaced.
+pub fn stop(conn: &Connection, id: i64, error: &str) -> AppResult<()> {
+    match conn.execute(
+        "UPDATE scheduled_notifications SET error = ?2 WHERE id = ?1",
+        params![id, error],
+    )? {
+        0 => Err(not_found(id)),
+        _ => Ok(()),
+    }
+}
+
+pub fn delete(conn: &Connection, id: i64) -> AppResult<()> {
+    match conn.execute("DELETE FROM scheduled_notifications WHERE id = ?1", [id])? {
+        0 => Err(not_found(id)),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,679 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use fluent_bundle::FluentArgs;
+use serde::Serialize;
+use specta::Type;
+use tauri::{AppHandle, Manager, Runtime, State, Window, WindowEvent};
+use tauri_plugin_dialog::{
+    DialogExt, MessageDialogButtons, MessageDialogKind, MessageDialogResult,
+};
+
+use crate::db::recent_files;
+use crate::db::Database;
+use crate::error::{AppError, AppResult, ErrorCode};
+use crate::files::{self, OpenDialogOptions, SaveDialogOptions};
+use crate::i18n::I18n;
+use crate::windows::MAIN_WINDOW;
+
+/// How a document's text is stored on disk. Kept when saving, so a file is
+/// written back the way it was read.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Type)]
+pub enum Encoding {
+    #[default]
+    #[serde(rename = "utf-8")]
+    Utf8,
+    /// UTF-8 with a byte order mark.
+    #[serde(rename = "utf-8-bom")]
+    Utf8Bom,
+    #[serde(rename = "utf-16le")]
+    Utf16Le,
+    #[serde(rename = "utf-16be")]
+    Utf16Be,
+}
+
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+const UTF16LE_BOM: &[u8] = &[0xFF, 0xFE];
+const UTF16BE_BOM: &[u8] = &[0xFE, 0xFF];
+
+impl Encoding {
+    /// Detects the encoding from the byte order mark, assuming UTF-8 without
+    /// one, and decodes `bytes`.
+    pub fn decode(bytes: &[u8]) -> AppResult<(Self, String)> {
+        let invalid = |encoding: Self| {
+            AppError::new(
+                ErrorCode::InvalidInput,
+                format!("the file is not valid {}", encoding.name()),
+            )
+            .with_details(serde_json::json!({ "field": "path", "reason": "encoding" }))
+        };
+        let utf16 = |bytes: &[u8], encoding: Self, unit: fn([u8; 2]) -> u16| {
+            if !bytes.len().is_multiple_of(2) {
+                return Err(invalid(encoding));
+            }
+            let units: Vec<u16> = bytes
+                .chunks_exact(2)
+                .map(|pair| unit([pair[0], pair[1]]))
+                .collect();
+            String::from_utf16(&units)
+                .map(|text| (encoding, text))
+                .map_err(|_| invalid(encoding))
+        };
+
+        if let Some(rest) = bytes.strip_prefix(UTF8_BOM) {
+            let text = String::from_utf8(rest.to_vec()).map_err(|_| invalid(Self::Utf8Bom))?;
+            Ok((Self::Utf8Bom, text))
+        } else if let Some(rest) = bytes.strip_prefix(UTF16LE_BOM) {
+            utf16(rest, Self::Utf16Le, u16::from_le_bytes)
+        } else if let Some(rest) = bytes.strip_prefix(UTF16BE_BOM) {
+            utf16(rest, Self::Utf16Be, u16::from_be_bytes)
+        } else {
+            let text = String::from_utf8(bytes.to_vec()).map_err(|_| invalid(Self::Utf8))?;
+            Ok((Self::Utf8, text))
+        }
+    }
+
+    /// Encodes `text`, with a byte order mark unless this is plain UTF-8.
+    pub fn encode(self, text: &str) -> Vec<u8> {
+        match self {
+            Self::Utf8 => text.as_bytes().to_vec(),
+            Self::Utf8Bom => [UTF8_BOM, text.as_bytes()].concat(),
+            Self::Utf16Le => UTF16LE_BOM
+                .iter()
+                .copied()
+                .chain(text.encode_utf16().flat_map(u16::to_le_bytes))
+                .collect(),
+            Self::Utf16Be => UTF16BE_BOM
+                .iter()
+                .copied()
+                .chain(text.encode_utf16().flat_map(u16::to_be_bytes))
+                .collect(),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Utf8 => "UTF-8",
+            Self::Utf8Bom => "UTF-8 with BOM",
+            Self::Utf16Le => "UTF-16LE",
+            Self::Utf16Be => "UTF-16BE",
+        }
+    }
+}
+
+/// A text document open in the app.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Document {
+    pub id: u64,
+    /// `None` until the document is first saved.
+    pub path: Option<PathBuf>,
+    pub content: String,
+    pub encoding: Encoding,
+    /// Whether `content` has changes that are not on disk.
+    pub dirty: bool,
+    /// Modification time of the file when it was last read or written here.
+    pub modified_at: Option<DateTime<Utc>>,
+    /// Length of the file at that time; together with `modified_at` it tells
+    /// whether another program has changed the file since.
+    #[serde(skip)]
+    len: u64,
+}
+
+impl Document {
+    /// The file name, or `None` for an untitled document.
+    pub fn name(&self) -> Option<String> {
+        self.path
+            .as_deref()
+            .and_then(Path::file_name)
+            .map(|name| name.to_string_lossy().into_owned())
+    }
+
+    /// Records the file's current modification time and length.
+    fn stamp(&mut self, path: &Path) -> AppResult<()> {
+        let (modified_at, len) = disk_stamp(path)?;
+        self.modified_at = modified_at;
+        self.len = len;
+        Ok(())
+    }
+}
+
+fn disk_stamp(path: &Path) -> AppResult<(Option<DateTime<Utc>>, u64)> {
+    let metadata = fs::metadata(path)?;
+    Ok((metadata.modified().ok().map(DateTime::from), metadata.len()))
+}
+
+/// Told whenever a document is opened or closed, or gains or loses unsaved
+/// changes.
+pub type ChangeListener = Box<dyn Fn(&Documents) + Send + Sync>;
+
+/// The open documents, by id. Held in managed state.
+pub struct Documents {
+    next_id: AtomicU64,
+    open: Mutex<BTreeMap<u64, Document>>,
+    listener: Option<ChangeListener>,
+}
+
+impl Default for Documents {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Documents {
+    pub fn new() -> Self {
+        Self {
+            next_id: AtomicU64::new(1),
+            open: Mutex::new(BTreeMap::new()),
+            listener: None,
+        }
+    }
+
+    pub fn with_listener(mut self, listener: ChangeListener) -> Self {
+        self.listener = Some(listener);
+        self
+    }
+
+    fn changed(&self) {
+        if let Some(listener) = &self.listener {
+            listener(self);
+        }
+    }
+
+    /// Adds `document` under a fresh id, unless another document already
+    /// has its path, which is returned instead.
+    fn insert(&self, mut document: Document) -> Document {
+        let mut open = self.open.lock().unwrap();
+        if let Some(existing) = document.path.as_deref().and_then(|path| find(&open, path)) {
+            return existing.clone();
+        }
+        document.id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        open.insert(document.id, document.clone());
+        drop(open);
+        self.changed();
+        document
+    }
+
+    /// Starts an empty, untitled document.
+    pub fn create(&self) -> Document {
+        self.insert(Document {
+            id: 0,
+            path: None,
+            content: String::new(),
+            encoding: Encoding::default(),
+            dirty: false,
+            modified_at: None,
+            len: 0,
+        })
+    }
+
+    /// Reads the file at `path` into a new document, or returns the document
+    /// already open for it, however its path is spelled.
+    pub fn open(&self, path: &Path) -> AppResult<Document> {
+        let path = &canonical(path)?;
+        if let Some(open) = find(&self.open.lock().unwrap(), path) {
+            return Ok(open.clone());
+        }
+
+        let (modified_at, len) = disk_stamp(path)?;
+        let (encoding, content) = Encoding::decode(&fs::read(path)?)?;
+        // Opened by someone else meanwhile? Then that document wins.
+        Ok(self.insert(Document {
+            id: 0,
+            path: Some(path.to_owned()),
+            content,
+            encoding,
+            dirty: false,
+            modified_at,
+            len,
+        }))
+    }
+
+    /// Every open document, by id.
+    pub fn list(&self) -> Vec<Document> {
+        self.open.lock().unwrap().values().cloned().collect()
+    }
+
+    /// Open documents with unsaved changes.
+    pub fn dirty(&self) -> Vec<Document> {
+        self.list()
+            .into_iter()
+            .filter(|document| document.dirty)
+            .collect()
+    }
+
+    pub fn get(&self, id: u64) -> AppResult<Document> {
+        self.update(id, |_| Ok(()))
+    }
+
+    /// Replaces the content, e.g. after an edit in the frontend.
+    pub fn edit(&self, id: u64, content: String) -> AppResult<Document> {
+        self.update(id, |document| {
+            if document.content != content {
+                document.content = content;
+                document.dirty = true;
+            }
+            Ok(())
+        })
+    }
+
+    /// Whether the file has been changed or removed by another program since
+    /// it was last read or written here. Always `false` for untitled
+    /// documents.
+    pub fn modified_externally(&self, id: u64) -> AppResult<bool> {
+        let document = self.get(id)?;
+        let Some(path) = &document.path else {
+            return Ok(false);
+        };
+        match disk_stamp(path) {
+            Ok((modified_at, len)) => {
+                Ok(modified_at != document.modified_at || len != document.len)
+            }
+            Err(err) if err.code == ErrorCode::Io => Ok(true),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Writes the document to its file. Fails with `conflict` if another
+    /// program changed the file since it was read, unless `overwrite` is set.
+    pub fn save(&self, id: u64, overwrite: bool) -> AppResult<Document> {
+        let document = self.get(id)?;
+        let Some(path) = document.path.clone() else {
+            return Err(AppError::new(
+                ErrorCode::InvalidInput,
+                "an untitled document must be saved under a new name",
+            )
+            .with_details(serde_json::json!({ "id": id, "reason": "untitled" })));
+        };
+        if !overwrite && self.modified_externally(id)? {
+            return Err(AppError::new(
+                ErrorCode::Conflict,
+                format!("`{}` was changed by another program", path.display()),
+            )
+            .with_details(serde_json::json!({ "id": id, "reason": "modified" })));
+        }
+        self.write(id, &path)
+    }
+
+    /// Writes the document to `path`, which becomes its file from now on.
+    /// Fails with `conflict` if another open document has that file.
+    pub fn save_as(&self, id: u64, path: &Path) -> AppResult<Document> {
+        self.get(id)?;
+        let path = &canonical(path)?;
+        if let Some(other) = find(&self.open.lock().unwrap(), path).filter(|other| other.id != id) {
+            return Err(AppError::new(
+                ErrorCode::Conflict,
+                format!("`{}` is open in another document", path.display()),
+            )
+            .with_details(serde_json::json!({ "id": id, "reason": "open", "other": other.id })));
+        }
+        self.write(id, path)
+    }
+
+    /// Writes what the document holds now without keeping the others
+    /// locked meanwhile. Edits made during the write leave it dirty.
+    fn write(&self, id: u64, path: &Path) -> AppResult<Document> {
+        let written = self.get(id)?;
+        files::write_atomic(path, &written.encoding.encode(&written.content))?;
+        let (modified_at, len) = disk_stamp(path)?;
+        tracing::info!(id, path = %path.display(), "document saved");
+        self.update(id, |document| {
+            document.path = Some(path.to_owned());
+            document.dirty = document.content != written.content;
+            document.modified_at = modified_at;
+            document.len = len;
+            Ok(())
+        })
+    }
+
+    /// Discards unsaved changes by reading the file again.
+    pub fn revert(&self, id: u64) -> AppResult<Document> {
+        let document = self.get(id)?;
+        let Some(path) = document.path else {
+            return Err(AppError::new(
+                ErrorCode::InvalidInput,
+                "an untitled document has nothing to revert to",
+            )
+            .with_details(serde_json::json!({ "id": id, "reason": "untitled" })));
+        };
+        let (encoding, content) = Encoding::decode(&fs::read(&path)?)?;
+        self.update(id, |document| {
+            document.content = content;
+            document.encoding = encoding;
+            document.dirty = false;
+            document.stamp(&path)
+        })
+    }
+
+    /// Forgets the document. One with unsaved changes is only closed if
+    /// `discard` is set.
+    pub fn close(&self, id: u64, discard: bool) -> AppResult<Document> {
+        let mut open = self.open.lock().unwrap();
+        let document = open.get(&id).ok_or_else(|| not_found(id))?;
+        if document.dirty && !discard {
+            return Err(
+                AppError::new(ErrorCode::InvalidInput, "the document has unsaved changes")
+                    .with_details(serde_json::json!({ "id": id, "reason": "dirty" })),
+            );
+        }
+        let document = open.remove(&id).unwrap();
+        drop(open);
+        self.changed();
+        Ok(document)
+    }
+
+    /// Applies `change` to a copy of the document and keeps it only if the
+    /// change succeeds.
+    fn update(
+        &self,
+        id: u64,
+        change: impl FnOnce(&mut Document) -> AppResult<()>,
+    ) -> AppResult<Document> {
+        let mut open = self.open.lock().unwrap();
+        let document = open.get_mut(&id).ok_or_else(|| not_found(id))?;
+        let mut updated = document.clone();
+        change(&mut updated)?;
+        let dirtied = document.dirty != updated.dirty;
+        *document = updated.clone();
+        drop(open);
+        if dirtied {
+            self.changed();
+        }
+        Ok(updated)
+    }
+}
+
+/// `path` made absolute with symlinks, `.` and `..` resolved, so each file
+/// has one path to store and compare. Directories that do not exist yet are
+/// kept as spelled, below the nearest one that does.
+fn canonical(path: &Path) -> AppResult<PathBuf> {
+    match fs::canonicalize(path) {
+        Ok(resolved) => Ok(resolved),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            let (Some(dir), Some(name)) = (path.parent(), path.file_name()) else {
+                return Err(err.into());
+            };
+            let dir = if dir.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                dir
+            };
+            Ok(canonical(dir)?.join(name))
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// The open document whose file is `path`, which must be [`canonical`].
+fn find<'a>(open: &'a BTreeMap<u64, Document>, path: &Path) -> Option<&'a Document> {
+    open.values()
+        .find(|document| document.path.as_deref() == Some(path))
+}
+
+fn not_found(id: u64) -> AppError {
+    AppError::new(ErrorCode::NotFound, format!("no open document {id}"))
+        .with_details(serde_json::json!({ "id": id }))
+}
+
+/// Shows a save dialog for the document and saves it under the chosen path.
+/// Returns `None` if the user cancelled. Blocks like [`files::pick_save_path`].
+pub fn save_as_with_dialog<R: Runtime>(app: &AppHandle<R>, id: u64) -> AppResult<Option<Document>> {
+    let documents = app.state::<Documents>();
+    let document = documents.get(id)?;
+    let options = SaveDialogOptions {
+        file_name: document.name(),
+        directory: document
+            .path
+            .as_deref()
+            .and_then(Path::parent)
+            .map(Path::to_owned),
+        ..Default::default()
+    };
+    match files::pick_save_path(app, options)? {
+        Some(path) => documents.save_as(id, &path).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Saves the document, asking for a path first if it is untitled.
+pub fn save_with_dialog<R: Runtime>(
+    app: &AppHandle<R>,
+    id: u64,
+    overwrite: bool,
+) -> AppResult<Option<Document>> {
+    let documents = app.state::<Documents>();
+    if documents.get(id)?.path.is_none() {
+        return save_as_with_dialog(app, id);
+    }
+    documents.save(id, overwrite).map(Some)
+}
+
+/// Handles window events for document windows: closing [`MAIN_WINDOW`] with
+/// unsaved documents first asks whether to save them.
+pub fn on_window_event<R: Runtime>(window: &Window<R>, event: &WindowEvent) {
+    let WindowEvent::CloseRequested { api, .. } = event else {
+        return;
+    };
+    if window.label() != MAIN_WINDOW || window.state::<Documents>().dirty().is_empty() {
+        return;
+    }
+
+    api.prevent_close();
+    let closing = window.clone();
+    confirm_unsaved(window.app_handle(), move |_| {
+        if let Err(err) = closing.destroy() {
+            tracing::warn!("could not close the main window: {err}");
+        }
+    });
+}
+
+/// Asks what to do with unsaved documents, saves them if asked to, then
+/// calls `then` unless the user cancelled. A failed save is shown to the
+/// user, who is then asked again. With nothing unsaved, or no
+/// dialog plugin to ask with, calls `then` right away.
+pub fn confirm_unsaved<R: Runtime>(
+    app: &AppHandle<R>,
+    then: impl FnOnce(&AppHandle<R>) + Send + 'static,
+) {
+    if app.state::<Documents>().dirty().is_empty() {
+        return then(app);
+    }
+    if app.try_state::<tauri_plugin_dialog::Dialog<R>>().is_none() {
+        tracing::warn!("discarding unsaved documents; the dialog plugin is not registered");
+        return then(app);
+    }
+
+    let app = app.clone();
+    // The dialogs block, which must not happen on the event loop's thread.
+    tauri::async_runtime::spawn_blocking(move || loop {
+        match confirm_close(&app) {
+            Ok(true) => return then(&app),
+            Ok(false) => return,
+            // Say why, then ask again rather than leave the user guessing.
+            Err(err) => {
+                tracing::warn!("could not save documents before closing: {err}");
+                if let Err(err) = show_save_error(&app, &err) {
+                    tracing::warn!("could not report the failed save: {err}");
+                    return;
+                }
+            }
+        }
+    });
+}
+
+/// Tells the user a save failed, e.g. because another program changed the
+/// file. Blocks until they dismiss it.
+fn show_save_error<R: Runtime>(app: &AppHandle<R>, err: &AppError) -> AppResult<()> {
+    let i18n = app.state::<I18n>();
+    let mut args = FluentArgs::new();
+    args.set("error", err.message.clone());
+    let title = i18n.translate(None, "document-save-failed-title", None)?;
+    let message = i18n.translate(None, "document-save-failed-message", Some(&args))?;
+    app.dialog()
+        .message(message)
+        .title(title)
+        .kind(MessageDialogKind::Error)
+        .blocking_show();
+    Ok(())
+}
+
+/// Asks what to do with unsaved documents and saves them if asked to.
+/// Returns whether the window may close.
+fn confirm_close<R: Runtime>(app: &AppHandle<R>) -> AppResult<bool> {
+    let dirty = app.state::<Documents>().dirty();
+    if dirty.is_empty() {
+        return Ok(true);
+    }
+
+    let i18n = app.state::<I18n>();
+    let untitled = i18n.translate(None, "document-untitled", None)?;
+    let names: Vec<String> = dirty
+        .iter()
+        .map(|document| document.name().unwrap_or_else(|| untitled.clone()))
+        .collect();
+    let mut args = FluentArgs::new();
+    args.set("count", dirty.len());
+    args.set("names", names.join(", "));
+    let title = i18n.translate(None, "document-close-title", None)?;
+    let message = i18n.translate(None, "document-close-message", Some(&args))?;
+    let save = i18n.translate(None, "document-save", None)?;
+    let discard = i18n.translate(None, "document-discard", None)?;
+    let cancel = i18n.translate(None, "document-cancel", None)?;
+
+    let choice = app
+        .dialog()
+        .message(message)
+        .title(title)
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::YesNoCancelCustom(
+            save.clone(),
+            discard.clone(),
+            cancel,
+        ))
+        .blocking_show_with_result();
+    match choice {
+        MessageDialogResult::Yes => {}
+        MessageDialogResult::Custom(label) if label == save => {}
+        MessageDialogResult::No => return Ok(true),
+        MessageDialogResult::Custom(label) if label == discard => return Ok(true),
+        _ => return Ok(false),
+    }
+
+    for document in dirty {
+        if save_with_dialog(app, document.id, false)?.is_none() {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Opens `path`, which must be one of the recent files, and moves it to the
+/// top of them.
+pub fn open_recent<R: Runtime>(app: &AppHandle<R>, path: &Path) -> AppResult<Document> {
+    let conn = app.state::<Database>().conn()?;
+    let recent = recent_files::list(&conn)?;
+    if !recent.iter().any(|file| file.path == path) {
+        return Err(AppError::new(
+            ErrorCode::PermissionDenied,
+            "only recent files can be opened without a dialog",
+        )
+        .with_details(serde_json::json!({ "field": "path", "reason": "not_recent" })));
+    }
+    files::remember(app, path)?;
+    app.state::<Documents>().open(path)
+}
+
+/// Opens the file the user picks. Returns `None` if the user cancelled.
+/// Blocks like [`files::pick_files`].
+pub fn open_with_dialog<R: Runtime>(app: &AppHandle<R>) -> AppResult<Option<Document>> {
+    match files::pick_files(app, OpenDialogOptions::default())?
+        .into_iter()
+        .next()
+    {
+        Some(path) => app.state::<Documents>().open(&path).map(Some),
+        None => Ok(None),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn new_document(documents: State<'_, Documents>) -> AppResult<Document> {
+    Ok(documents.create())
+}
+
+/// Opens `path`, which must be one of the recent files, or else the file the
+/// user picks. Returns `None` if the user cancelled.
+#[tauri::command]
+#[specta::specta]
+pub async fn open_document<R: Runtime>(
+    app: AppHandle<R>,
+    path: Option<PathBuf>,
+) -> AppResult<Option<Document>> {
+    match path {
+        Some(path) => open_recent(&app, &path).map(Some),
+        None => open_with_dialog(&app),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn list_documents(documents: State<'_, Documents>) -> AppResult<Vec<Document>> {
+    Ok(documents.list())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_document(documents: State<'_, Documents>, id: u64) -> AppResult<Document> {
+    documents.get(id)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn edit_document(
+    documents: State<'_, Documents>,
+    id: u64,
+    content: String,
+) -> AppResult<Document> {
+    documents.edit(id, content)
+}
+
+/// Saves the document, asking for a path if it is untitled. Returns `None`
+/// if the user cancelled.
+#[tauri::command]
+#[specta::specta]
+pub async fn save_document<R: Runtime>(
+    app: AppHandle<R>,
+    id: u64,
+    overwrite: Option<bool>,
+) -> AppResult<Option<Document>> {
+    save_with_dialog(&app, id, overwrite.unwrap_or(false))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn save_document_as<R: Runtime>(
+    app: AppHandle<R>,
+    id: u64,
+) -> AppResult<Option<Document>> {
+    save_as_with_dialog(&app, id)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn revert_document(documents: State<'_, Documents>, id: u64) -> AppResult<Document> {
+    documents.revert(id)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn close_document(
+    documents: State<'_, Documents>,
+    id: u64,
+    discard: Option<bool>,
+) -> AppResult<Document> {
+    documents.close(id, discard.unwrap_or(false))
+}
@@ -0,0 +1,131 @@
+use std::fmt;
+
+use serde::Serialize;
+use serde_json::Value;
+use specta::Type;
+
+/// Machine-readable error codes shared with the frontend.
+///
+/// These are serialized in `snake_case` and are part of the IPC contract, so
+/// existing variants must never be renamed; add new ones instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    Internal,
+    Io,
+    Json,
+    Tauri,
+    Dialog,
+    Shell,
+    InvalidInput,
+    NotFound,
+    Database,
+    Notification,
+    PermissionDenied,
+    Cancelled,
+    Timeout,
+    Conflict,
+}
+
+impl ErrorCode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::Internal => "internal",
+            ErrorCode::Io => "io",
+            ErrorCode::Json => "json",
+            ErrorCode::Tauri => "tauri",
+            ErrorCode::Dialog => "dialog",
+            ErrorCode::Shell => "shell",
+            ErrorCode::InvalidInput => "invalid_input",
+            ErrorCode::NotFound => "not_found",
+            ErrorCode::Database => "database",
+            ErrorCode::Notification => "notification",
+            ErrorCode::PermissionDenied => "permission_denied",
+            ErrorCode::Cancelled => "cancelled",
+            ErrorCode::Timeout => "timeout",
+            ErrorCode::Conflict => "conflict",
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The error type returned by every `#[tauri::command]`.
+///
+/// It reaches the frontend as `{ code, message, details? }`, so the React side
+/// can branch on `code` while showing `message` to the user.
+#[derive(Debug, Clone, PartialEq, Serialize, thiserror::Error, Type)]
+#[error("{message}")]
+pub struct AppError {
+    pub code: ErrorCode,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<Value>,
+}
+
+pub type AppResult<T> = Result<T, AppError>;
+
+impl AppError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Internal, message)
+    }
+
+    pub fn with_details(mut self, details: impl Serialize) -> Self {
+        // Details are best-effort context; a value that fails to serialize
+        // should not mask the original error.
+        self.details = serde_json::to_value(details).ok();
+        self
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(err: std::io::Error) -> Self {
+        AppError::new(ErrorCode::Io, err.to_string())
+            .with_details(serde_json::json!({ "kind": format!("{:?}", err.kind()) }))
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(err: serde_json::Error) -> Self {
+        AppError::new(ErrorCode::Json, err.to_string()).with_details(serde_json::json!({
+            "line": err.line(),
+            "column": err.column(),
+        }))
+    }
+}
+
+impl From<tauri::Error> for AppError {
+    fn from(err: tauri::Error) -> Self {
+        AppError::new(ErrorCode::Tauri, err.to_string())
+    }
+}
+
+impl From<tauri_plugin_dialog::Error> for AppError {
+    fn from(err: tauri_plugin_dialog::Error) -> Self {
+        AppError::new(ErrorCode::Dialog, err.to_string())
+    }
+}
+
+impl From<tauri_plugin_shell::Error> for AppError {
+    fn from(err: tauri_plugin_shell::Error) -> Self {
+        AppError::new(ErrorCode::Shell, err.to_string())
+    }
+}
+
+impl From<tauri_plugin_notification::Error> for AppError {
+    fn from(err: tauri_plugin_notification::Error) -> Self {
+        AppError::new(ErrorCode::Notification, err.to_string())
+    }
+}
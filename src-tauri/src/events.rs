@@ -0,0 +1,155 @@
+use std::fmt::Write;
+
+use serde::Serialize;
+use serde_json::Value;
+use specta::{Type, TypeCollection};
+use specta_typescript::{BigIntExportBehavior, Typescript};
+use tauri::{AppHandle, Emitter, EventTarget, Runtime};
+
+use crate::error::{AppError, AppResult, ErrorCode};
+use crate::process::manager::ProcessInfo;
+use crate::settings::Settings;
+
+pub const SETTINGS_CHANGED: &str = "settings-changed";
+pub const NOTIFICATIONS_CHANGED: &str = "notifications-changed";
+pub const RECENT_FILES_CHANGED: &str = "recent-files-changed";
+pub const DOCUMENTS_CHANGED: &str = "documents-changed";
+pub const PROCESS_STATUS: &str = "process-status";
+
+/// Where [`typescript`] is checked in, relative to the crate root.
+pub const TYPESCRIPT_FILE: &str = "../src/events.ts";
+
+/// Every event with the version of its payload. Bump an event's version
+/// whenever its payload changes in a way an older frontend would misread;
+/// frontends ignore versions they were not built for.
+pub const EVENTS: &[(&str, u32)] = &[
+    (SETTINGS_CHANGED, 1),
+    (NOTIFICATIONS_CHANGED, 1),
+    (RECENT_FILES_CHANGED, 1),
+    (DOCUMENTS_CHANGED, 1),
+    (PROCESS_STATUS, 1),
+];
+
+/// Everything the backend tells the frontend about. Each variant is emitted
+/// under its own name, with its payload wrapped in an [`Envelope`].
+#[derive(Debug, Clone, PartialEq, Serialize, Type)]
+#[serde(tag = "event", content = "payload", rename_all = "kebab-case")]
+pub enum AppEvent {
+    /// The settings after an update.
+    SettingsChanged(Settings),
+    NotificationsChanged {
+        /// Delivered notifications the user has not seen yet.
+        unread: u64,
+    },
+    RecentFilesChanged,
+    /// A document was opened or closed, or gained or lost unsaved changes.
+    DocumentsChanged,
+    /// A managed process started, exited or was killed.
+    ProcessStatus(ProcessInfo),
+}
+
+/// What is actually emitted: an event's payload and the version of its
+/// shape.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Envelope {
+    pub version: u32,
+    /// `null` for events without one.
+    pub payload: Value,
+}
+
+impl AppEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::SettingsChanged(_) => SETTINGS_CHANGED,
+            Self::NotificationsChanged { .. } => NOTIFICATIONS_CHANGED,
+            Self::RecentFilesChanged => RECENT_FILES_CHANGED,
+            Self::DocumentsChanged => DOCUMENTS_CHANGED,
+            Self::ProcessStatus(_) => PROCESS_STATUS,
+        }
+    }
+
+    pub fn version(&self) -> u32 {
+        EVENTS
+            .iter()
+            .find(|(name, _)| *name == self.name())
+            .map(|(_, version)| *version)
+            .expect("every event is listed in EVENTS")
+    }
+
+    pub fn envelope(&self) -> AppResult<Envelope> {
+        let payload = match serde_json::to_value(self)? {
+            Value::Object(mut tagged) => tagged.remove("payload").unwrap_or(Value::Null),
+            _ => Value::Null,
+        };
+        Ok(Envelope {
+            version: self.version(),
+            payload,
+        })
+    }
+
+    /// Sends the event to every window, and to Rust listeners.
+    pub fn emit<R: Runtime>(&self, app: &AppHandle<R>) -> AppResult<()> {
+        Ok(app.emit(self.name(), self.envelope()?)?)
+    }
+
+    /// Sends the event to the window labelled `label` only.
+    pub fn emit_to<R: Runtime>(&self, app: &AppHandle<R>, label: &str) -> AppResult<()> {
+        Ok(app.emit_to(EventTarget::labeled(label), self.name(), self.envelope()?)?)
+    }
+}
+
+/// TypeScript definitions of every event and its payload, checked in at
+/// [`TYPESCRIPT_FILE`] for the frontend to subscribe with.
+pub fn typescript() -> AppResult<String> {
+    let mut types = TypeCollection::default();
+    types.register::<AppEvent>();
+    let mut out = Typescript::default()
+        .bigint(BigIntExportBehavior::Number)
+        .header(HEADER)
+        .export(&types)
+        .map_err(|err| AppError::new(ErrorCode::Internal, err.to_string()))?;
+
+    out.push_str("/** The payload version each event was generated with. */\n");
+    out.push_str("export const EVENT_VERSIONS = {\n");
+    for (name, version) in EVENTS {
+        let _ = writeln!(out, "  \"{name}\": {version},");
+    }
+    out.push_str("} as const;\n\n");
+    out.push_str(HELPERS);
+    Ok(out)
+}
+
+const HEADER: &str =
+    "// From src-tauri/src/events.rs; regenerate with `cargo run --bin export_types`.
+/* eslint-disable */
+import { listen, type UnlistenFn } from \"@tauri-apps/api/event\";";
+
+const HELPERS: &str = r#"export type AppEventName = AppEvent["event"];
+
+export type AppEventPayload<E extends AppEventName> =
+  Extract<AppEvent, { event: E }> extends { payload: infer P } ? P : null;
+
+/** What the backend emits: a payload and the version of its shape. */
+export type Envelope<E extends AppEventName> = {
+  version: number;
+  payload: AppEventPayload<E>;
+};
+
+/**
+ * Calls `handler` with the payload of every `event` the backend emits.
+ * Payloads of another version than this file was generated with are
+ * dropped with a warning rather than misread.
+ */
+export function onAppEvent<E extends AppEventName>(
+  event: E,
+  handler: (payload: AppEventPayload<E>) => void,
+): Promise<UnlistenFn> {
+  return listen<Envelope<E>>(event, ({ payload }) => {
+    if (payload.version !== EVENT_VERSIONS[event]) {
+      console.warn(`ignoring ${event} v${payload.version}; expected v${EVENT_VERSIONS[event]}`);
+      return;
+    }
+    handler(payload.payload);
+  });
+}
+"#;
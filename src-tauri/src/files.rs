@@ -0,0 +1,262 @@
+use std::ffi::OsString;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use serde::Deserialize;
+use specta::Type;
+use tauri::{AppHandle, Manager, Runtime, State};
+use tauri_plugin_dialog::{DialogExt, FileDialogBuilder};
+use tauri_plugin_fs::FsExt;
+
+use crate::db::recent_files::{self, RecentFile};
+use crate::db::Database;
+use crate::error::{AppError, AppResult, ErrorCode};
+use crate::events::AppEvent;
+use crate::validation::TextRule;
+
+const FILTER_NAME: TextRule = TextRule::new(1, 64);
+const TITLE: TextRule = TextRule::new(1, 128);
+
+/// A named group of extensions offered by a file dialog, e.g.
+/// `{ name: "Text", extensions: ["txt", "md"] }`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FileFilter {
+    pub name: String,
+    /// Without the leading dot; `*` matches any file.
+    pub extensions: Vec<String>,
+}
+
+impl FileFilter {
+    /// Validates the name and extensions.
+    pub fn validated(self) -> AppResult<Self> {
+        let name = FILTER_NAME.apply("filters.name", &self.name)?;
+        let valid = |ext: &String| {
+            ext == "*"
+                || (!ext.is_empty()
+                    && ext.len() <= 16
+                    && ext.chars().all(|c| c.is_ascii_alphanumeric()))
+        };
+        if self.extensions.is_empty() || !self.extensions.iter().all(valid) {
+            return Err(AppError::new(
+                ErrorCode::InvalidInput,
+                format!("filter `{name}` needs one or more plain extensions such as `txt`"),
+            )
+            .with_details(
+                serde_json::json!({ "field": "filters.extensions", "reason": "invalid" }),
+            ));
+        }
+        Ok(Self {
+            name,
+            extensions: self.extensions,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Type)]
+#[serde(default, rename_all = "camelCase")]
+pub struct OpenDialogOptions {
+    pub title: Option<String>,
+    pub filters: Vec<FileFilter>,
+    /// Folder the dialog starts in.
+    pub directory: Option<PathBuf>,
+    pub multiple: bool,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Type)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SaveDialogOptions {
+    pub title: Option<String>,
+    pub filters: Vec<FileFilter>,
+    pub directory: Option<PathBuf>,
+    /// Suggested file name.
+    pub file_name: Option<String>,
+}
+
+fn file_dialog<R: Runtime>(
+    app: &AppHandle<R>,
+    title: Option<&str>,
+    filters: Vec<FileFilter>,
+    directory: Option<&Path>,
+) -> AppResult<FileDialogBuilder<R>> {
+    if app.try_state::<tauri_plugin_dialog::Dialog<R>>().is_none() {
+        return Err(AppError::new(
+            ErrorCode::Internal,
+            "the dialog plugin is not registered",
+        ));
+    }
+
+    let mut dialog = app.dialog().file();
+    if let Some(title) = title {
+        dialog = dialog.set_title(TITLE.apply("title", title)?);
+    }
+    for filter in filters {
+        let filter = filter.validated()?;
+        let extensions: Vec<&str> = filter.extensions.iter().map(String::as_str).collect();
+        dialog = dialog.add_filter(filter.name, &extensions);
+    }
+    if let Some(directory) = directory {
+        dialog = dialog.set_directory(directory);
+    }
+    Ok(dialog)
+}
+
+/// Adds `path` to the fs plugin's scope, when the plugin is loaded, so the
+/// frontend's file APIs can reach that one file, then puts it at the top of
+/// the recent files. The asset protocol scope is left alone.
+pub fn remember<R: Runtime>(app: &AppHandle<R>, path: &Path) -> AppResult<()> {
+    if let Some(scope) = app.try_fs_scope() {
+        scope.allow_file(path)?;
+    }
+
+    let conn = app.state::<Database>().conn()?;
+    recent_files::touch(&conn, path, Utc::now())?;
+    tracing::debug!(path = %path.display(), "file picked");
+    AppEvent::RecentFilesChanged.emit(app)?;
+    Ok(())
+}
+
+/// Shows an open dialog and returns the chosen files; empty if the user
+/// cancelled. Blocks until the dialog closes, so never call it from the main
+/// thread.
+pub fn pick_files<R: Runtime>(
+    app: &AppHandle<R>,
+    options: OpenDialogOptions,
+) -> AppResult<Vec<PathBuf>> {
+    let dialog = file_dialog(
+        app,
+        options.title.as_deref(),
+        options.filters,
+        options.directory.as_deref(),
+    )?;
+    let picked = if options.multiple {
+        dialog.blocking_pick_files().unwrap_or_default()
+    } else {
+        dialog.blocking_pick_file().into_iter().collect()
+    };
+
+    let paths = picked
+        .into_iter()
+        .map(|file| {
+            file.into_path()
+                .map_err(|err| AppError::new(ErrorCode::Dialog, err.to_string()))
+        })
+        .collect::<AppResult<Vec<_>>>()?;
+    for path in &paths {
+        remember(app, path)?;
+    }
+    Ok(paths)
+}
+
+/// Shows a save dialog and returns the chosen path, or `None` if the user
+/// cancelled. Blocks like [`pick_files`].
+pub fn pick_save_path<R: Runtime>(
+    app: &AppHandle<R>,
+    options: SaveDialogOptions,
+) -> AppResult<Option<PathBuf>> {
+    let mut dialog = file_dialog(
+        app,
+        options.title.as_deref(),
+        options.filters,
+        options.directory.as_deref(),
+    )?;
+    if let Some(file_name) = options.file_name {
+        dialog = dialog.set_file_name(file_name);
+    }
+    let Some(file) = dialog.blocking_save_file() else {
+        return Ok(None);
+    };
+    let path = file
+        .into_path()
+        .map_err(|err| AppError::new(ErrorCode::Dialog, err.to_string()))?;
+    remember(app, &path)?;
+    Ok(Some(path))
+}
+
+/// Forgets every recent file. Returns how many entries were removed.
+pub fn clear_recent<R: Runtime>(app: &AppHandle<R>) -> AppResult<usize> {
+    let conn = app.state::<Database>().conn()?;
+    let cleared = recent_files::clear(&conn)?;
+    AppEvent::RecentFilesChanged.emit(app)?;
+    Ok(cleared)
+}
+
+/// Writes `bytes` to a hidden sibling temp file, syncs it and renames it
+/// over `path`, so a crash or power loss mid-write leaves either the old
+/// file or the new one, never a truncated mix. Each call gets its own temp
+/// file, so concurrent writers cannot interleave. Creates the parent
+/// directory if needed.
+///
+/// A symlink at `path` is followed and its target replaced, and an existing
+/// file keeps its permissions rather than getting the temp file's 0600.
+pub fn write_atomic(path: &Path, bytes: &[u8]) -> AppResult<()> {
+    let resolved = match fs::canonicalize(path) {
+        Ok(resolved) => resolved,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => path.to_owned(),
+        Err(err) => return Err(err.into()),
+    };
+    let path = resolved.as_path();
+    let name = path.file_name().ok_or_else(|| {
+        AppError::new(ErrorCode::InvalidInput, "path must name a file")
+            .with_details(serde_json::json!({ "field": "path", "reason": "invalid" }))
+    })?;
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    fs::create_dir_all(dir)?;
+
+    let mut prefix = OsString::from(".");
+    prefix.push(name);
+    prefix.push(".");
+    let mut tmp = tempfile::Builder::new()
+        .prefix(&prefix)
+        .suffix(".tmp")
+        .tempfile_in(dir)?;
+    tmp.write_all(bytes)?;
+    match fs::metadata(path) {
+        Ok(existing) => tmp.as_file().set_permissions(existing.permissions())?,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err.into()),
+    }
+    tmp.as_file().sync_all()?;
+    tmp.persist(path).map_err(|err| err.error)?;
+    // The rename is only durable once the directory entry is.
+    #[cfg(unix)]
+    fs::File::open(dir)?.sync_all()?;
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn open_file_dialog<R: Runtime>(
+    app: AppHandle<R>,
+    options: Option<OpenDialogOptions>,
+) -> AppResult<Vec<PathBuf>> {
+    pick_files(&app, options.unwrap_or_default())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn save_file_dialog<R: Runtime>(
+    app: AppHandle<R>,
+    options: Option<SaveDialogOptions>,
+) -> AppResult<Option<PathBuf>> {
+    pick_save_path(&app, options.unwrap_or_default())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_recent_files(db: State<'_, Database>) -> AppResult<Vec<RecentFile>> {
+    let conn = db.conn()?;
+    recent_files::list(&conn)
+}
+
+/// Returns how many entries were removed.
+#[tauri::command]
+#[specta::specta]
+pub async fn clear_recent_files<R: Runtime>(app: AppHandle<R>) -> AppResult<usize> {
+    clear_recent(&app)
+}
@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+use fluent_langneg::{negotiate_languages, NegotiationStrategy};
+use serde::Deserialize;
+use specta::Type;
+use tauri::State;
+use unic_langid::LanguageIdentifier;
+
+use crate::error::{AppError, AppResult, ErrorCode};
+
+pub const DEFAULT_LOCALE: &str = "en-US";
+
+/// Translation catalogues compiled into the binary, keyed by locale.
+pub const EMBEDDED_RESOURCES: &[(&str, &str)] = &[
+    ("en-US", include_str!("../locales/en-US/app.ftl")),
+    ("es", include_str!("../locales/es/app.ftl")),
+    ("fr", include_str!("../locales/fr/app.ftl")),
+    ("de", include_str!("../locales/de/app.ftl")),
+];
+
+/// A value passed into a Fluent placeable from the frontend.
+#[derive(Debug, Clone, Deserialize, Type)]
+#[serde(untagged)]
+pub enum ArgValue {
+    Number(f64),
+    Text(String),
+}
+
+pub type TranslationArgs = HashMap<String, ArgValue>;
+
+/// The shared message catalogue used by commands, notifications and dialogs.
+///
+/// Lookups walk a fallback chain: the explicitly requested locale, the
+/// user's preferred locale, the OS locale and finally [`DEFAULT_LOCALE`]. A
+/// message missing from one bundle is looked up in the next.
+pub struct I18n {
+    locales: Vec<LanguageIdentifier>,
+    bundles: Vec<FluentBundle<FluentResource>>,
+    default: LanguageIdentifier,
+    system: Option<LanguageIdentifier>,
+    preferred: RwLock<Option<LanguageIdentifier>>,
+}
+
+impl I18n {
+    /// Builds the catalogue from [`EMBEDDED_RESOURCES`] using the OS locale.
+    pub fn from_system() -> Self {
+        Self::new(EMBEDDED_RESOURCES, sys_locale::get_locale().as_deref())
+    }
+
+    /// Builds a catalogue from `(locale, ftl source)` pairs. The first locale
+    /// equal to [`DEFAULT_LOCALE`] (or the first entry) is the final fallback.
+    ///
+    /// Panics if a resource fails to parse, since catalogues are embedded at
+    /// build time.
+    pub fn new(resources: &[(&str, &str)], system_locale: Option<&str>) -> Self {
+        let mut locales = Vec::with_capacity(resources.len());
+        let mut bundles = Vec::with_capacity(resources.len());
+
+        for (locale, source) in resources {
+            let langid: LanguageIdentifier = locale
+                .parse()
+                .unwrap_or_else(|_| panic!("invalid locale identifier `{locale}`"));
+            let resource = FluentResource::try_new(source.to_string())
+                .unwrap_or_else(|(_, errors)| panic!("invalid ftl for `{locale}`: {errors:?}"));
+
+            let mut bundle = FluentBundle::new_concurrent(vec![langid.clone()]);
+            // Output ends up in native notifications and dialogs, which render
+            // Unicode isolation marks as stray glyphs on some platforms.
+            bundle.set_use_isolating(false);
+            bundle.add_resource(resource).unwrap_or_else(|errors| {
+                panic!("duplicate ftl entries for `{locale}`: {errors:?}")
+            });
+
+            locales.push(langid);
+            bundles.push(bundle);
+        }
+
+        let default = DEFAULT_LOCALE
+            .parse()
+            .ok()
+            .filter(|langid| locales.contains(langid))
+            .or_else(|| locales.first().cloned())
+            .expect("at least one translation resource");
+
+        Self {
+            locales,
+            bundles,
+            default,
+            system: system_locale.and_then(|locale| parse_locale(locale).ok()),
+            preferred: RwLock::new(None),
+        }
+    }
+
+    pub fn available_locales(&self) -> &[LanguageIdentifier] {
+        &self.locales
+    }
+
+    /// Overrides the OS locale, e.g. from the user's settings. `None` goes
+    /// back to following the OS.
+    pub fn set_preferred_locale(&self, locale: Option<&str>) -> AppResult<()> {
+        let locale = locale.map(parse_locale).transpose()?;
+        *self.preferred.write().unwrap() = locale;
+        Ok(())
+    }
+
+    /// The locale used when a caller does not request one.
+    pub fn current_locale(&self) -> LanguageIdentifier {
+        self.fallback_chain(None)
+            .first()
+            .cloned()
+            .unwrap_or_else(|| self.default.clone())
+    }
+
+    /// The locale a lookup for `requested` starts from.
+    pub fn resolve_locale(&self, requested: Option<&str>) -> AppResult<LanguageIdentifier> {
+        let requested = requested.map(parse_locale).transpose()?;
+        Ok(self
+            .fallback_chain(requested.as_ref())
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| self.default.clone()))
+    }
+
+    /// Available locales to try, in order, for a lookup.
+    pub fn fallback_chain(
+        &self,
+        requested: Option<&LanguageIdentifier>,
+    ) -> Vec<LanguageIdentifier> {
+        let preferred = self.preferred.read().unwrap().clone();
+        let wanted: Vec<LanguageIdentifier> = requested
+            .cloned()
+            .into_iter()
+            .chain(preferred)
+            .chain(self.system.clone())
+            .collect();
+
+        negotiate_languages(
+            &wanted,
+            &self.locales,
+            Some(&self.default),
+            NegotiationStrategy::Filtering,
+        )
+        .into_iter()
+        .cloned()
+        .collect()
+    }
+
+    /// Formats the message `key`, walking the fallback chain until a bundle
+    /// that defines it is found.
+    pub fn translate(
+        &self,
+        requested: Option<&str>,
+        key: &str,
+        args: Option<&FluentArgs>,
+    ) -> AppResult<String> {
+        let requested = requested.map(parse_locale).transpose()?;
+
+        for locale in self.fallback_chain(requested.as_ref()) {
+            let Some(bundle) = self.bundle(&locale) else {
+                continue;
+            };
+            let Some(pattern) = bundle.get_message(key).and_then(|message| message.value()) else {
+                continue;
+            };
+
+            // Formatting errors (e.g. a missing argument) still produce
+            // usable text with the placeable left visible, so we keep it.
+            let mut errors = vec![];
+            return Ok(bundle
+                .format_pattern(pattern, args, &mut errors)
+                .into_owned());
+        }
+
+        Err(
+            AppError::new(ErrorCode::NotFound, format!("no translation for `{key}`"))
+                .with_details(serde_json::json!({ "key": key })),
+        )
+    }
+
+    fn bundle(&self, locale: &LanguageIdentifier) -> Option<&FluentBundle<FluentResource>> {
+        self.locales
+            .iter()
+            .position(|candidate| candidate == locale)
+            .map(|index| &self.bundles[index])
+    }
+}
+
+pub fn to_fluent_args(args: &TranslationArgs) -> FluentArgs<'_> {
+    let mut fluent = FluentArgs::with_capacity(args.len());
+    for (name, value) in args {
+        let value = match value {
+            ArgValue::Number(number) => FluentValue::from(*number),
+            ArgValue::Text(text) => FluentValue::from(text.as_str()),
+        };
+        fluent.set(name.as_str(), value);
+    }
+    fluent
+}
+
+pub fn parse_locale(locale: &str) -> AppResult<LanguageIdentifier> {
+    locale.parse().map_err(|_| {
+        AppError::new(
+            ErrorCode::InvalidInput,
+            format!("`{locale}` is not a valid locale"),
+        )
+        .with_details(serde_json::json!({ "field": "locale", "reason": "invalid_locale" }))
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn translate(
+    i18n: State<'_, I18n>,
+    key: &str,
+    args: Option<TranslationArgs>,
+    locale: Option<&str>,
+) -> AppResult<String> {
+    let args = args.as_ref().map(to_fluent_args);
+    i18n.translate(locale, key, args.as_ref())
+}
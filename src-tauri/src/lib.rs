@@ -1,16 +1,108 @@
+pub mod app;
+pub mod bindings;
+pub mod crash;
+pub mod db;
+pub mod documents;
+pub mod error;
+pub mod events;
+pub mod files;
+pub mod i18n;
+pub mod links;
+pub mod logging;
+pub mod menu;
+pub mod notifications;
+pub mod process;
+pub mod settings;
+pub mod streaming;
+#[cfg(desktop)]
+pub mod tray;
+pub mod validation;
+pub mod windows;
+
+pub use app::{AppBuilder, Plugins};
+
+use chrono::Utc;
+use db::greetings::{self, NewGreeting};
+use db::Database;
+use error::AppResult;
+use fluent_bundle::FluentArgs;
+use i18n::I18n;
+use settings::SettingsStore;
+use streaming::{Batching, Requests, StreamMessage};
+use tauri::ipc::Channel;
+use tauri::{Runtime, State, Window};
+
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
-fn greet(name: &str) -> String {
-    format!("Hello, {name}! You've been greeted from Rust!")
+#[specta::specta]
+async fn greet<R: Runtime>(
+    window: Window<R>,
+    i18n: State<'_, I18n>,
+    db: State<'_, Database>,
+    settings: State<'_, SettingsStore>,
+    name: &str,
+    locale: Option<&str>,
+) -> AppResult<String> {
+    let name = validation::NAME.apply("name", name)?;
+    let locale = i18n.resolve_locale(locale)?.to_string();
+
+    let mut args = FluentArgs::new();
+    args.set("name", name.as_str());
+    let message = i18n.translate(Some(&locale), "greeting", Some(&args))?;
+
+    let conn = db.conn()?;
+    greetings::record(
+        &conn,
+        NewGreeting {
+            name: &name,
+            locale: &locale,
+            window: window.label(),
+        },
+        Utc::now(),
+        &settings.get().history,
+    )?;
+    tracing::debug!(locale = %locale, window = window.label(), "greeting recorded");
+    Ok(message)
+}
+
+/// Streams a greeting for each of `names`, in order, over `on_event`.
+/// Cancel it with `cancel_request(request_id)`. Unlike `greet`, nothing is
+/// recorded in the history.
+#[tauri::command]
+#[specta::specta]
+async fn greet_many(
+    i18n: State<'_, I18n>,
+    requests: State<'_, Requests>,
+    request_id: String,
+    names: Vec<String>,
+    locale: Option<String>,
+    on_event: Channel<StreamMessage<String>>,
+) -> AppResult<u64> {
+    let i18n = &*i18n;
+    let locale = i18n.resolve_locale(locale.as_deref())?.to_string();
+    requests
+        .stream(
+            &request_id,
+            &on_event,
+            Batching::default(),
+            |greetings| async move {
+                for name in names {
+                    let name = validation::NAME.apply("name", &name)?;
+                    let mut args = FluentArgs::new();
+                    args.set("name", name.as_str());
+                    let message = i18n.translate(Some(&locale), "greeting", Some(&args))?;
+                    greetings.send(message).await?;
+                }
+                Ok(())
+            },
+        )
+        .await
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    tauri::Builder::default()
-        .plugin(tauri_plugin_notification::init())
-        .plugin(tauri_plugin_dialog::init())
-        .plugin(tauri_plugin_shell::init())
-        .invoke_handler(tauri::generate_handler![greet])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+    if let Err(err) = AppBuilder::new().run(tauri::generate_context!()) {
+        eprintln!("error while running tauri application: {err}");
+        std::process::exit(1);
+    }
 }
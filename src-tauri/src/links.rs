@@ -0,0 +1,221 @@
+use std::fs;
+use std::path::Path;
+
+use fluent_bundle::FluentArgs;
+use serde::Deserialize;
+use tauri::{AppHandle, Manager, Runtime, State, Url};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+use tauri_plugin_shell::ShellExt;
+
+use crate::error::{AppError, AppResult, ErrorCode};
+use crate::i18n::I18n;
+
+/// Name of the link policy in the app config directory. Like the command
+/// allowlist it is only ever read, so the frontend cannot widen it.
+pub const LINKS_FILE: &str = "links.json";
+
+/// Query parameters dropped by [`LinkPolicy::strip_tracking`], in addition to
+/// anything starting with `utm_`.
+pub const TRACKING_PARAMS: &[&str] = &[
+    "fbclid", "gclid", "dclid", "gbraid", "wbraid", "msclkid", "yclid", "igshid", "mc_cid",
+    "mc_eid", "_hsenc", "_hsmi",
+];
+
+/// Which links the webview may hand to the OS. Held in managed state,
+/// loaded from [`LINKS_FILE`]; the frontend cannot change it.
+///
+/// Only `https` links are allowed by default. Links to hosts outside
+/// [`LinkPolicy::allow_host`] need the user's confirmation, or are refused if
+/// confirmation is turned off.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct LinkPolicy {
+    mailto: bool,
+    hosts: Vec<String>,
+    strip_tracking: bool,
+    confirm_unknown: bool,
+}
+
+impl Default for LinkPolicy {
+    fn default() -> Self {
+        Self {
+            mailto: false,
+            hosts: Vec::new(),
+            strip_tracking: true,
+            confirm_unknown: true,
+        }
+    }
+}
+
+/// A link that passed the policy, ready to open.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckedLink {
+    pub url: Url,
+    /// Whether it may be opened without asking.
+    pub trusted: bool,
+}
+
+impl LinkPolicy {
+    /// Loads `{ "mailto": bool, "hosts": [..], "stripTracking": bool,
+    /// "confirmUnknown": bool }` from `path`, every field optional. A
+    /// missing file or field keeps the default.
+    pub fn load(path: &Path) -> AppResult<Self> {
+        match fs::read(path) {
+            Ok(bytes) => {
+                let mut policy: Self = serde_json::from_slice(&bytes)?;
+                for host in &mut policy.hosts {
+                    host.make_ascii_lowercase();
+                }
+                Ok(policy)
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn allow_mailto(mut self, allow: bool) -> Self {
+        self.mailto = allow;
+        self
+    }
+
+    /// Trusts `host`, or every subdomain of `example.com` for
+    /// `*.example.com`.
+    pub fn allow_host(mut self, host: impl Into<String>) -> Self {
+        self.hosts.push(host.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Whether to drop [`TRACKING_PARAMS`] from `https` links.
+    pub fn strip_tracking(mut self, strip: bool) -> Self {
+        self.strip_tracking = strip;
+        self
+    }
+
+    /// Whether links to untrusted hosts are offered to the user rather than
+    /// refused.
+    pub fn confirm_unknown(mut self, confirm: bool) -> Self {
+        self.confirm_unknown = confirm;
+        self
+    }
+
+    fn trusts(&self, host: &str) -> bool {
+        self.hosts
+            .iter()
+            .any(|allowed| match allowed.strip_prefix("*.") {
+                Some(domain) => host
+                    .strip_suffix(domain)
+                    .is_some_and(|sub| sub.ends_with('.')),
+                None => allowed == host,
+            })
+    }
+
+    /// Parses `url` and applies the policy, or fails with `invalid_input` or
+    /// `permission_denied` saying why the link may not be opened.
+    pub fn check(&self, url: &str) -> AppResult<CheckedLink> {
+        let mut url = Url::parse(url).map_err(|err| {
+            AppError::new(ErrorCode::InvalidInput, format!("not a valid URL: {err}"))
+                .with_details(serde_json::json!({ "field": "url", "reason": "invalid" }))
+        })?;
+        let denied = |reason: &str, message: String| {
+            AppError::new(ErrorCode::PermissionDenied, message)
+                .with_details(serde_json::json!({ "field": "url", "reason": reason }))
+        };
+
+        match url.scheme() {
+            "mailto" if self.mailto => {
+                return Ok(CheckedLink { url, trusted: true });
+            }
+            "https" => {}
+            scheme => {
+                return Err(denied(
+                    "scheme",
+                    format!("`{scheme}` links may not be opened"),
+                ));
+            }
+        }
+
+        let host = url.host_str().unwrap_or_default().to_ascii_lowercase();
+        let trusted = self.trusts(&host);
+        if !trusted && !self.confirm_unknown {
+            return Err(denied(
+                "host",
+                format!("links to `{host}` may not be opened"),
+            ));
+        }
+
+        if self.strip_tracking && url.query().is_some() {
+            let kept: Vec<(String, String)> = url
+                .query_pairs()
+                .filter(|(name, _)| {
+                    !name.starts_with("utm_") && !TRACKING_PARAMS.contains(&name.as_ref())
+                })
+                .map(|(name, value)| (name.into_owned(), value.into_owned()))
+                .collect();
+            if kept.is_empty() {
+                url.set_query(None);
+            } else {
+                url.query_pairs_mut().clear().extend_pairs(kept);
+            }
+        }
+        Ok(CheckedLink { url, trusted })
+    }
+}
+
+/// Asks whether to open a link to `host`.
+fn confirm<R: Runtime>(app: &AppHandle<R>, host: &str) -> AppResult<bool> {
+    if app.try_state::<tauri_plugin_dialog::Dialog<R>>().is_none() {
+        return Err(AppError::new(
+            ErrorCode::Internal,
+            "the dialog plugin is not registered",
+        ));
+    }
+
+    let i18n = app.state::<I18n>();
+    let mut args = FluentArgs::new();
+    args.set("host", host);
+    let title = i18n.translate(None, "link-confirm-title", None)?;
+    let message = i18n.translate(None, "link-confirm-message", Some(&args))?;
+    let open = i18n.translate(None, "link-open", None)?;
+    let cancel = i18n.translate(None, "link-cancel", None)?;
+
+    Ok(app
+        .dialog()
+        .message(message)
+        .title(title)
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(open, cancel))
+        .blocking_show())
+}
+
+/// Opens `url` in the user's browser or mail client if [`LinkPolicy`]
+/// allows it. Returns `false` if the user declined to open an untrusted
+/// link.
+#[tauri::command]
+#[specta::specta]
+pub async fn open_external<R: Runtime>(
+    app: AppHandle<R>,
+    policy: State<'_, LinkPolicy>,
+    url: String,
+) -> AppResult<bool> {
+    let link = policy.check(&url).inspect_err(|err| {
+        tracing::warn!(url = %url, "refused to open link: {err}");
+    })?;
+    let host = link.url.host_str().unwrap_or_default();
+    if !link.trusted && !confirm(&app, host)? {
+        tracing::info!(url = %link.url, "user declined to open link");
+        return Ok(false);
+    }
+
+    if app.try_state::<tauri_plugin_shell::Shell<R>>().is_none() {
+        return Err(AppError::new(
+            ErrorCode::Internal,
+            "the shell plugin is not registered",
+        ));
+    }
+    // The opener plugin is not available to us; the shell plugin's opener
+    // does the same job and is only reached through the policy above.
+    #[allow(deprecated)]
+    app.shell().open(link.url.as_str(), None)?;
+    tracing::info!(url = %link.url, trusted = link.trusted, "opened external link");
+    Ok(true)
+}
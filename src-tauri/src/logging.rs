@@ -0,0 +1,457 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use specta::Type;
+use tauri::{Runtime, State, Window};
+use tracing::Level;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::filter::EnvFilter;
+use tracing_subscriber::fmt::writer::{MakeWriter, MakeWriterExt};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt as layers, reload, Registry};
+
+use crate::crash::RECENT_LOG_ENTRIES;
+use crate::error::{AppError, AppResult, ErrorCode};
+use crate::validation::TextRule;
+
+pub const LOG_FILE_PREFIX: &str = "app";
+pub const LOG_FILE_SUFFIX: &str = "log";
+/// Daily files older than this many days are deleted on rotation.
+pub const MAX_LOG_FILES: usize = 14;
+
+/// Target of events forwarded by [`log_from_frontend`], so their level can be
+/// set like any backend module's.
+pub const FRONTEND_TARGET: &str = "frontend";
+
+pub const DEFAULT_READ_LIMIT: usize = 200;
+pub const MAX_READ_LIMIT: usize = 5000;
+
+/// Frontend messages may carry stack traces, hence newlines and a generous
+/// limit.
+const FRONTEND_MESSAGE: TextRule = TextRule::new(1, 8192).keep_whitespace().allow_newlines();
+
+/// Ordered from most to least verbose.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Trace => "trace",
+            Self::Debug => "debug",
+            Self::Info => "info",
+            Self::Warn => "warn",
+            Self::Error => "error",
+        }
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Case-insensitive, so it accepts both our names and the `INFO` style that
+/// `tracing` writes to the log files.
+impl FromStr for LogLevel {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            Self::Trace,
+            Self::Debug,
+            Self::Info,
+            Self::Warn,
+            Self::Error,
+        ]
+        .into_iter()
+        .find(|level| level.as_str().eq_ignore_ascii_case(s))
+        .ok_or_else(|| AppError::new(ErrorCode::InvalidInput, format!("unknown log level `{s}`")))
+    }
+}
+
+/// Log verbosity, stored in the settings file. `RUST_LOG`, when set, takes
+/// precedence.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+#[serde(default, rename_all = "camelCase")]
+pub struct LogSettings {
+    pub level: LogLevel,
+    /// Per-target overrides, e.g. `{ "tauri_app_lib::db": "debug" }`.
+    pub modules: BTreeMap<String, LogLevel>,
+}
+
+impl Default for LogSettings {
+    fn default() -> Self {
+        Self {
+            level: LogLevel::Info,
+            modules: BTreeMap::new(),
+        }
+    }
+}
+
+/// Builds the `tracing` filter for `settings`.
+pub fn env_filter(settings: &LogSettings) -> AppResult<EnvFilter> {
+    let mut directives = settings.level.to_string();
+    for (module, level) in &settings.modules {
+        directives.push_str(&format!(",{module}={level}"));
+    }
+    EnvFilter::try_new(&directives).map_err(|err| {
+        AppError::new(
+            ErrorCode::InvalidInput,
+            format!("invalid log filter `{directives}`: {err}"),
+        )
+    })
+}
+
+/// The log directory, plus the global subscriber's handles once
+/// [`Logging::install`] has run.
+pub struct Logging {
+    dir: PathBuf,
+    recent: RecentLines,
+    installed: Mutex<Option<Installed>>,
+}
+
+struct Installed {
+    filter: reload::Handle<EnvFilter, Registry>,
+    /// Set when `RUST_LOG` chose the filter, so settings changes leave it be.
+    from_env: bool,
+    // Flushes buffered lines to the file when dropped.
+    _guard: WorkerGuard,
+}
+
+impl Logging {
+    /// Reads logs from `dir` without writing any; see [`Logging::install`].
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            recent: RecentLines::default(),
+            installed: Mutex::new(None),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// What was logged last, once [`Logging::install`] has run.
+    pub fn recent(&self) -> &RecentLines {
+        &self.recent
+    }
+
+    /// Installs the global subscriber, writing JSON lines to daily files in
+    /// the log directory and to [`Logging::recent`] (and to stderr in debug
+    /// builds). Fails if a subscriber is already installed in this process.
+    pub fn install(&self, settings: &LogSettings) -> AppResult<()> {
+        let (filter, from_env) = match EnvFilter::try_from_default_env() {
+            Ok(filter) => (filter, true),
+            // A bad filter in the settings file must not keep the app from
+            // starting.
+            Err(_) => (
+                env_filter(settings).or_else(|_| env_filter(&LogSettings::default()))?,
+                false,
+            ),
+        };
+        let (filter, handle) = reload::Layer::new(filter);
+
+        let appender = RollingFileAppender::builder()
+            .rotation(Rotation::DAILY)
+            .filename_prefix(LOG_FILE_PREFIX)
+            .filename_suffix(LOG_FILE_SUFFIX)
+            .max_log_files(MAX_LOG_FILES)
+            .build(&self.dir)
+            .map_err(|err| AppError::new(ErrorCode::Io, err.to_string()))?;
+        let (writer, guard) = tracing_appender::non_blocking(appender);
+
+        let stderr = cfg!(debug_assertions).then(|| layers::layer().with_writer(std::io::stderr));
+        tracing_subscriber::registry()
+            .with(filter)
+            .with(
+                layers::layer()
+                    .json()
+                    .with_current_span(false)
+                    .with_span_list(false)
+                    .with_writer(writer.and(self.recent.clone())),
+            )
+            .with(stderr)
+            .try_init()
+            .map_err(|err| AppError::internal(format!("could not install logger: {err}")))?;
+
+        *self.installed.lock().unwrap() = Some(Installed {
+            filter: handle,
+            from_env,
+            _guard: guard,
+        });
+        Ok(())
+    }
+
+    /// Switches the installed subscriber to `settings`. Does nothing before
+    /// [`Logging::install`] or while `RUST_LOG` is in charge.
+    pub fn reload(&self, settings: &LogSettings) -> AppResult<()> {
+        let installed = self.installed.lock().unwrap();
+        match installed.as_ref() {
+            Some(installed) if !installed.from_env => installed
+                .filter
+                .reload(env_filter(settings)?)
+                .map_err(|err| AppError::internal(err.to_string())),
+            _ => Ok(()),
+        }
+    }
+
+    /// Writes out buffered lines and stops logging to the file. Call on exit.
+    pub fn flush(&self) {
+        self.installed.lock().unwrap().take();
+    }
+}
+
+/// The last [`RECENT_LOG_ENTRIES`] lines logged, kept in memory so a panic
+/// hook can include them without reading the log files.
+#[derive(Debug, Clone, Default)]
+pub struct RecentLines(Arc<Mutex<VecDeque<String>>>);
+
+impl RecentLines {
+    /// The lines held, oldest first, or `None` while another thread is
+    /// adding one. Never blocks.
+    pub fn try_entries(&self) -> Option<Vec<LogEntry>> {
+        let lines = self.0.try_lock().ok()?;
+        Some(
+            lines
+                .iter()
+                .filter_map(|line| LogEntry::parse(line))
+                .collect(),
+        )
+    }
+
+    fn push(&self, bytes: &[u8]) {
+        let Ok(mut lines) = self.0.lock() else {
+            return;
+        };
+        for line in String::from_utf8_lossy(bytes).lines() {
+            if lines.len() == RECENT_LOG_ENTRIES {
+                lines.pop_front();
+            }
+            lines.push_back(line.to_owned());
+        }
+    }
+}
+
+impl<'a> MakeWriter<'a> for RecentLines {
+    type Writer = RecentWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RecentWriter {
+            recent: self.clone(),
+            buf: Vec::new(),
+        }
+    }
+}
+
+/// Collects one formatted event and hands it to [`RecentLines`] when
+/// dropped.
+pub struct RecentWriter {
+    recent: RecentLines,
+    buf: Vec<u8>,
+}
+
+impl io::Write for RecentWriter {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.buf.extend_from_slice(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for RecentWriter {
+    fn drop(&mut self) {
+        self.recent.push(&self.buf);
+    }
+}
+
+/// One line of a log file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LogEntry {
+    pub timestamp: DateTime<Utc>,
+    pub level: LogLevel,
+    /// Module path of the code that logged, or [`FRONTEND_TARGET`].
+    pub target: String,
+    pub message: String,
+    /// Structured fields other than the message.
+    pub fields: Map<String, Value>,
+}
+
+/// Shape of the lines written by `tracing_subscriber`'s JSON formatter.
+#[derive(Deserialize)]
+struct RawEntry {
+    timestamp: DateTime<Utc>,
+    level: String,
+    target: String,
+    #[serde(default)]
+    fields: Map<String, Value>,
+}
+
+impl LogEntry {
+    /// Parses a line as written by [`Logging::install`]. Returns `None` for
+    /// anything else, such as a line still being written.
+    pub fn parse(line: &str) -> Option<Self> {
+        let raw: RawEntry = serde_json::from_str(line).ok()?;
+        let mut fields = raw.fields;
+        let message = match fields.remove("message") {
+            Some(Value::String(message)) => message,
+            Some(other) => other.to_string(),
+            None => String::new(),
+        };
+        Some(Self {
+            timestamp: raw.timestamp,
+            level: raw.level.parse().ok()?,
+            target: raw.target,
+            message,
+            fields,
+        })
+    }
+}
+
+/// Which entries [`read`] returns. Every filter is optional.
+#[derive(Debug, Clone, Default, Deserialize, Type)]
+#[serde(default, rename_all = "camelCase")]
+pub struct LogFilter {
+    /// Minimum severity.
+    pub level: Option<LogLevel>,
+    /// Target prefix, e.g. `tauri_app_lib::db` or `frontend`.
+    pub target: Option<String>,
+    /// Case-insensitive substring of the message.
+    pub text: Option<String>,
+}
+
+impl LogFilter {
+    fn matches(&self, entry: &LogEntry, text: Option<&str>) -> bool {
+        self.level.is_none_or(|level| entry.level >= level)
+            && self
+                .target
+                .as_deref()
+                .is_none_or(|target| entry.target.starts_with(target))
+            && text.is_none_or(|text| entry.message.to_lowercase().contains(text))
+    }
+}
+
+/// The most recent entries in `dir` matching `filter` and logged at or after
+/// `since`, oldest first. `limit` defaults to [`DEFAULT_READ_LIMIT`] and is
+/// capped at [`MAX_READ_LIMIT`].
+pub fn read(
+    dir: &Path,
+    filter: &LogFilter,
+    since: Option<DateTime<Utc>>,
+    limit: Option<usize>,
+) -> AppResult<Vec<LogEntry>> {
+    let limit = limit.unwrap_or(DEFAULT_READ_LIMIT).clamp(1, MAX_READ_LIMIT);
+    let text = filter.text.as_deref().map(str::to_lowercase);
+
+    let mut entries = Vec::new();
+    // Newest file first, so we can stop once `limit` is reached.
+    for path in log_files(dir)?.into_iter().rev() {
+        let mut matched: Vec<LogEntry> = BufReader::new(fs::File::open(&path)?)
+            .lines()
+            .map_while(Result::ok)
+            .filter_map(|line| LogEntry::parse(&line))
+            .filter(|entry| since.is_none_or(|since| entry.timestamp >= since))
+            .filter(|entry| filter.matches(entry, text.as_deref()))
+            .collect();
+        let take = matched.len().min(limit - entries.len());
+        entries.extend(matched.drain(matched.len() - take..).rev());
+        if entries.len() == limit {
+            break;
+        }
+    }
+    entries.reverse();
+    Ok(entries)
+}
+
+/// Log files in `dir`, oldest first. Their names embed the date, so name order
+/// is chronological.
+fn log_files(dir: &Path) -> AppResult<Vec<PathBuf>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+
+    let prefix = format!("{LOG_FILE_PREFIX}.");
+    let suffix = format!(".{LOG_FILE_SUFFIX}");
+    let mut files = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        let is_log = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with(&prefix) && name.ends_with(&suffix));
+        if is_log {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Records a frontend `console` message under [`FRONTEND_TARGET`].
+#[tauri::command]
+#[specta::specta]
+pub fn log_from_frontend<R: Runtime>(
+    window: Window<R>,
+    level: LogLevel,
+    message: &str,
+    context: Option<Value>,
+) -> AppResult<()> {
+    let message = FRONTEND_MESSAGE.apply("message", message)?;
+    let context = context.map(|context| context.to_string());
+
+    macro_rules! forward {
+        ($level:expr) => {
+            tracing::event!(
+                target: FRONTEND_TARGET,
+                $level,
+                window = window.label(),
+                context = context.as_deref(),
+                "{message}"
+            )
+        };
+    }
+    match level {
+        LogLevel::Trace => forward!(Level::TRACE),
+        LogLevel::Debug => forward!(Level::DEBUG),
+        LogLevel::Info => forward!(Level::INFO),
+        LogLevel::Warn => forward!(Level::WARN),
+        LogLevel::Error => forward!(Level::ERROR),
+    }
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn read_logs(
+    logging: State<'_, Logging>,
+    filter: Option<LogFilter>,
+    since: Option<DateTime<Utc>>,
+    limit: Option<usize>,
+) -> AppResult<Vec<LogEntry>> {
+    read(logging.dir(), &filter.unwrap_or_default(), since, limit)
+}
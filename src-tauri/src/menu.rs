@@ -0,0 +1,492 @@
+use std::path::{Path, PathBuf};
+
+#[cfg(desktop)]
+use tauri::menu::{
+    CheckMenuItem, Menu, MenuEvent, MenuItem, MenuItemKind, PredefinedMenuItem, Submenu,
+};
+use tauri::{AppHandle, Manager, Runtime, WebviewWindow};
+#[cfg(desktop)]
+use tauri::{Listener, Window, WindowEvent};
+
+use crate::db::recent_files;
+use crate::db::Database;
+use crate::documents::{self, Documents};
+use crate::error::{AppError, AppResult};
+#[cfg(desktop)]
+use crate::events::{DOCUMENTS_CHANGED, RECENT_FILES_CHANGED, SETTINGS_CHANGED};
+use crate::files;
+use crate::i18n::I18n;
+use crate::windows::{WindowManager, WindowRole};
+
+/// How many recent files a menu lists.
+pub const RECENT_ITEMS: usize = 10;
+
+const RECENT_PREFIX: &str = "recent:";
+
+/// Items whose behavior the platform provides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Predefined {
+    Undo,
+    Redo,
+    Cut,
+    Copy,
+    Paste,
+    SelectAll,
+}
+
+/// One entry of a menu, independent of any display.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MenuEntry {
+    Item {
+        id: String,
+        label: String,
+        enabled: bool,
+        /// E.g. `CmdOrCtrl+S`.
+        accelerator: Option<String>,
+    },
+    Check {
+        id: String,
+        label: String,
+        checked: bool,
+    },
+    Predefined {
+        kind: Predefined,
+        label: String,
+    },
+    Submenu {
+        label: String,
+        items: Vec<MenuEntry>,
+    },
+    Separator,
+}
+
+impl MenuEntry {
+    /// The entry's label; `None` for a separator.
+    pub fn label(&self) -> Option<&str> {
+        match self {
+            Self::Item { label, .. }
+            | Self::Check { label, .. }
+            | Self::Predefined { label, .. }
+            | Self::Submenu { label, .. } => Some(label),
+            Self::Separator => None,
+        }
+    }
+
+    /// The item with `id`, searching submenus too.
+    pub fn find<'a>(entries: &'a [MenuEntry], id: &str) -> Option<&'a MenuEntry> {
+        entries.iter().find_map(|entry| match entry {
+            Self::Item { id: found, .. } | Self::Check { id: found, .. } if found == id => {
+                Some(entry)
+            }
+            Self::Submenu { items, .. } => Self::find(items, id),
+            _ => None,
+        })
+    }
+}
+
+/// What picking an application menu item does. The tray's menu uses these
+/// too for the items both menus share.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MenuAction {
+    New,
+    Open,
+    OpenRecent(PathBuf),
+    ClearRecent,
+    /// Saves what [`MenuModel::save_targets`] returns.
+    Save,
+    SaveAs,
+    CloseWindow,
+    /// Quits once unsaved documents are saved or discarded.
+    Quit,
+    Settings,
+    ToggleFullscreen,
+    About,
+}
+
+impl MenuAction {
+    /// The menu item id this action is bound to.
+    pub fn id(&self) -> String {
+        match self {
+            Self::OpenRecent(path) => format!("{RECENT_PREFIX}{}", path.display()),
+            Self::New => "new".into(),
+            Self::Open => "open".into(),
+            Self::ClearRecent => "clear-recent".into(),
+            Self::Save => "save".into(),
+            Self::SaveAs => "save-as".into(),
+            Self::CloseWindow => "close-window".into(),
+            Self::Quit => "quit".into(),
+            Self::Settings => "settings".into(),
+            Self::ToggleFullscreen => "toggle-fullscreen".into(),
+            Self::About => "about".into(),
+        }
+    }
+
+    /// The action bound to a menu item id, if any.
+    pub fn parse(id: &str) -> Option<Self> {
+        Some(match id {
+            "new" => Self::New,
+            "open" => Self::Open,
+            "clear-recent" => Self::ClearRecent,
+            "save" => Self::Save,
+            "save-as" => Self::SaveAs,
+            "close-window" => Self::CloseWindow,
+            "quit" => Self::Quit,
+            "settings" => Self::Settings,
+            "toggle-fullscreen" => Self::ToggleFullscreen,
+            "about" => Self::About,
+            _ => {
+                return id
+                    .strip_prefix(RECENT_PREFIX)
+                    .map(|path| Self::OpenRecent(path.into()))
+            }
+        })
+    }
+}
+
+/// The app state the menu bar depends on. The menu is rebuilt from scratch
+/// whenever any of it changes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MenuModel {
+    /// The document shown in the focused window, if it shows one.
+    pub focused_document: Option<u64>,
+    /// Documents with unsaved changes.
+    pub dirty: Vec<u64>,
+    /// Most recent first, at most [`RECENT_ITEMS`].
+    pub recent: Vec<PathBuf>,
+}
+
+impl MenuModel {
+    /// Reads the model from the app's state.
+    pub fn current<R: Runtime>(app: &AppHandle<R>) -> AppResult<Self> {
+        let focused_document = focused_window(app).and_then(|window| {
+            app.state::<WindowManager>()
+                .get(window.label())
+                .and_then(|info| info.document)
+        });
+        let dirty = app
+            .state::<Documents>()
+            .dirty()
+            .into_iter()
+            .map(|document| document.id)
+            .collect();
+        Ok(Self {
+            focused_document,
+            dirty,
+            recent: recent(app)?,
+        })
+    }
+
+    /// What Save saves: the focused window's document if it has unsaved
+    /// changes, or every document that has when the focused window shows
+    /// none. Save is disabled when this is empty.
+    pub fn save_targets(&self) -> Vec<u64> {
+        match self.focused_document {
+            Some(id) => self
+                .dirty
+                .iter()
+                .copied()
+                .filter(|&dirty| dirty == id)
+                .collect(),
+            None => self.dirty.clone(),
+        }
+    }
+
+    pub fn entries(&self, i18n: &I18n) -> AppResult<Vec<MenuEntry>> {
+        let text = |key| i18n.translate(None, key, None);
+        let item = |action: MenuAction, key, accelerator: Option<&str>, enabled| {
+            Ok::<_, AppError>(MenuEntry::Item {
+                id: action.id(),
+                label: text(key)?,
+                enabled,
+                accelerator: accelerator.map(Into::into),
+            })
+        };
+        let predefined = |kind, key| {
+            Ok::<_, AppError>(MenuEntry::Predefined {
+                kind,
+                label: text(key)?,
+            })
+        };
+
+        let mut recent = recent_items(&self.recent, i18n)?;
+        if !self.recent.is_empty() {
+            recent.push(MenuEntry::Separator);
+            recent.push(item(
+                MenuAction::ClearRecent,
+                "menu-clear-recent",
+                None,
+                true,
+            )?);
+        }
+        let file = vec![
+            item(MenuAction::New, "menu-new", Some("CmdOrCtrl+N"), true)?,
+            item(MenuAction::Open, "menu-open", Some("CmdOrCtrl+O"), true)?,
+            MenuEntry::Submenu {
+                label: text("menu-open-recent")?,
+                items: recent,
+            },
+            MenuEntry::Separator,
+            item(
+                MenuAction::Save,
+                "menu-save",
+                Some("CmdOrCtrl+S"),
+                !self.save_targets().is_empty(),
+            )?,
+            item(
+                MenuAction::SaveAs,
+                "menu-save-as",
+                Some("CmdOrCtrl+Shift+S"),
+                self.focused_document.is_some(),
+            )?,
+            MenuEntry::Separator,
+            item(
+                MenuAction::CloseWindow,
+                "menu-close-window",
+                Some("CmdOrCtrl+W"),
+                true,
+            )?,
+            item(MenuAction::Quit, "menu-quit", Some("CmdOrCtrl+Q"), true)?,
+        ];
+        let edit = vec![
+            predefined(Predefined::Undo, "menu-undo")?,
+            predefined(Predefined::Redo, "menu-redo")?,
+            MenuEntry::Separator,
+            predefined(Predefined::Cut, "menu-cut")?,
+            predefined(Predefined::Copy, "menu-copy")?,
+            predefined(Predefined::Paste, "menu-paste")?,
+            predefined(Predefined::SelectAll, "menu-select-all")?,
+            MenuEntry::Separator,
+            item(
+                MenuAction::Settings,
+                "menu-settings",
+                Some("CmdOrCtrl+,"),
+                true,
+            )?,
+        ];
+        let view = vec![item(
+            MenuAction::ToggleFullscreen,
+            "menu-fullscreen",
+            Some("F11"),
+            true,
+        )?];
+        let help = vec![item(MenuAction::About, "menu-about", None, true)?];
+
+        Ok(vec![
+            MenuEntry::Submenu {
+                label: text("menu-file")?,
+                items: file,
+            },
+            MenuEntry::Submenu {
+                label: text("menu-edit")?,
+                items: edit,
+            },
+            MenuEntry::Submenu {
+                label: text("menu-view")?,
+                items: view,
+            },
+            MenuEntry::Submenu {
+                label: text("menu-help")?,
+                items: help,
+            },
+        ])
+    }
+}
+
+/// The most recent files, at most [`RECENT_ITEMS`].
+pub fn recent<R: Runtime>(app: &AppHandle<R>) -> AppResult<Vec<PathBuf>> {
+    let conn = app.state::<Database>().conn()?;
+    Ok(recent_files::list(&conn)?
+        .into_iter()
+        .take(RECENT_ITEMS)
+        .map(|file| file.path)
+        .collect())
+}
+
+fn file_label(path: &Path) -> String {
+    path.file_name()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// An item per recent file that opens it, or a disabled placeholder when
+/// there are none.
+pub fn recent_items(recent: &[PathBuf], i18n: &I18n) -> AppResult<Vec<MenuEntry>> {
+    if recent.is_empty() {
+        return Ok(vec![MenuEntry::Item {
+            id: "recent-empty".into(),
+            label: i18n.translate(None, "menu-recent-empty", None)?,
+            enabled: false,
+            accelerator: None,
+        }]);
+    }
+    Ok(recent
+        .iter()
+        .map(|path| MenuEntry::Item {
+            id: MenuAction::OpenRecent(path.clone()).id(),
+            label: file_label(path),
+            enabled: true,
+            accelerator: None,
+        })
+        .collect())
+}
+
+#[cfg(desktop)]
+fn build_items<R: Runtime>(
+    app: &AppHandle<R>,
+    entries: &[MenuEntry],
+) -> AppResult<Vec<MenuItemKind<R>>> {
+    entries
+        .iter()
+        .map(|entry| {
+            Ok(match entry {
+                MenuEntry::Item {
+                    id,
+                    label,
+                    enabled,
+                    accelerator,
+                } => MenuItemKind::MenuItem(MenuItem::with_id(
+                    app,
+                    id,
+                    label,
+                    *enabled,
+                    accelerator.as_deref(),
+                )?),
+                MenuEntry::Check { id, label, checked } => MenuItemKind::Check(
+                    CheckMenuItem::with_id(app, id, label, true, *checked, None::<&str>)?,
+                ),
+                MenuEntry::Predefined { kind, label } => {
+                    let label = Some(label.as_str());
+                    MenuItemKind::Predefined(match kind {
+                        Predefined::Undo => PredefinedMenuItem::undo(app, label)?,
+                        Predefined::Redo => PredefinedMenuItem::redo(app, label)?,
+                        Predefined::Cut => PredefinedMenuItem::cut(app, label)?,
+                        Predefined::Copy => PredefinedMenuItem::copy(app, label)?,
+                        Predefined::Paste => PredefinedMenuItem::paste(app, label)?,
+                        Predefined::SelectAll => PredefinedMenuItem::select_all(app, label)?,
+                    })
+                }
+                MenuEntry::Submenu { label, items } => {
+                    let items = build_items(app, items)?;
+                    let refs: Vec<_> = items.iter().map(|item| item as _).collect();
+                    MenuItemKind::Submenu(Submenu::with_items(app, label, true, &refs)?)
+                }
+                MenuEntry::Separator => {
+                    MenuItemKind::Predefined(PredefinedMenuItem::separator(app)?)
+                }
+            })
+        })
+        .collect()
+}
+
+/// Builds a native menu from `entries`.
+#[cfg(desktop)]
+pub fn build_menu<R: Runtime>(app: &AppHandle<R>, entries: &[MenuEntry]) -> AppResult<Menu<R>> {
+    let items = build_items(app, entries)?;
+    let refs: Vec<_> = items.iter().map(|item| item as _).collect();
+    Ok(Menu::with_items(app, &refs)?)
+}
+
+/// The window that has keyboard focus, if any of the app's does.
+pub fn focused_window<R: Runtime>(app: &AppHandle<R>) -> Option<WebviewWindow<R>> {
+    app.webview_windows()
+        .into_values()
+        .find(|window| window.is_focused().unwrap_or(false))
+}
+
+/// Carries out a menu action through the same functions the matching
+/// commands use. May show dialogs and block until they close, so never call
+/// it from the main thread.
+pub fn dispatch<R: Runtime>(app: &AppHandle<R>, action: MenuAction) -> AppResult<()> {
+    tracing::debug!(?action, "menu action");
+    let documents = app.state::<Documents>();
+    let windows = app.state::<WindowManager>();
+    let show = |id: u64| windows.open(app, WindowRole::Document, Some(id)).map(drop);
+    match action {
+        MenuAction::New => show(documents.create().id),
+        MenuAction::Open => match documents::open_with_dialog(app)? {
+            Some(document) => show(document.id),
+            None => Ok(()),
+        },
+        MenuAction::OpenRecent(path) => show(documents::open_recent(app, &path)?.id),
+        MenuAction::ClearRecent => files::clear_recent(app).map(drop),
+        MenuAction::Save => {
+            for id in MenuModel::current(app)?.save_targets() {
+                if documents::save_with_dialog(app, id, false)?.is_none() {
+                    break;
+                }
+            }
+            Ok(())
+        }
+        MenuAction::SaveAs => match MenuModel::current(app)?.focused_document {
+            Some(id) => documents::save_as_with_dialog(app, id).map(drop),
+            None => Ok(()),
+        },
+        MenuAction::CloseWindow => match focused_window(app) {
+            Some(window) => Ok(window.close()?),
+            None => Ok(()),
+        },
+        MenuAction::Quit => {
+            documents::confirm_unsaved(app, |app| app.exit(0));
+            Ok(())
+        }
+        MenuAction::Settings => windows.open(app, WindowRole::Settings, None).map(drop),
+        MenuAction::About => windows.open(app, WindowRole::About, None).map(drop),
+        MenuAction::ToggleFullscreen => match focused_window(app) {
+            Some(window) => Ok(window.set_fullscreen(!window.is_fullscreen()?)?),
+            None => Ok(()),
+        },
+    }
+}
+
+/// Routes a click on any menu item bound to a [`MenuAction`] to
+/// [`dispatch`], off the main thread.
+#[cfg(desktop)]
+pub fn on_menu_event<R: Runtime>(app: &AppHandle<R>, event: MenuEvent) {
+    let Some(action) = MenuAction::parse(event.id().as_ref()) else {
+        return;
+    };
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        if let Err(err) = dispatch(&app, action) {
+            tracing::warn!("menu action failed: {err}");
+        }
+    });
+}
+
+/// Sets the application menu and keeps it up to date.
+#[cfg(desktop)]
+pub fn create<R: Runtime>(app: &AppHandle<R>) -> AppResult<()> {
+    let model = MenuModel::current(app)?;
+    app.set_menu(build_menu(app, &model.entries(&app.state::<I18n>())?)?)?;
+    for event in [DOCUMENTS_CHANGED, RECENT_FILES_CHANGED, SETTINGS_CHANGED] {
+        let app = app.clone();
+        app.clone().listen_any(event, move |_| refresh(&app));
+    }
+    Ok(())
+}
+
+/// Rebuilds the application menu from the app's current state. Does nothing
+/// without one.
+#[cfg(desktop)]
+pub fn refresh<R: Runtime>(app: &AppHandle<R>) {
+    if app.menu().is_none() {
+        return;
+    }
+    let update = || -> AppResult<()> {
+        let model = MenuModel::current(app)?;
+        app.set_menu(build_menu(app, &model.entries(&app.state::<I18n>())?)?)?;
+        Ok(())
+    };
+    if let Err(err) = update() {
+        tracing::warn!("could not update the menu: {err}");
+    }
+}
+
+/// Updates the menu for whichever window gains focus.
+#[cfg(desktop)]
+pub fn on_window_event<R: Runtime>(window: &Window<R>, event: &WindowEvent) {
+    if let WindowEvent::Focused(true) = event {
+        refresh(window.app_handle());
+    }
+}
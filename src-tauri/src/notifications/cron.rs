@@ -0,0 +1,193 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, TimeZone, Timelike};
+
+use crate::error::{AppError, ErrorCode};
+
+/// How far ahead [`Cron::next_after`] looks before giving up, so that
+/// expressions that can never match (e.g. `0 0 31 2 *`) terminate.
+const SEARCH_YEARS: i32 = 5;
+
+/// A five-field cron expression: minute, hour, day of month, month and day of
+/// week (0 or 7 is Sunday).
+///
+/// Each field accepts `*`, numbers, ranges (`1-5`), lists (`1,15`) and steps
+/// (`*/15`, `8-18/2`). As in classic cron, when both day fields are
+/// restricted a time matches if either of them does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cron {
+    source: String,
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    any_day: bool,
+    any_weekday: bool,
+}
+
+struct Field {
+    name: &'static str,
+    min: u32,
+    max: u32,
+}
+
+const MINUTE: Field = Field {
+    name: "minute",
+    min: 0,
+    max: 59,
+};
+const HOUR: Field = Field {
+    name: "hour",
+    min: 0,
+    max: 23,
+};
+const DAY: Field = Field {
+    name: "day of month",
+    min: 1,
+    max: 31,
+};
+const MONTH: Field = Field {
+    name: "month",
+    min: 1,
+    max: 12,
+};
+const WEEKDAY: Field = Field {
+    name: "day of week",
+    min: 0,
+    max: 7,
+};
+
+impl Field {
+    /// Parses one field into a bit set of the values it matches.
+    fn parse(&self, expr: &str, input: &str) -> Result<u64, AppError> {
+        let invalid = || invalid(expr, format!("invalid {} `{input}`", self.name));
+        let number = |s: &str| {
+            s.parse::<u32>()
+                .ok()
+                .filter(|n| (self.min..=self.max).contains(n))
+                .ok_or_else(invalid)
+        };
+
+        let mut bits = 0;
+        for part in input.split(',') {
+            let (range, step) = match part.split_once('/') {
+                Some((range, step)) => (range, step.parse::<u32>().map_err(|_| invalid())?),
+                None => (part, 1),
+            };
+            if step == 0 {
+                return Err(invalid());
+            }
+            let (start, end) = match range {
+                "*" => (self.min, self.max),
+                _ => match range.split_once('-') {
+                    Some((start, end)) => (number(start)?, number(end)?),
+                    None if step > 1 => (number(range)?, self.max),
+                    None => {
+                        let n = number(range)?;
+                        (n, n)
+                    }
+                },
+            };
+            if start > end {
+                return Err(invalid());
+            }
+            for value in (start..=end).step_by(step as usize) {
+                bits |= 1 << value;
+            }
+        }
+        Ok(bits)
+    }
+}
+
+fn invalid(expr: &str, reason: String) -> AppError {
+    AppError::new(
+        ErrorCode::InvalidInput,
+        format!("invalid cron expression `{expr}`: {reason}"),
+    )
+    .with_details(serde_json::json!({ "field": "expression" }))
+}
+
+fn has(bits: u64, value: u32) -> bool {
+    bits & (1 << value) != 0
+}
+
+impl FromStr for Cron {
+    type Err = AppError;
+
+    fn from_str(expr: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(invalid(expr, "expected 5 fields".into()));
+        };
+
+        let mut weekdays = WEEKDAY.parse(expr, weekday)?;
+        // 7 is an alias for Sunday.
+        if has(weekdays, 7) {
+            weekdays = (weekdays & !(1 << 7)) | 1;
+        }
+
+        Ok(Self {
+            source: fields.join(" "),
+            minutes: MINUTE.parse(expr, minute)?,
+            hours: HOUR.parse(expr, hour)?,
+            days: DAY.parse(expr, day)?,
+            months: MONTH.parse(expr, month)?,
+            weekdays,
+            any_day: day == "*",
+            any_weekday: weekday == "*",
+        })
+    }
+}
+
+impl fmt::Display for Cron {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl Cron {
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let day = has(self.days, date.day());
+        let weekday = has(self.weekdays, date.weekday().num_days_from_sunday());
+        match (self.any_day, self.any_weekday) {
+            (false, false) => day || weekday,
+            _ => day && weekday,
+        }
+    }
+
+    /// The first matching minute strictly after `after`, in `after`'s time
+    /// zone. Local times skipped by a DST change never match.
+    pub fn next_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let tz = after.timezone();
+        let start = after.naive_local();
+        let mut time = start.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let limit = start.year() + SEARCH_YEARS;
+
+        while time.year() <= limit {
+            if !has(self.months, time.month()) {
+                let (year, month) = match time.month() {
+                    12 => (time.year() + 1, 1),
+                    month => (time.year(), month + 1),
+                };
+                time = NaiveDate::from_ymd_opt(year, month, 1)?.and_hms_opt(0, 0, 0)?;
+            } else if !self.matches_day(time.date()) {
+                time = start_of_next_day(time)?;
+            } else if !has(self.hours, time.hour()) {
+                time = time.with_minute(0)? + Duration::hours(1);
+            } else if !has(self.minutes, time.minute()) {
+                time += Duration::minutes(1);
+            } else if let Some(local) = tz.from_local_datetime(&time).earliest() {
+                return Some(local);
+            } else {
+                time += Duration::minutes(1);
+            }
+        }
+        None
+    }
+}
+
+fn start_of_next_day(time: NaiveDateTime) -> Option<NaiveDateTime> {
+    time.date().succ_opt()?.and_hms_opt(0, 0, 0)
+}
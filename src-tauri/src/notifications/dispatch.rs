@@ -0,0 +1,263 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use fluent_bundle::FluentArgs;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::State;
+
+use super::clock::Clock;
+use super::{CategoryPreferences, Notification, NotificationSettings, Notifier, Priority};
+use crate::db::notifications::{self, DeliveryStatus, NotificationRecord};
+use crate::db::Database;
+use crate::error::AppResult;
+use crate::i18n::I18n;
+use crate::settings::SettingsStore;
+
+/// Category of the digest shown when quiet hours end.
+pub const DIGEST_CATEGORY: &str = "digest";
+
+/// How many deferred titles a digest lists before summarizing the rest.
+pub const DIGEST_LINES: usize = 5;
+
+/// How long to wait before showing a failed digest again. Doubles with every
+/// failure, up to [`MAX_DIGEST_RETRY`].
+pub const DIGEST_RETRY: Duration = Duration::minutes(1);
+
+/// Longest wait between attempts to show a digest.
+pub const MAX_DIGEST_RETRY: Duration = Duration::hours(6);
+
+/// When to try a digest again after its `attempts`-th failure, counting
+/// from one.
+pub fn digest_retry_delay(attempts: u32) -> Duration {
+    let factor = 1i32 << attempts.saturating_sub(1).min(16);
+    (DIGEST_RETRY * factor).min(MAX_DIGEST_RETRY)
+}
+
+/// What happens to non-urgent notifications during quiet hours.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum QuietMode {
+    /// Hold them for a digest once quiet hours end.
+    #[default]
+    Defer,
+    /// Drop them; they are still recorded in the history.
+    Suppress,
+}
+
+/// A daily do-not-disturb window, which may span midnight. `start == end`
+/// is an empty window.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct QuietHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+    #[serde(default)]
+    pub mode: QuietMode,
+}
+
+impl QuietHours {
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+/// Told about every notification the [`Dispatcher`] records, once it is in
+/// the history.
+pub type RecordListener = Box<dyn Fn(&Connection, &NotificationRecord) + Send + Sync>;
+
+/// Shows notifications, applying quiet hours and recording every attempt in
+/// the notification history. Everything the app shows goes through here.
+pub struct Dispatcher {
+    clock: Arc<dyn Clock>,
+    notifier: Arc<dyn Notifier>,
+    listener: Option<RecordListener>,
+}
+
+impl Dispatcher {
+    pub fn new(clock: Arc<dyn Clock>, notifier: Arc<dyn Notifier>) -> Self {
+        Self {
+            clock,
+            notifier,
+            listener: None,
+        }
+    }
+
+    pub fn with_listener(mut self, listener: RecordListener) -> Self {
+        self.listener = Some(listener);
+        self
+    }
+
+    pub fn now(&self) -> DateTime<Utc> {
+        self.clock.now()
+    }
+
+    fn is_quiet(&self, settings: &NotificationSettings) -> Option<QuietMode> {
+        if settings.paused {
+            return Some(QuietMode::Defer);
+        }
+        let quiet = settings.quiet_hours.as_ref()?;
+        quiet
+            .contains(self.clock.now_local().time())
+            .then_some(quiet.mode)
+    }
+
+    /// Shows `notification` now, applying the preferences for its category:
+    /// disabled categories are dropped, and during quiet hours anything not
+    /// urgent or high priority is deferred or suppressed. While notifications
+    /// are paused, they are deferred as during quiet hours.
+    pub fn send(
+        &self,
+        conn: &Connection,
+        notification: Notification,
+        settings: &NotificationSettings,
+    ) -> AppResult<NotificationRecord> {
+        let notification = notification.validated()?;
+        let preferences = settings.preferences(notification.category.as_deref());
+        let urgent = notification.urgent || preferences.priority == Priority::High;
+        let held = match self.is_quiet(settings) {
+            _ if !preferences.enabled => Some(DeliveryStatus::Disabled),
+            Some(_) if urgent => None,
+            Some(_) if preferences.priority == Priority::Low => Some(DeliveryStatus::Suppressed),
+            Some(QuietMode::Defer) => Some(DeliveryStatus::Deferred),
+            Some(QuietMode::Suppress) => Some(DeliveryStatus::Suppressed),
+            None => None,
+        };
+        let record = match held {
+            Some(status) => notifications::record(conn, &notification, status, None, self.now())?,
+            None => self.deliver(conn, &notification, &preferences)?,
+        };
+        self.recorded(conn, &record);
+        Ok(record)
+    }
+
+    /// Once quiet hours are over and notifications are not paused, shows
+    /// everything they deferred as a single digest. Returns the digest, if one was shown.
+    ///
+    /// A digest that cannot be shown keeps its row in the history and is
+    /// tried again after [`digest_retry_delay`], so a notifier that keeps
+    /// failing does not add a digest on every scheduler pass.
+    pub fn send_digest(
+        &self,
+        conn: &Connection,
+        settings: &NotificationSettings,
+        i18n: &I18n,
+    ) -> AppResult<Option<NotificationRecord>> {
+        if self.is_quiet(settings).is_some() {
+            return Ok(None);
+        }
+        let deferred = notifications::deferred(conn)?;
+        if deferred.is_empty() {
+            return Ok(None);
+        }
+        let failed = notifications::failed_digest(conn)?;
+        if failed
+            .as_ref()
+            .is_some_and(|failed| self.now() < failed.retry_at)
+        {
+            return Ok(None);
+        }
+
+        let mut args = FluentArgs::new();
+        args.set("count", deferred.len());
+        let title = i18n.translate(None, "notification-digest-title", Some(&args))?;
+
+        let mut lines: Vec<String> = deferred
+            .iter()
+            .take(DIGEST_LINES)
+            .map(|record| record.notification.title.clone())
+            .collect();
+        if deferred.len() > DIGEST_LINES {
+            let mut args = FluentArgs::new();
+            args.set("count", deferred.len() - DIGEST_LINES);
+            lines.push(i18n.translate(None, "notification-digest-more", Some(&args))?);
+        }
+
+        let digest = Notification {
+            title,
+            body: Some(lines.join("\n")),
+            category: Some(DIGEST_CATEGORY.into()),
+            urgent: false,
+        };
+        let preferences = settings.preferences(Some(DIGEST_CATEGORY));
+        let status = if preferences.enabled {
+            DeliveryStatus::Delivered
+        } else {
+            DeliveryStatus::Disabled
+        };
+        // The deferred notifications are marked before the digest is shown,
+        // so one that cannot be marked is never shown again on the next tick.
+        let ids: Vec<i64> = deferred.iter().map(|record| record.id).collect();
+        let tx = conn.unchecked_transaction()?;
+        let mut record = match &failed {
+            Some(failed) => {
+                notifications::retry_digest(&tx, failed.id, &digest, status, self.now())?
+            }
+            None => notifications::record(&tx, &digest, status, None, self.now())?,
+        };
+        notifications::mark_digested(&tx, &ids, record.id)?;
+        tx.commit()?;
+
+        if preferences.enabled {
+            if let (DeliveryStatus::Failed, error) = self.show(&digest, &preferences) {
+                // Hand the notifications back until the digest is retried.
+                let attempts = failed.map_or(0, |failed| failed.attempts) + 1;
+                let retry_at = self.now() + digest_retry_delay(attempts);
+                notifications::fail_digest(conn, record.id, error.as_deref(), retry_at)?;
+                record.status = DeliveryStatus::Failed;
+                record.error = error;
+            }
+        }
+        self.recorded(conn, &record);
+        Ok(Some(record))
+    }
+
+    fn recorded(&self, conn: &Connection, record: &NotificationRecord) {
+        if let Some(listener) = &self.listener {
+            listener(conn, record);
+        }
+    }
+
+    fn deliver(
+        &self,
+        conn: &Connection,
+        notification: &Notification,
+        preferences: &CategoryPreferences,
+    ) -> AppResult<NotificationRecord> {
+        let (status, error) = self.show(notification, preferences);
+        notifications::record(conn, notification, status, error.as_deref(), self.now())
+    }
+
+    fn show(
+        &self,
+        notification: &Notification,
+        preferences: &CategoryPreferences,
+    ) -> (DeliveryStatus, Option<String>) {
+        match self.notifier.send(notification, preferences) {
+            Ok(()) => (DeliveryStatus::Delivered, None),
+            Err(err) => {
+                tracing::warn!(title = %notification.title, "could not show notification: {err}");
+                (DeliveryStatus::Failed, Some(err.message))
+            }
+        }
+    }
+}
+
+/// Shows a notification on behalf of the frontend, so it is subject to quiet
+/// hours and recorded like any other.
+#[tauri::command]
+#[specta::specta]
+pub async fn send_notification(
+    dispatcher: State<'_, Dispatcher>,
+    db: State<'_, Database>,
+    settings: State<'_, SettingsStore>,
+    notification: Notification,
+) -> AppResult<NotificationRecord> {
+    let conn = db.conn()?;
+    dispatcher.send(&conn, notification, &settings.get().notifications)
+}
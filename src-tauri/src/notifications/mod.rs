@@ -0,0 +1,165 @@
+pub mod clock;
+pub mod cron;
+pub mod dispatch;
+pub mod scheduler;
+pub mod templates;
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_notification::NotificationExt;
+
+use self::dispatch::QuietHours;
+use crate::error::{AppError, AppResult, ErrorCode};
+use crate::validation::TextRule;
+
+const TITLE: TextRule = TextRule::new(1, 128);
+const BODY: TextRule = TextRule::new(0, 1024).allow_newlines();
+const CATEGORY: TextRule = TextRule::new(0, 64);
+
+/// What a notification shows.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Notification {
+    pub title: String,
+    pub body: Option<String>,
+    /// Groups related notifications, in the history and on platforms that
+    /// thread them.
+    #[serde(default)]
+    pub category: Option<String>,
+    /// Shown even during quiet hours.
+    #[serde(default)]
+    pub urgent: bool,
+}
+
+impl Notification {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            body: None,
+            category: None,
+            urgent: false,
+        }
+    }
+
+    /// Validates the text, dropping an empty body or category.
+    pub fn validated(self) -> AppResult<Self> {
+        let optional = |rule: TextRule, field, value: Option<String>| match value {
+            Some(value) => Ok(Some(rule.apply(field, &value)?).filter(|value| !value.is_empty())),
+            None => Ok::<_, AppError>(None),
+        };
+        Ok(Self {
+            title: TITLE.apply("title", &self.title)?,
+            body: optional(BODY, "body", self.body)?,
+            category: optional(CATEGORY, "category", self.category)?,
+            urgent: self.urgent,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Type)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NotificationSettings {
+    /// Daily do-not-disturb window, in local time.
+    pub quiet_hours: Option<QuietHours>,
+    /// Do not disturb until turned off: notifications are deferred as during
+    /// quiet hours.
+    pub paused: bool,
+    /// Preferences by category; categories without an entry use the
+    /// defaults.
+    pub categories: BTreeMap<String, CategoryPreferences>,
+}
+
+impl NotificationSettings {
+    /// The preferences that apply to notifications in `category`.
+    pub fn preferences(&self, category: Option<&str>) -> CategoryPreferences {
+        category
+            .and_then(|category| self.categories.get(category))
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// How a notification competes for the user's attention.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum Priority {
+    /// Dropped rather than deferred during quiet hours.
+    Low,
+    #[default]
+    Normal,
+    /// Shown even during quiet hours, like an urgent notification.
+    High,
+}
+
+/// What the user chose for one category of notifications.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CategoryPreferences {
+    /// Disabled categories are recorded in the history but never shown.
+    pub enabled: bool,
+    /// Play the platform's notification sound.
+    pub sound: bool,
+    pub priority: Priority,
+}
+
+impl Default for CategoryPreferences {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            sound: true,
+            priority: Priority::Normal,
+        }
+    }
+}
+
+/// Delivers notifications to the user.
+pub trait Notifier: Send + Sync {
+    fn send(&self, notification: &Notification, preferences: &CategoryPreferences)
+        -> AppResult<()>;
+}
+
+/// Shows notifications through `tauri_plugin_notification`.
+pub struct PluginNotifier<R: Runtime> {
+    app: AppHandle<R>,
+}
+
+impl<R: Runtime> PluginNotifier<R> {
+    pub fn new(app: AppHandle<R>) -> Self {
+        Self { app }
+    }
+}
+
+impl<R: Runtime> Notifier for PluginNotifier<R> {
+    fn send(
+        &self,
+        notification: &Notification,
+        preferences: &CategoryPreferences,
+    ) -> AppResult<()> {
+        if self
+            .app
+            .try_state::<tauri_plugin_notification::Notification<R>>()
+            .is_none()
+        {
+            return Err(AppError::new(
+                ErrorCode::Internal,
+                "the notification plugin is not registered",
+            ));
+        }
+
+        let mut builder = self.app.notification().builder().title(&notification.title);
+        if let Some(body) = &notification.body {
+            builder = builder.body(body);
+        }
+        if let Some(category) = &notification.category {
+            builder = builder.group(category);
+        }
+        if !preferences.sound {
+            builder = builder.silent();
+        }
+        builder.show()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,256 @@
+use std::sync::Arc;
+use std::time::Duration as StdDuration;
+
+use chrono::{DateTime, Duration, Local, Utc};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Manager, Runtime, State};
+use tokio::sync::Notify;
+
+use super::clock::Clock;
+use super::cron::Cron;
+use super::dispatch::Dispatcher;
+use super::{Notification, NotificationSettings};
+use crate::db::notifications::NotificationRecord;
+use crate::db::schedules::{self, ScheduledNotification};
+use crate::db::Database;
+use crate::error::{AppError, AppResult, ErrorCode};
+use crate::i18n::I18n;
+use crate::settings::SettingsStore;
+
+/// Longest the background task sleeps between checks, so that wall-clock
+/// changes (e.g. waking from suspend) are noticed reasonably soon.
+pub const MAX_SLEEP: StdDuration = StdDuration::from_secs(60);
+
+/// When a scheduled notification fires.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum Trigger {
+    /// Once, at `at`.
+    At { at: DateTime<Utc> },
+    /// Once, `seconds` after being scheduled.
+    After { seconds: u64 },
+    /// Every time a [`Cron`] expression matches, in local time unless `utc`
+    /// is set.
+    Cron {
+        expression: String,
+        #[serde(default)]
+        utc: bool,
+    },
+}
+
+impl Trigger {
+    /// The first run for a schedule created at `now`.
+    pub fn first_run(&self, now: DateTime<Utc>) -> AppResult<DateTime<Utc>> {
+        match self {
+            Self::At { at } => Ok(*at),
+            Self::After { seconds } => i64::try_from(*seconds)
+                .ok()
+                .and_then(Duration::try_seconds)
+                .and_then(|delay| now.checked_add_signed(delay))
+                .ok_or_else(|| AppError::new(ErrorCode::InvalidInput, "`seconds` is out of range")),
+            Self::Cron { .. } => self.next_run_after(now)?.ok_or_else(|| {
+                AppError::new(ErrorCode::InvalidInput, "cron expression never matches")
+            }),
+        }
+    }
+
+    /// The run following one at `after`; `None` once a one-off has fired or a
+    /// recurrence has no further matches.
+    pub fn next_run_after(&self, after: DateTime<Utc>) -> AppResult<Option<DateTime<Utc>>> {
+        let Self::Cron { expression, utc } = self else {
+            return Ok(None);
+        };
+        let cron: Cron = expression.parse()?;
+        Ok(if *utc {
+            cron.next_after(&after)
+        } else {
+            cron.next_after(&after.with_timezone(&Local))
+                .map(|next| next.with_timezone(&Utc))
+        })
+    }
+}
+
+/// Persists schedules and hands them to the [`Dispatcher`] when due. Held in
+/// managed state; the background task started by [`spawn`] calls
+/// [`Scheduler::tick`].
+pub struct Scheduler {
+    clock: Arc<dyn Clock>,
+    changed: Notify,
+}
+
+impl Scheduler {
+    pub fn new(clock: Arc<dyn Clock>) -> Self {
+        Self {
+            clock,
+            changed: Notify::new(),
+        }
+    }
+
+    pub fn now(&self) -> DateTime<Utc> {
+        self.clock.now()
+    }
+
+    /// Makes the background task check for due notifications and a pending
+    /// digest right away.
+    pub fn wake(&self) {
+        self.changed.notify_one();
+    }
+
+    pub fn schedule(
+        &self,
+        conn: &Connection,
+        notification: Notification,
+        trigger: Trigger,
+    ) -> AppResult<ScheduledNotification> {
+        let notification = notification.validated()?;
+        let now = self.now();
+        let next_run = trigger.first_run(now)?;
+        let scheduled = schedules::insert(conn, &notification, &trigger, next_run, now)?;
+        self.changed.notify_one();
+        Ok(scheduled)
+    }
+
+    /// Replaces the trigger of schedule `id`, as if it were created now.
+    pub fn reschedule(
+        &self,
+        conn: &Connection,
+        id: i64,
+        trigger: Trigger,
+    ) -> AppResult<ScheduledNotification> {
+        let next_run = trigger.first_run(self.now())?;
+        schedules::update_trigger(conn, id, &trigger, next_run)?;
+        self.changed.notify_one();
+        schedules::get(conn, id)
+    }
+
+    pub fn cancel(&self, conn: &Connection, id: i64) -> AppResult<()> {
+        schedules::delete(conn, id)?;
+        self.changed.notify_one();
+        Ok(())
+    }
+
+    /// Sends every due notification through `dispatcher` and returns the
+    /// resulting history entries. One-offs are removed; recurrences move to
+    /// their first match after now, so runs missed while the app was closed
+    /// fire once rather than once per missed run. A schedule is moved on
+    /// before it is sent, so one that fails to send is logged and not
+    /// retried, and never holds up the others. One that cannot be moved on,
+    /// e.g. because its cron expression no longer parses, is stopped with
+    /// that error until [`Scheduler::reschedule`] gives it a new trigger.
+    pub fn tick(
+        &self,
+        conn: &Connection,
+        dispatcher: &Dispatcher,
+        settings: &NotificationSettings,
+    ) -> AppResult<Vec<NotificationRecord>> {
+        let now = self.now();
+        let mut sent = Vec::new();
+        for scheduled in schedules::due(conn, now)? {
+            let id = scheduled.id;
+            let moved_on =
+                scheduled
+                    .trigger
+                    .next_run_after(now)
+                    .and_then(|next_run| match next_run {
+                        Some(next_run) => schedules::set_next_run(conn, id, next_run),
+                        None => schedules::delete(conn, id),
+                    });
+            if let Err(err) = moved_on {
+                // Sending it anyway would send it again on every tick, and
+                // leaving it due would wake the task again right away.
+                tracing::warn!(id, "stopping scheduled notification: {err}");
+                if let Err(err) = schedules::stop(conn, id, &err.message) {
+                    tracing::warn!(id, "could not stop scheduled notification: {err}");
+                }
+                continue;
+            }
+            match dispatcher.send(conn, scheduled.notification, settings) {
+                Ok(record) => sent.push(record),
+                Err(err) => tracing::warn!(id, "could not send scheduled notification: {err}"),
+            }
+        }
+        Ok(sent)
+    }
+
+    /// How long to wait before the next [`Scheduler::tick`] is needed, at
+    /// most [`MAX_SLEEP`].
+    pub fn until_next(&self, conn: &Connection) -> AppResult<StdDuration> {
+        Ok(match schedules::next_run(conn)? {
+            Some(next_run) => (next_run - self.now())
+                .to_std()
+                .unwrap_or_default()
+                .min(MAX_SLEEP),
+            None => MAX_SLEEP,
+        })
+    }
+}
+
+/// Starts the background task that fires due notifications and sends the
+/// quiet-hours digest, waking early whenever a schedule changes.
+pub fn spawn<R: Runtime>(app: &AppHandle<R>) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let scheduler = app.state::<Scheduler>();
+        let dispatcher = app.state::<Dispatcher>();
+        let db = app.state::<Database>();
+        loop {
+            let settings = app.state::<SettingsStore>().get().notifications;
+            let wait = db.conn().and_then(|conn| {
+                scheduler.tick(&conn, &dispatcher, &settings)?;
+                dispatcher.send_digest(&conn, &settings, &app.state::<I18n>())?;
+                scheduler.until_next(&conn)
+            });
+            let wait = wait.unwrap_or_else(|err| {
+                tracing::warn!("notification scheduler failed: {err}");
+                MAX_SLEEP
+            });
+            let _ = tokio::time::timeout(wait, scheduler.changed.notified()).await;
+        }
+    });
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn schedule_notification(
+    scheduler: State<'_, Scheduler>,
+    db: State<'_, Database>,
+    notification: Notification,
+    trigger: Trigger,
+) -> AppResult<ScheduledNotification> {
+    let conn = db.conn()?;
+    scheduler.schedule(&conn, notification, trigger)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_scheduled_notifications(
+    db: State<'_, Database>,
+) -> AppResult<Vec<ScheduledNotification>> {
+    let conn = db.conn()?;
+    schedules::list(&conn)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn reschedule_notification(
+    scheduler: State<'_, Scheduler>,
+    db: State<'_, Database>,
+    id: i64,
+    trigger: Trigger,
+) -> AppResult<ScheduledNotification> {
+    let conn = db.conn()?;
+    scheduler.reschedule(&conn, id, trigger)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn cancel_scheduled_notification(
+    scheduler: State<'_, Scheduler>,
+    db: State<'_, Database>,
+    id: i64,
+) -> AppResult<()> {
+    let conn = db.conn()?;
+    scheduler.cancel(&conn, id)
+}
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+
+use fluent_bundle::FluentArgs;
+use tauri::{AppHandle, Manager, Runtime};
+
+use super::dispatch::Dispatcher;
+use super::Notification;
+use crate::db::notifications::NotificationRecord;
+use crate::db::Database;
+use crate::error::{AppError, AppResult, ErrorCode};
+use crate::i18n::{to_fluent_args, I18n, TranslationArgs};
+use crate::settings::SettingsStore;
+
+/// A named notification whose text comes from the message catalogue, so it
+/// is shown in the user's language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Template {
+    /// Dotted name backend code refers to, e.g. `greeting.received`.
+    pub name: &'static str,
+    pub category: &'static str,
+    /// Fluent message ids for the title and body.
+    pub title: &'static str,
+    pub body: Option<&'static str>,
+    /// Arguments the caller must supply. Others the messages use, such as a
+    /// selector with a default variant, are optional.
+    pub placeholders: &'static [&'static str],
+    pub urgent: bool,
+}
+
+/// Templates shipped with the app.
+pub const BUILTIN_TEMPLATES: &[Template] = &[Template {
+    name: "greeting.received",
+    category: "greetings",
+    title: "notification-greeting-received-title",
+    body: Some("greeting-received"),
+    placeholders: &["name"],
+    urgent: false,
+}];
+
+/// Looks up templates by name and renders them. Held in managed state.
+pub struct TemplateRegistry {
+    templates: HashMap<&'static str, Template>,
+}
+
+impl TemplateRegistry {
+    /// Panics if two templates share a name.
+    pub fn new(templates: &[Template]) -> Self {
+        let mut registry = HashMap::with_capacity(templates.len());
+        for template in templates {
+            if registry.insert(template.name, *template).is_some() {
+                panic!("duplicate notification template `{}`", template.name);
+            }
+        }
+        Self {
+            templates: registry,
+        }
+    }
+
+    pub fn builtin() -> Self {
+        Self::new(BUILTIN_TEMPLATES)
+    }
+
+    pub fn get(&self, name: &str) -> AppResult<&Template> {
+        self.templates.get(name).ok_or_else(|| {
+            AppError::new(
+                ErrorCode::NotFound,
+                format!("no notification template `{name}`"),
+            )
+            .with_details(serde_json::json!({ "template": name }))
+        })
+    }
+
+    /// Template names, sorted.
+    pub fn names(&self) -> Vec<&'static str> {
+        let mut names: Vec<_> = self.templates.keys().copied().collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Resolves the text of template `name` in `locale`, or the current
+    /// locale if `None`.
+    pub fn render(
+        &self,
+        i18n: &I18n,
+        name: &str,
+        args: Option<&FluentArgs>,
+        locale: Option<&str>,
+    ) -> AppResult<Notification> {
+        let template = self.get(name)?;
+        let missing = template
+            .placeholders
+            .iter()
+            .find(|placeholder| args.and_then(|args| args.get(**placeholder)).is_none());
+        if let Some(placeholder) = missing {
+            return Err(AppError::new(
+                ErrorCode::InvalidInput,
+                format!("template `{name}` needs a `{placeholder}` argument"),
+            )
+            .with_details(serde_json::json!({ "field": placeholder, "reason": "missing" })));
+        }
+
+        Ok(Notification {
+            title: i18n.translate(locale, template.title, args)?,
+            body: template
+                .body
+                .map(|body| i18n.translate(locale, body, args))
+                .transpose()?,
+            category: Some(template.category.into()),
+            urgent: template.urgent,
+        })
+    }
+}
+
+/// Shows template `name` with `args`, subject to the user's preferences and
+/// quiet hours, and returns the history entry.
+///
+/// ```ignore
+/// let mut args = FluentArgs::new();
+/// args.set("name", "Ada");
+/// notify(app.handle(), "greeting.received", Some(&args))?;
+/// ```
+pub fn notify<R: Runtime, M: Manager<R>>(
+    manager: &M,
+    name: &str,
+    args: Option<&FluentArgs>,
+) -> AppResult<NotificationRecord> {
+    let notification =
+        manager
+            .state::<TemplateRegistry>()
+            .render(&manager.state::<I18n>(), name, args, None)?;
+    let settings = manager.state::<SettingsStore>().get().notifications;
+    let conn = manager.state::<Database>().conn()?;
+    manager
+        .state::<Dispatcher>()
+        .send(&conn, notification, &settings)
+}
+
+/// Shows a template on behalf of the frontend.
+#[tauri::command]
+#[specta::specta]
+pub async fn send_template_notification<R: Runtime>(
+    app: AppHandle<R>,
+    template: String,
+    args: Option<TranslationArgs>,
+) -> AppResult<NotificationRecord> {
+    let args = args.as_ref().map(to_fluent_args);
+    notify(&app, &template, args.as_ref())
+}
@@ -0,0 +1,410 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use specta::Type;
+use tauri::{AppHandle, Runtime, State};
+use tauri_plugin_shell::Shell;
+use tokio::process::Child;
+use tokio::sync::{mpsc, watch, Notify};
+
+use super::runner::{forward, ProcessEvent, OUTPUT_BUFFER};
+use super::{kill_tree, shell, Allowlist};
+use crate::error::{AppError, AppResult, ErrorCode};
+use crate::validation::TextRule;
+
+/// Lines of output kept per process; older lines are dropped.
+pub const OUTPUT_LINES: usize = 1000;
+
+/// How long to keep reading output once a process has stopped.
+const OUTPUT_DRAIN: Duration = Duration::from_secs(1);
+
+const NAME: TextRule = TextRule::new(1, 64);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessState {
+    Running,
+    /// Exited on its own; see `exit_code`.
+    Exited,
+    /// Stopped by [`ProcessManager::kill`], a restart or app exit.
+    Killed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputLine {
+    pub stream: OutputStream,
+    pub line: String,
+    pub at: DateTime<Utc>,
+}
+
+/// A managed process, as of when it was looked up.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessInfo {
+    /// Unique among managed processes; chosen by whoever spawned it.
+    pub name: String,
+    /// The allowlisted command and its arguments.
+    pub command: String,
+    pub args: Vec<String>,
+    pub pid: Option<u32>,
+    pub state: ProcessState,
+    /// `None` while running, or if the process was killed by a signal.
+    pub exit_code: Option<i32>,
+    pub started_at: DateTime<Utc>,
+    pub exited_at: Option<DateTime<Utc>>,
+    /// Seconds from start until now, or until exit.
+    pub uptime_secs: u64,
+    pub restarts: u32,
+}
+
+/// Told about every status change, e.g. to emit
+/// [`AppEvent::ProcessStatus`](crate::events::AppEvent::ProcessStatus).
+pub type StatusListener = Box<dyn Fn(&ProcessInfo) + Send + Sync>;
+
+struct Entry {
+    /// Tells apart successive processes started under the same name.
+    id: u64,
+    info: ProcessInfo,
+    output: VecDeque<OutputLine>,
+    kill: Arc<Notify>,
+    done: watch::Receiver<bool>,
+}
+
+impl Entry {
+    fn info(&self) -> ProcessInfo {
+        let until = self.info.exited_at.unwrap_or_else(Utc::now);
+        ProcessInfo {
+            uptime_secs: (until - self.info.started_at).num_seconds().max(0) as u64,
+            ..self.info.clone()
+        }
+    }
+}
+
+/// State shared with the task that watches each child.
+struct Shared {
+    processes: Mutex<BTreeMap<String, Entry>>,
+    on_status: StatusListener,
+}
+
+impl Shared {
+    fn update(&self, name: &str, id: u64, change: impl FnOnce(&mut Entry) -> bool) {
+        let mut processes = self.processes.lock().unwrap();
+        let Some(entry) = processes.get_mut(name).filter(|entry| entry.id == id) else {
+            return;
+        };
+        if change(entry) {
+            let info = entry.info();
+            drop(processes);
+            (self.on_status)(&info);
+        }
+    }
+
+    fn push_output(&self, name: &str, id: u64, event: ProcessEvent) {
+        let (stream, line) = match event {
+            ProcessEvent::Stdout { line } => (OutputStream::Stdout, line),
+            ProcessEvent::Stderr { line } => (OutputStream::Stderr, line),
+            ProcessEvent::Started { .. } => return,
+        };
+        self.update(name, id, |entry| {
+            if entry.output.len() == OUTPUT_LINES {
+                entry.output.pop_front();
+            }
+            entry.output.push_back(OutputLine {
+                stream,
+                line,
+                at: Utc::now(),
+            });
+            false
+        });
+    }
+}
+
+/// Long-running helper processes (dev servers, watchers), started from the
+/// [`Allowlist`] and kept until the app exits. Held in managed state.
+pub struct ProcessManager {
+    allowlist: Allowlist,
+    next_id: AtomicU64,
+    shared: Arc<Shared>,
+}
+
+impl ProcessManager {
+    pub fn new(allowlist: Allowlist, on_status: StatusListener) -> Self {
+        Self {
+            allowlist,
+            next_id: AtomicU64::new(1),
+            shared: Arc::new(Shared {
+                processes: Mutex::new(BTreeMap::new()),
+                on_status,
+            }),
+        }
+    }
+
+    /// Starts the allowlisted `command`, built by `shell`, as `name`. A name
+    /// may be reused once its previous process has stopped.
+    pub async fn spawn<R: Runtime>(
+        &self,
+        shell: &Shell<R>,
+        name: &str,
+        command: &str,
+        args: &[String],
+    ) -> AppResult<ProcessInfo> {
+        let name = NAME.apply("name", name)?;
+        self.start(shell, name, command, args.to_vec(), 0)
+    }
+
+    fn start<R: Runtime>(
+        &self,
+        shell: &Shell<R>,
+        name: String,
+        command: &str,
+        args: Vec<String>,
+        restarts: u32,
+    ) -> AppResult<ProcessInfo> {
+        let spec = self.allowlist.check(command, &args)?;
+
+        let mut processes = self.shared.processes.lock().unwrap();
+        if processes
+            .get(&name)
+            .is_some_and(|entry| entry.info.state == ProcessState::Running)
+        {
+            return Err(AppError::new(
+                ErrorCode::InvalidInput,
+                format!("a process named `{name}` is already running"),
+            )
+            .with_details(serde_json::json!({ "field": "name", "reason": "running" })));
+        }
+
+        let mut child = spec.command(shell, &args).spawn()?;
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let kill = Arc::new(Notify::new());
+        let (done_tx, done) = watch::channel(false);
+        let entry = Entry {
+            id,
+            info: ProcessInfo {
+                name: name.clone(),
+                command: command.into(),
+                args,
+                pid: child.id(),
+                state: ProcessState::Running,
+                exit_code: None,
+                started_at: Utc::now(),
+                exited_at: None,
+                uptime_secs: 0,
+                restarts,
+            },
+            output: VecDeque::new(),
+            kill: kill.clone(),
+            done,
+        };
+        let info = entry.info();
+        processes.insert(name.clone(), entry);
+        drop(processes);
+        tracing::info!(name = %name, command, pid = info.pid, "managed process started");
+
+        let (tx, output) = mpsc::channel(OUTPUT_BUFFER);
+        forward(child.stdout.take(), tx.clone(), |line| {
+            ProcessEvent::Stdout { line }
+        });
+        forward(child.stderr.take(), tx, |line| ProcessEvent::Stderr {
+            line,
+        });
+        // Reported before the watcher starts, so it always precedes the exit.
+        (self.shared.on_status)(&info);
+        tauri::async_runtime::spawn(watch_child(
+            self.shared.clone(),
+            name,
+            id,
+            child,
+            output,
+            kill,
+            done_tx,
+        ));
+        Ok(info)
+    }
+
+    /// Every managed process, running or not, by name.
+    pub fn list(&self) -> Vec<ProcessInfo> {
+        let processes = self.shared.processes.lock().unwrap();
+        processes.values().map(Entry::info).collect()
+    }
+
+    pub fn get(&self, name: &str) -> AppResult<ProcessInfo> {
+        self.with_entry(name, Entry::info)
+    }
+
+    /// The most recent output of `name`, oldest first, at most
+    /// [`OUTPUT_LINES`] lines.
+    pub fn output(&self, name: &str) -> AppResult<Vec<OutputLine>> {
+        self.with_entry(name, |entry| entry.output.iter().cloned().collect())
+    }
+
+    /// Resolves once `name` has stopped.
+    pub async fn wait(&self, name: &str) -> AppResult<ProcessInfo> {
+        let mut done = self.with_entry(name, |entry| entry.done.clone())?;
+        // The sender is only dropped after signalling.
+        let _ = done.wait_for(|done| *done).await;
+        self.get(name)
+    }
+
+    /// Kills `name`, along with the processes it started (see
+    /// [`kill_tree`]), and waits for it to stop. Killing a stopped process
+    /// does nothing.
+    pub async fn kill(&self, name: &str) -> AppResult<ProcessInfo> {
+        let kill = self.with_entry(name, |entry| entry.kill.clone())?;
+        kill.notify_one();
+        self.wait(name).await
+    }
+
+    /// Kills `name` if it is running and starts it again with the same
+    /// command.
+    pub async fn restart<R: Runtime>(
+        &self,
+        shell: &Shell<R>,
+        name: &str,
+    ) -> AppResult<ProcessInfo> {
+        let stopped = self.kill(name).await?;
+        self.start(
+            shell,
+            stopped.name,
+            &stopped.command,
+            stopped.args,
+            stopped.restarts + 1,
+        )
+    }
+
+    /// Kills every running process; called when the app exits.
+    pub async fn kill_all(&self) {
+        let running: Vec<String> = self
+            .list()
+            .into_iter()
+            .filter(|info| info.state == ProcessState::Running)
+            .map(|info| info.name)
+            .collect();
+        for name in running {
+            if let Err(err) = self.kill(&name).await {
+                tracing::warn!(name = %name, "could not kill managed process: {err}");
+            }
+        }
+    }
+
+    fn with_entry<T>(&self, name: &str, f: impl FnOnce(&Entry) -> T) -> AppResult<T> {
+        let processes = self.shared.processes.lock().unwrap();
+        processes.get(name).map(f).ok_or_else(|| {
+            AppError::new(
+                ErrorCode::NotFound,
+                format!("no managed process named `{name}`"),
+            )
+            .with_details(serde_json::json!({ "name": name }))
+        })
+    }
+}
+
+/// Collects the output of process `id` until it exits or is killed, then
+/// records how it stopped. A kill request is looked at first, so a child
+/// that never stops writing cannot hold it up.
+async fn watch_child(
+    shared: Arc<Shared>,
+    name: String,
+    id: u64,
+    mut child: Child,
+    mut output: mpsc::Receiver<ProcessEvent>,
+    kill: Arc<Notify>,
+    done: watch::Sender<bool>,
+) {
+    let (state, exit_code) = loop {
+        tokio::select! {
+            biased;
+            _ = kill.notified() => {
+                if let Err(err) = kill_tree(&mut child).await {
+                    tracing::warn!(name = %name, "could not kill managed process: {err}");
+                }
+                break (ProcessState::Killed, None);
+            }
+            status = child.wait() => break match status {
+                Ok(status) => (ProcessState::Exited, status.code()),
+                Err(err) => {
+                    tracing::warn!(name = %name, "lost track of managed process: {err}");
+                    (ProcessState::Exited, None)
+                }
+            },
+            Some(event) = output.recv() => shared.push_output(&name, id, event),
+        }
+    };
+    // Output still in the pipes arrives after the exit. A grandchild holding
+    // the pipes open must not keep the process from being reported stopped.
+    let _ = tokio::time::timeout(OUTPUT_DRAIN, async {
+        while let Some(event) = output.recv().await {
+            shared.push_output(&name, id, event);
+        }
+    })
+    .await;
+
+    tracing::info!(name = %name, ?state, exit_code, "managed process stopped");
+    shared.update(&name, id, |entry| {
+        entry.info.state = state;
+        entry.info.exit_code = exit_code;
+        entry.info.exited_at = Some(Utc::now());
+        true
+    });
+    let _ = done.send(true);
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn spawn_process<R: Runtime>(
+    app: AppHandle<R>,
+    manager: State<'_, ProcessManager>,
+    name: String,
+    command: String,
+    args: Option<Vec<String>>,
+) -> AppResult<ProcessInfo> {
+    manager
+        .spawn(shell(&app)?, &name, &command, &args.unwrap_or_default())
+        .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn list_processes(manager: State<'_, ProcessManager>) -> AppResult<Vec<ProcessInfo>> {
+    Ok(manager.list())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_process_output(
+    manager: State<'_, ProcessManager>,
+    name: &str,
+) -> AppResult<Vec<OutputLine>> {
+    manager.output(name)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn restart_process<R: Runtime>(
+    app: AppHandle<R>,
+    manager: State<'_, ProcessManager>,
+    name: String,
+) -> AppResult<ProcessInfo> {
+    manager.restart(shell(&app)?, &name).await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn kill_process(
+    manager: State<'_, ProcessManager>,
+    name: String,
+) -> AppResult<ProcessInfo> {
+    manager.kill(&name).await
+}
@@ -0,0 +1,209 @@
+pub mod manager;
+pub mod runner;
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+use regex::Regex;
+use serde::{Deserialize, Deserializer};
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_shell::{Shell, ShellExt};
+use tokio::process::{Child, Command};
+
+use crate::error::{AppError, AppResult, ErrorCode};
+
+/// Name of the allowlist in the app config directory. It is only ever read,
+/// so the frontend cannot widen what it may run.
+pub const ALLOWLIST_FILE: &str = "commands.json";
+
+/// Used when a command does not declare its own timeout.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// One permitted argument, matched by position.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum ArgRule {
+    /// Must be passed exactly as written.
+    Fixed(String),
+    /// Must match the regular expression in full.
+    Pattern {
+        #[serde(deserialize_with = "anchored_regex")]
+        validator: Regex,
+    },
+}
+
+impl ArgRule {
+    pub fn matches(&self, arg: &str) -> bool {
+        match self {
+            Self::Fixed(fixed) => fixed == arg,
+            Self::Pattern { validator } => validator.is_match(arg),
+        }
+    }
+}
+
+fn anchored_regex<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Regex, D::Error> {
+    let pattern = String::deserialize(deserializer)?;
+    Regex::new(&format!("^(?:{pattern})$")).map_err(serde::de::Error::custom)
+}
+
+/// An external program the app may run, and the only way it may be run.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandSpec {
+    /// Absolute path; `PATH` is never searched.
+    pub program: PathBuf,
+    /// The arguments a caller must pass: one per rule, in order.
+    #[serde(default)]
+    pub args: Vec<ArgRule>,
+    /// The child sees only these variables.
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    /// Working directory; the app's own if unset.
+    #[serde(default)]
+    pub cwd: Option<PathBuf>,
+    /// Defaults to [`DEFAULT_TIMEOUT`]. Processes started by the
+    /// [`manager::ProcessManager`] run until they exit or are killed.
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+}
+
+impl CommandSpec {
+    pub fn timeout(&self) -> Duration {
+        self.timeout_secs
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_TIMEOUT)
+    }
+
+    /// A command for `args`, already checked against this spec, built by
+    /// the shell plugin, with its output piped and killed if dropped. On
+    /// unix it leads a process group of its own, so [`kill_tree`] reaches
+    /// whatever it starts.
+    fn command<R: Runtime>(&self, shell: &Shell<R>, args: &[String]) -> Command {
+        let mut command = shell
+            .command(&self.program)
+            .args(args)
+            .env_clear()
+            .envs(&self.env);
+        if let Some(cwd) = &self.cwd {
+            command = command.current_dir(cwd);
+        }
+        // Run by tokio rather than the plugin's `spawn`, which reads lines
+        // without a length limit and cannot kill a dropped child.
+        let mut command = Command::from(std::process::Command::from(command));
+        command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        #[cfg(unix)]
+        command.process_group(0);
+        command
+    }
+}
+
+/// The shell plugin, which builds every command the app runs.
+pub fn shell<R: Runtime>(app: &AppHandle<R>) -> AppResult<&Shell<R>> {
+    if app.try_state::<Shell<R>>().is_none() {
+        return Err(AppError::new(
+            ErrorCode::Internal,
+            "the shell plugin is not registered",
+        ));
+    }
+    Ok(app.shell())
+}
+
+/// Kills `child` together with the processes it started, then waits for it.
+/// On unix that is its whole process group; elsewhere only `child` itself.
+pub(crate) async fn kill_tree(child: &mut Child) -> std::io::Result<()> {
+    #[cfg(unix)]
+    if let Some(pid) = child.id() {
+        // SAFETY: `killpg` takes plain integers and touches no memory of ours.
+        if unsafe { libc::killpg(pid as libc::pid_t, libc::SIGKILL) } == 0 {
+            child.wait().await?;
+            return Ok(());
+        }
+    }
+    child.kill().await
+}
+
+/// The commands the app may run, by name.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Allowlist {
+    commands: BTreeMap<String, CommandSpec>,
+}
+
+impl Allowlist {
+    pub fn new(commands: impl IntoIterator<Item = (String, CommandSpec)>) -> AppResult<Self> {
+        let allowlist = Self {
+            commands: commands.into_iter().collect(),
+        };
+        for (name, spec) in &allowlist.commands {
+            if !spec.program.is_absolute() {
+                return Err(AppError::new(
+                    ErrorCode::InvalidInput,
+                    format!("program for `{name}` must be an absolute path"),
+                )
+                .with_details(serde_json::json!({ "command": name })));
+            }
+        }
+        Ok(allowlist)
+    }
+
+    /// Loads `{ "commands": { "<name>": CommandSpec } }` from `path`. A
+    /// missing file allows nothing.
+    pub fn load(path: &Path) -> AppResult<Self> {
+        match fs::read(path) {
+            Ok(bytes) => {
+                let allowlist: Self = serde_json::from_slice(&bytes)?;
+                Self::new(allowlist.commands)
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Command names, sorted.
+    pub fn names(&self) -> Vec<&str> {
+        self.commands.keys().map(String::as_str).collect()
+    }
+
+    /// The spec for running `name` with `args`, or a `permission_denied`
+    /// error saying why the invocation is not allowed.
+    pub fn check(&self, name: &str, args: &[String]) -> AppResult<&CommandSpec> {
+        let denied = |message: String, details: serde_json::Value| {
+            AppError::new(ErrorCode::PermissionDenied, message).with_details(details)
+        };
+
+        let spec = self.commands.get(name).ok_or_else(|| {
+            denied(
+                format!("`{name}` is not an allowed command"),
+                serde_json::json!({ "command": name, "reason": "not_allowed" }),
+            )
+        })?;
+        if args.len() != spec.args.len() {
+            return Err(denied(
+                format!(
+                    "`{name}` takes {} arguments, not {}",
+                    spec.args.len(),
+                    args.len()
+                ),
+                serde_json::json!({ "command": name, "reason": "argument_count" }),
+            ));
+        }
+        if let Some(index) = spec
+            .args
+            .iter()
+            .zip(args)
+            .position(|(rule, arg)| !rule.matches(arg))
+        {
+            return Err(denied(
+                format!("argument {index} of `{name}` is not allowed"),
+                serde_json::json!({ "command": name, "reason": "argument", "index": index }),
+            ));
+        }
+        Ok(spec)
+    }
+}
@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use specta::Type;
+use tauri::ipc::Channel;
+use tauri::{AppHandle, Runtime, State};
+use tauri_plugin_shell::Shell;
+use tokio::io::{self, AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncReadExt, BufReader};
+use tokio::sync::{mpsc, Notify};
+
+use super::{kill_tree, shell, Allowlist};
+use crate::error::{AppError, AppResult, ErrorCode};
+
+/// Progress of a run, streamed while it happens.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Type)]
+#[serde(
+    tag = "event",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum ProcessEvent {
+    /// Sent first; `id` is what [`ProcessRunner::cancel`] takes.
+    Started {
+        id: u64,
+        pid: Option<u32>,
+    },
+    /// A line of output, without its line ending. Invalid UTF-8 is replaced.
+    Stdout {
+        line: String,
+    },
+    Stderr {
+        line: String,
+    },
+}
+
+/// How a run ended.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessExit {
+    pub id: u64,
+    /// `None` if the process was killed by a signal.
+    pub code: Option<i32>,
+    pub success: bool,
+}
+
+/// Runs the commands in an [`Allowlist`], one-shot. Held in managed state.
+pub struct ProcessRunner {
+    allowlist: Allowlist,
+    next_id: AtomicU64,
+    running: Mutex<HashMap<u64, Arc<Notify>>>,
+}
+
+/// Forgets a run however its future ends, including by being dropped.
+struct Running<'a> {
+    runner: &'a ProcessRunner,
+    id: u64,
+}
+
+impl Drop for Running<'_> {
+    fn drop(&mut self) {
+        self.runner.running.lock().unwrap().remove(&self.id);
+    }
+}
+
+impl ProcessRunner {
+    pub fn new(allowlist: Allowlist) -> Self {
+        Self {
+            allowlist,
+            next_id: AtomicU64::new(1),
+            running: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn allowlist(&self) -> &Allowlist {
+        &self.allowlist
+    }
+
+    /// Runs the allowlisted command `name` with `args`, built by `shell`,
+    /// passing its output to `on_event` line by line. The process is killed
+    /// if it outlives its timeout, is cancelled, or this future is dropped.
+    pub async fn run<R: Runtime>(
+        &self,
+        shell: &Shell<R>,
+        name: &str,
+        args: &[String],
+        mut on_event: impl FnMut(ProcessEvent),
+    ) -> AppResult<ProcessExit> {
+        let spec = self.allowlist.check(name, args)?;
+        let mut child = spec.command(shell, args).spawn()?;
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let cancel = Arc::new(Notify::new());
+        self.running.lock().unwrap().insert(id, cancel.clone());
+        let _running = Running { runner: self, id };
+        tracing::info!(command = name, id, pid = child.id(), "process started");
+        on_event(ProcessEvent::Started {
+            id,
+            pid: child.id(),
+        });
+
+        let (tx, mut rx) = mpsc::channel(OUTPUT_BUFFER);
+        forward(child.stdout.take(), tx.clone(), |line| {
+            ProcessEvent::Stdout { line }
+        });
+        forward(child.stderr.take(), tx, |line| ProcessEvent::Stderr {
+            line,
+        });
+
+        let finished = tokio::select! {
+            status = async {
+                while let Some(event) = rx.recv().await {
+                    on_event(event);
+                }
+                child.wait().await
+            } => Ok(status?),
+            _ = cancel.notified() => Err(AppError::new(
+                ErrorCode::Cancelled,
+                format!("`{name}` was cancelled"),
+            )),
+            _ = tokio::time::sleep(spec.timeout()) => Err(AppError::new(
+                ErrorCode::Timeout,
+                format!("`{name}` did not finish within {}s", spec.timeout().as_secs()),
+            )),
+        };
+
+        match finished {
+            Ok(status) => {
+                tracing::info!(command = name, id, code = status.code(), "process exited");
+                Ok(ProcessExit {
+                    id,
+                    code: status.code(),
+                    success: status.success(),
+                })
+            }
+            Err(err) => {
+                tracing::warn!(command = name, id, "process stopped: {err}");
+                kill_tree(&mut child).await?;
+                Err(err.with_details(serde_json::json!({ "command": name, "id": id })))
+            }
+        }
+    }
+
+    /// Stops run `id`, which then fails with a `cancelled` error.
+    pub fn cancel(&self, id: u64) -> AppResult<()> {
+        match self.running.lock().unwrap().get(&id) {
+            Some(cancel) => {
+                cancel.notify_one();
+                Ok(())
+            }
+            None => Err(AppError::new(
+                ErrorCode::NotFound,
+                format!("process run {id} is not running"),
+            )
+            .with_details(serde_json::json!({ "id": id }))),
+        }
+    }
+}
+
+/// Longest line forwarded from a process, in bytes. The rest of a longer line
+/// is dropped so a child that never prints a newline cannot exhaust memory.
+pub const MAX_LINE_BYTES: usize = 64 * 1024;
+
+/// Lines buffered between a process and whoever consumes its output. Once
+/// the buffer is full its pipes are no longer read, so a child that writes
+/// faster than it is listened to blocks instead of growing the buffer.
+pub(super) const OUTPUT_BUFFER: usize = 256;
+
+/// Sends each line of `stream` to `tx` until either end closes.
+pub(super) fn forward(
+    stream: Option<impl AsyncRead + Unpin + Send + 'static>,
+    tx: mpsc::Sender<ProcessEvent>,
+    event: fn(String) -> ProcessEvent,
+) {
+    let Some(stream) = stream else {
+        return;
+    };
+    tauri::async_runtime::spawn(async move {
+        let mut reader = BufReader::new(stream);
+        let mut line = Vec::new();
+        loop {
+            line.clear();
+            let limit = MAX_LINE_BYTES as u64 + 1;
+            match (&mut reader).take(limit).read_until(b'\n', &mut line).await {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {}
+            }
+            if line.last() == Some(&b'\n') {
+                line.pop();
+            } else if line.len() > MAX_LINE_BYTES {
+                line.truncate(MAX_LINE_BYTES);
+                if skip_line(&mut reader).await.is_err() {
+                    break;
+                }
+            }
+            let line = line.strip_suffix(b"\r").unwrap_or(&line);
+            if tx
+                .send(event(String::from_utf8_lossy(line).into_owned()))
+                .await
+                .is_err()
+            {
+                break;
+            }
+        }
+    });
+}
+
+/// Discards the rest of the current line, including its newline.
+async fn skip_line(reader: &mut (impl AsyncBufRead + Unpin)) -> io::Result<()> {
+    loop {
+        let buf = reader.fill_buf().await?;
+        if buf.is_empty() {
+            return Ok(());
+        }
+        match buf.iter().position(|byte| *byte == b'\n') {
+            Some(end) => {
+                reader.consume(end + 1);
+                return Ok(());
+            }
+            None => {
+                let len = buf.len();
+                reader.consume(len);
+            }
+        }
+    }
+}
+
+/// Names of the commands the frontend may run.
+#[tauri::command]
+#[specta::specta]
+pub fn list_allowed_commands(runner: State<'_, ProcessRunner>) -> AppResult<Vec<String>> {
+    Ok(runner
+        .allowlist()
+        .names()
+        .into_iter()
+        .map(Into::into)
+        .collect())
+}
+
+/// Runs an allowlisted command, streaming its output over `on_event`.
+#[tauri::command]
+#[specta::specta]
+pub async fn run_command<R: Runtime>(
+    app: AppHandle<R>,
+    runner: State<'_, ProcessRunner>,
+    command: String,
+    args: Option<Vec<String>>,
+    on_event: Channel<ProcessEvent>,
+) -> AppResult<ProcessExit> {
+    let args = args.unwrap_or_default();
+    runner
+        .run(shell(&app)?, &command, &args, |event| {
+            // The window may have gone away; the run carries on regardless.
+            let _ = on_event.send(event);
+        })
+        .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn cancel_command(runner: State<'_, ProcessRunner>, id: u64) -> AppResult<()> {
+    runner.cancel(id)
+}
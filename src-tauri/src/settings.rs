@@ -0,0 +1,251 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use specta::Type;
+use tauri::{AppHandle, Manager, Runtime, State};
+
+use crate::db::{greetings, Database};
+use crate::error::{AppError, AppResult, ErrorCode};
+use crate::events::AppEvent;
+use crate::files;
+use crate::i18n::{self, I18n};
+use crate::logging::{self, LogSettings, Logging};
+use crate::notifications::NotificationSettings;
+
+pub const SETTINGS_FILE: &str = "settings.json";
+
+/// Version written to disk alongside the settings. Bump it and append to
+/// [`MIGRATIONS`] whenever a stored field is renamed or reshaped; purely
+/// additive fields only need a `#[serde(default)]`.
+pub const CURRENT_VERSION: u64 = 1;
+
+/// Upgrades a stored document from version `index + 1` to `index + 2`.
+pub type Migration = fn(&mut Map<String, Value>);
+
+pub const MIGRATIONS: &[Migration] = &[];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum Theme {
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+/// How much greeting history to keep.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+#[serde(default, rename_all = "camelCase")]
+pub struct HistorySettings {
+    pub max_entries: u32,
+    /// Entries older than this are pruned; `None` keeps them regardless of age.
+    pub max_age_days: Option<u32>,
+}
+
+impl Default for HistorySettings {
+    fn default() -> Self {
+        Self {
+            max_entries: 1000,
+            max_age_days: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Type)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Settings {
+    /// Overrides the OS locale when set.
+    pub locale: Option<String>,
+    pub theme: Theme,
+    pub history: HistorySettings,
+    pub logging: LogSettings,
+    pub notifications: NotificationSettings,
+    /// Closing the main window hides it in the tray instead of quitting.
+    pub close_to_tray: bool,
+}
+
+/// Serialized form on disk: the settings plus a schema version.
+#[derive(Serialize)]
+struct Versioned<'a> {
+    version: u64,
+    #[serde(flatten)]
+    settings: &'a Settings,
+}
+
+/// Settings held in managed state and mirrored to a JSON file.
+pub struct SettingsStore {
+    path: PathBuf,
+    settings: Mutex<Settings>,
+}
+
+impl SettingsStore {
+    /// Loads settings from `path`, falling back to defaults when the file is
+    /// missing. A file that cannot be read back is moved aside to
+    /// `<file>.corrupt-<unix time>` and replaced with defaults.
+    pub fn load(path: impl Into<PathBuf>) -> AppResult<Self> {
+        let path = path.into();
+        let settings = match fs::read(&path) {
+            Ok(bytes) => match decode(&bytes, MIGRATIONS) {
+                Ok(settings) => settings,
+                Err(err) => {
+                    let backup = backup_corrupt(&path)?;
+                    tracing::warn!(
+                        path = %path.display(),
+                        backup = %backup.display(),
+                        "settings file unreadable, reset to defaults: {err}"
+                    );
+                    let settings = Settings::default();
+                    write(&path, &settings)?;
+                    settings
+                }
+            },
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Settings::default(),
+            Err(err) => return Err(err.into()),
+        };
+
+        Ok(Self {
+            path,
+            settings: Mutex::new(settings),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn get(&self) -> Settings {
+        self.settings.lock().unwrap().clone()
+    }
+
+    /// Merges `patch` (a partial settings object) into the current settings,
+    /// checks the result and persists it. Only the stored settings change;
+    /// [`apply`] also applies them to the running app and tells every window.
+    pub fn update(&self, patch: Value) -> AppResult<Settings> {
+        self.update_with(|settings| {
+            let next = patched(settings, patch)?;
+            if let Some(locale) = &next.locale {
+                i18n::parse_locale(locale)?;
+            }
+            logging::env_filter(&next.logging)?;
+            *settings = next;
+            Ok(())
+        })
+    }
+
+    /// Applies `f` to the settings and persists the result. Nothing is
+    /// changed in memory if `f` or the write fails.
+    fn update_with(&self, f: impl FnOnce(&mut Settings) -> AppResult<()>) -> AppResult<Settings> {
+        let mut current = self.settings.lock().unwrap();
+        let mut next = current.clone();
+        f(&mut next)?;
+        write(&self.path, &next)?;
+        *current = next.clone();
+        Ok(next)
+    }
+}
+
+/// Parses a stored document and brings it up to [`CURRENT_VERSION`].
+/// Documents without a version are treated as version 1.
+pub fn decode(bytes: &[u8], migrations: &[Migration]) -> AppResult<Settings> {
+    let Value::Object(mut doc) = serde_json::from_slice(bytes)? else {
+        return Err(AppError::new(
+            ErrorCode::InvalidInput,
+            "settings file is not a JSON object",
+        ));
+    };
+
+    let version = match doc.remove("version") {
+        None => 1,
+        Some(value) => value.as_u64().ok_or_else(|| {
+            AppError::new(ErrorCode::InvalidInput, "settings version is not a number")
+        })?,
+    };
+    let latest = migrations.len() as u64 + 1;
+    if version == 0 || version > latest {
+        return Err(AppError::new(
+            ErrorCode::InvalidInput,
+            format!("unsupported settings version {version}"),
+        ));
+    }
+
+    for migration in &migrations[version as usize - 1..] {
+        migration(&mut doc);
+    }
+    Ok(serde_json::from_value(Value::Object(doc))?)
+}
+
+/// Writes `settings` with their version, never leaving a truncated file.
+fn write(path: &Path, settings: &Settings) -> AppResult<()> {
+    let json = serde_json::to_vec_pretty(&Versioned {
+        version: CURRENT_VERSION,
+        settings,
+    })?;
+    files::write_atomic(path, &json)
+}
+
+fn backup_corrupt(path: &Path) -> AppResult<PathBuf> {
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(format!(".corrupt-{stamp}"));
+    let backup = PathBuf::from(backup);
+    fs::rename(path, &backup)?;
+    Ok(backup)
+}
+
+fn patched(settings: &Settings, patch: Value) -> AppResult<Settings> {
+    let mut merged = serde_json::to_value(settings)?;
+    merge(&mut merged, patch);
+    serde_json::from_value(merged)
+        .map_err(|err| AppError::new(ErrorCode::InvalidInput, format!("invalid settings: {err}")))
+}
+
+/// Recursively merges object `patch` into `target`; any other value replaces
+/// the target outright.
+fn merge(target: &mut Value, patch: Value) {
+    match (target, patch) {
+        (Value::Object(target), Value::Object(patch)) => {
+            for (key, value) in patch {
+                merge(target.entry(key).or_insert(Value::Null), value);
+            }
+        }
+        (target, patch) => *target = patch,
+    }
+}
+
+/// Merges `patch` into the settings like [`SettingsStore::update`], then
+/// applies the result to the running app and tells every window. A changed
+/// history retention prunes the greeting history right away.
+pub fn apply<R: Runtime>(app: &AppHandle<R>, patch: Value) -> AppResult<Settings> {
+    let store = app.state::<SettingsStore>();
+    let previous = store.get().history;
+    let settings = store.update(patch)?;
+    app.state::<I18n>()
+        .set_preferred_locale(settings.locale.as_deref())?;
+    app.state::<Logging>().reload(&settings.logging)?;
+    if settings.history != previous {
+        greetings::apply_retention(&app.state::<Database>(), &store)?;
+    }
+    tracing::info!("settings updated");
+
+    AppEvent::SettingsChanged(settings.clone()).emit(app)?;
+    Ok(settings)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_settings(store: State<'_, SettingsStore>) -> AppResult<Settings> {
+    Ok(store.get())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_settings<R: Runtime>(app: AppHandle<R>, patch: Value) -> AppResult<Settings> {
+    apply(&app, patch)
+}
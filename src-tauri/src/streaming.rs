@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::Serialize;
+use specta::Type;
+use tauri::ipc::Channel;
+use tauri::State;
+use tokio::sync::{mpsc, watch};
+
+use crate::error::{AppError, AppResult, ErrorCode};
+use crate::validation::TextRule;
+
+/// Ids the frontend picks for its requests, e.g. a UUID.
+const REQUEST_ID: TextRule = TextRule::new(1, 64);
+
+/// What a streaming command sends over its channel: any number of batches,
+/// then exactly one of `done` or `error`.
+#[derive(Debug, Clone, PartialEq, Serialize, Type)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum StreamMessage<T> {
+    /// The next items, in order.
+    Batch { items: Vec<T> },
+    /// Every item was sent; `count` is how many.
+    Done { count: u64 },
+    /// The stream failed or was cancelled. Items already sent stand.
+    Error { error: AppError },
+}
+
+/// How items are grouped into [`StreamMessage::Batch`]es.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Batching {
+    /// Largest batch sent.
+    pub max_items: usize,
+    /// How long the first item of a batch waits for more to join it.
+    pub max_delay: Duration,
+    /// Items the producer may queue ahead of batching before
+    /// [`StreamSender::send`] waits. This only bounds what is held on the
+    /// Rust side: batches go to the channel as soon as they are ready,
+    /// without waiting for the frontend to take them, so a slow frontend
+    /// does not slow the producer down.
+    pub buffer: usize,
+}
+
+impl Default for Batching {
+    fn default() -> Self {
+        Self {
+            max_items: 64,
+            max_delay: Duration::from_millis(50),
+            buffer: 256,
+        }
+    }
+}
+
+/// Tells a request it has been cancelled.
+#[derive(Debug, Clone)]
+pub struct CancelToken(watch::Receiver<bool>);
+
+impl CancelToken {
+    pub fn is_cancelled(&self) -> bool {
+        *self.0.borrow()
+    }
+
+    /// Resolves once the request is cancelled; never, if it finishes first.
+    pub async fn cancelled(&self) {
+        let mut cancelled = self.0.clone();
+        if cancelled.wait_for(|cancelled| *cancelled).await.is_err() {
+            std::future::pending::<()>().await;
+        }
+    }
+}
+
+/// Hands a stream's items to its channel.
+pub struct StreamSender<T> {
+    tx: mpsc::Sender<T>,
+    token: CancelToken,
+}
+
+impl<T> StreamSender<T> {
+    /// Queues `item` for batching, waiting while [`Batching::buffer`] items
+    /// already are. Fails with `cancelled` once nobody will see it.
+    pub async fn send(&self, item: T) -> AppResult<()> {
+        if self.token.is_cancelled() {
+            return Err(cancelled());
+        }
+        self.tx.send(item).await.map_err(|_| cancelled())
+    }
+
+    pub fn token(&self) -> &CancelToken {
+        &self.token
+    }
+}
+
+fn cancelled() -> AppError {
+    AppError::new(ErrorCode::Cancelled, "the request was cancelled")
+}
+
+/// Requests in flight, by id, so they can be cancelled. Held in managed
+/// state.
+#[derive(Default)]
+pub struct Requests {
+    running: Mutex<HashMap<String, watch::Sender<bool>>>,
+}
+
+/// Forgets a request however its future ends, including by being dropped.
+struct Running<'a> {
+    requests: &'a Requests,
+    id: String,
+}
+
+impl Drop for Running<'_> {
+    fn drop(&mut self) {
+        self.requests.running.lock().unwrap().remove(&self.id);
+    }
+}
+
+impl Requests {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The key request `id` is registered under: trimmed and NFC-normalized,
+    /// so every method finds a request by the id the frontend passed.
+    fn key(id: &str) -> AppResult<String> {
+        REQUEST_ID.apply("id", id)
+    }
+
+    /// Registers request `id` until the returned guard is dropped.
+    fn start(&self, id: &str) -> AppResult<(Running<'_>, CancelToken)> {
+        let id = Self::key(id)?;
+        let mut running = self.running.lock().unwrap();
+        if running.contains_key(&id) {
+            return Err(AppError::new(
+                ErrorCode::Conflict,
+                format!("request `{id}` is already running"),
+            )
+            .with_details(serde_json::json!({ "id": id })));
+        }
+        let (cancel, token) = watch::channel(false);
+        running.insert(id.clone(), cancel);
+        Ok((Running { requests: self, id }, CancelToken(token)))
+    }
+
+    /// Whether request `id` is in flight.
+    pub fn is_running(&self, id: &str) -> bool {
+        Self::key(id).is_ok_and(|id| self.running.lock().unwrap().contains_key(&id))
+    }
+
+    /// Stops request `id`, whose stream then ends with a `cancelled` error.
+    pub fn cancel(&self, id: &str) -> AppResult<()> {
+        let id = Self::key(id)?;
+        match self.running.lock().unwrap().get(&id) {
+            Some(cancel) => {
+                cancel.send_replace(true);
+                Ok(())
+            }
+            None => Err(AppError::new(
+                ErrorCode::NotFound,
+                format!("request `{id}` is not running"),
+            )
+            .with_details(serde_json::json!({ "id": id }))),
+        }
+    }
+
+    /// Runs `produce` as request `id`, sending what it produces over
+    /// `channel` in batches and finishing with [`StreamMessage::Done`] or
+    /// [`StreamMessage::Error`]. Returns the number of items sent, or the
+    /// error the stream ended with. A request that cannot start, e.g.
+    /// because `id` is taken, fails without sending anything.
+    pub async fn stream<T, F, Fut>(
+        &self,
+        id: &str,
+        channel: &Channel<StreamMessage<T>>,
+        batching: Batching,
+        produce: F,
+    ) -> AppResult<u64>
+    where
+        T: Serialize + Clone,
+        F: FnOnce(StreamSender<T>) -> Fut,
+        Fut: Future<Output = AppResult<()>>,
+    {
+        let (_running, token) = self.start(id)?;
+        let (tx, rx) = mpsc::channel(batching.buffer.max(1));
+        let sender = StreamSender {
+            tx,
+            token: token.clone(),
+        };
+
+        let outcome = tokio::select! {
+            (produced, delivered) = async {
+                tokio::join!(produce(sender), deliver(rx, channel, batching))
+            } => {
+                // A channel that failed is why the producer could not send.
+                delivered.and_then(|count| produced.map(|()| count))
+            }
+            _ = token.cancelled() => Err(cancelled()),
+        };
+
+        let last = match &outcome {
+            Ok(count) => StreamMessage::Done { count: *count },
+            Err(err) => {
+                tracing::debug!(id, "stream ended: {err}");
+                StreamMessage::Error { error: err.clone() }
+            }
+        };
+        // The window may have gone away; there is nobody left to tell.
+        let _ = channel.send(last);
+        outcome
+    }
+}
+
+/// Sends what arrives on `rx` until the producer is done. Returns how many
+/// items were sent.
+async fn deliver<T: Serialize + Clone>(
+    mut rx: mpsc::Receiver<T>,
+    channel: &Channel<StreamMessage<T>>,
+    batching: Batching,
+) -> AppResult<u64> {
+    let max_items = batching.max_items.max(1);
+    let mut count = 0;
+    while let Some(first) = rx.recv().await {
+        let mut items = vec![first];
+        let deadline = tokio::time::sleep(batching.max_delay);
+        tokio::pin!(deadline);
+        while items.len() < max_items {
+            tokio::select! {
+                item = rx.recv() => match item {
+                    Some(item) => items.push(item),
+                    None => break,
+                },
+                _ = &mut deadline => break,
+            }
+        }
+        count += items.len() as u64;
+        channel.send(StreamMessage::Batch { items })?;
+    }
+    Ok(count)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn cancel_request(requests: State<'_, Requests>, id: String) -> AppResult<()> {
+    requests.cancel(&id)
+}
@@ -0,0 +1,244 @@
+use std::path::PathBuf;
+
+use fluent_bundle::FluentArgs;
+use serde_json::json;
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Listener, Manager, Runtime, Window, WindowEvent};
+
+use crate::db::notifications::unread_count;
+use crate::db::Database;
+use crate::error::AppResult;
+use crate::events::{NOTIFICATIONS_CHANGED, RECENT_FILES_CHANGED, SETTINGS_CHANGED};
+use crate::i18n::I18n;
+use crate::menu::{self, MenuAction, MenuEntry};
+use crate::notifications::scheduler::Scheduler;
+use crate::settings::{self, SettingsStore};
+use crate::windows::MAIN_WINDOW;
+
+pub const TRAY_ID: &str = "main";
+
+/// What picking a tray-only menu item does. The items the tray shares with
+/// the application menu use [`MenuAction`]s instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayAction {
+    /// Shows the main window if it is hidden, hides it otherwise.
+    ToggleMain,
+    TogglePause,
+}
+
+impl TrayAction {
+    /// The menu item id this action is bound to.
+    pub fn id(self) -> &'static str {
+        match self {
+            Self::ToggleMain => "toggle-main",
+            Self::TogglePause => "toggle-pause",
+        }
+    }
+
+    /// The action bound to a menu item id, if any.
+    pub fn parse(id: &str) -> Option<Self> {
+        match id {
+            "toggle-main" => Some(Self::ToggleMain),
+            "toggle-pause" => Some(Self::TogglePause),
+            _ => None,
+        }
+    }
+}
+
+/// Everything the tray shows. The menu is rebuilt from scratch whenever any
+/// of it changes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrayModel {
+    pub main_visible: bool,
+    /// Most recent first, at most [`menu::RECENT_ITEMS`].
+    pub recent: Vec<PathBuf>,
+    pub unread: u64,
+    pub paused: bool,
+}
+
+impl TrayModel {
+    /// Reads the model from the app's state.
+    pub fn current<R: Runtime>(app: &AppHandle<R>) -> AppResult<Self> {
+        let main_visible = match app.get_webview_window(MAIN_WINDOW) {
+            Some(window) => window.is_visible()?,
+            None => false,
+        };
+        let conn = app.state::<Database>().conn()?;
+        Ok(Self {
+            main_visible,
+            recent: menu::recent(app)?,
+            unread: unread_count(&conn)?,
+            paused: app.state::<SettingsStore>().get().notifications.paused,
+        })
+    }
+
+    pub fn entries(&self, i18n: &I18n) -> AppResult<Vec<MenuEntry>> {
+        let text = |key| i18n.translate(None, key, None);
+        let mut args = FluentArgs::new();
+        args.set("count", self.unread);
+        let toggle = if self.main_visible {
+            "tray-hide"
+        } else {
+            "tray-show"
+        };
+        Ok(vec![
+            MenuEntry::Item {
+                id: TrayAction::ToggleMain.id().into(),
+                label: text(toggle)?,
+                enabled: true,
+                accelerator: None,
+            },
+            MenuEntry::Separator,
+            MenuEntry::Submenu {
+                label: text("tray-recent")?,
+                items: menu::recent_items(&self.recent, i18n)?,
+            },
+            MenuEntry::Separator,
+            MenuEntry::Item {
+                id: "unread".into(),
+                label: i18n.translate(None, "tray-unread", Some(&args))?,
+                enabled: false,
+                accelerator: None,
+            },
+            MenuEntry::Check {
+                id: TrayAction::TogglePause.id().into(),
+                label: text("tray-pause")?,
+                checked: self.paused,
+            },
+            MenuEntry::Separator,
+            MenuEntry::Item {
+                id: MenuAction::Quit.id(),
+                label: text("tray-quit")?,
+                enabled: true,
+                accelerator: None,
+            },
+        ])
+    }
+
+    pub fn tooltip(&self, i18n: &I18n) -> AppResult<String> {
+        let mut args = FluentArgs::new();
+        args.set("count", self.unread);
+        i18n.translate(None, "tray-tooltip", Some(&args))
+    }
+}
+
+/// Shows the tray icon and keeps its menu up to date.
+pub fn create<R: Runtime>(app: &AppHandle<R>) -> AppResult<()> {
+    let model = TrayModel::current(app)?;
+    let i18n = app.state::<I18n>();
+    let menu = menu::build_menu(app, &model.entries(&i18n)?)?;
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .menu(&menu)
+        .tooltip(model.tooltip(&i18n)?)
+        .show_menu_on_left_click(false)
+        .on_menu_event(|app, event| {
+            if let Some(action) = TrayAction::parse(event.id().as_ref()) {
+                handle(app, action);
+            }
+        })
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                handle(tray.app_handle(), TrayAction::ToggleMain);
+            }
+        });
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+
+    for event in [
+        SETTINGS_CHANGED,
+        NOTIFICATIONS_CHANGED,
+        RECENT_FILES_CHANGED,
+    ] {
+        let app = app.clone();
+        app.clone().listen_any(event, move |_| refresh(&app));
+    }
+    Ok(())
+}
+
+/// Rebuilds the tray menu and tooltip from the app's current state. Does
+/// nothing without a tray icon.
+pub fn refresh<R: Runtime>(app: &AppHandle<R>) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    let update = || -> AppResult<()> {
+        let model = TrayModel::current(app)?;
+        let i18n = app.state::<I18n>();
+        tray.set_menu(Some(menu::build_menu(app, &model.entries(&i18n)?)?))?;
+        tray.set_tooltip(Some(model.tooltip(&i18n)?))?;
+        Ok(())
+    };
+    if let Err(err) = update() {
+        tracing::warn!("could not update the tray: {err}");
+    }
+}
+
+/// Carries out a tray menu action.
+pub fn handle<R: Runtime>(app: &AppHandle<R>, action: TrayAction) {
+    let result = match action {
+        TrayAction::ToggleMain => toggle_main(app),
+        TrayAction::TogglePause => {
+            let paused = app.state::<SettingsStore>().get().notifications.paused;
+            settings::apply(app, json!({ "notifications": { "paused": !paused } })).map(|_| {
+                // Deliver whatever was deferred while paused.
+                if paused {
+                    app.state::<Scheduler>().wake();
+                }
+            })
+        }
+    };
+    if let Err(err) = result {
+        tracing::warn!("tray action failed: {err}");
+    }
+}
+
+fn show_main<R: Runtime>(app: &AppHandle<R>) -> AppResult<()> {
+    if let Some(window) = app.get_webview_window(MAIN_WINDOW) {
+        window.show()?;
+        window.unminimize()?;
+        window.set_focus()?;
+    }
+    refresh(app);
+    Ok(())
+}
+
+fn toggle_main<R: Runtime>(app: &AppHandle<R>) -> AppResult<()> {
+    match app.get_webview_window(MAIN_WINDOW) {
+        Some(window) if window.is_visible()? => {
+            window.hide()?;
+            refresh(app);
+            Ok(())
+        }
+        _ => show_main(app),
+    }
+}
+
+/// Hides the main window instead of closing it when the settings ask for
+/// that and there is a tray icon to bring it back with. Returns whether the
+/// close was prevented.
+pub fn on_window_event<R: Runtime>(window: &Window<R>, event: &WindowEvent) -> bool {
+    let WindowEvent::CloseRequested { api, .. } = event else {
+        return false;
+    };
+    let app = window.app_handle();
+    if window.label() != MAIN_WINDOW
+        || !app.state::<SettingsStore>().get().close_to_tray
+        || app.tray_by_id(TRAY_ID).is_none()
+    {
+        return false;
+    }
+
+    api.prevent_close();
+    if let Err(err) = window.hide() {
+        tracing::warn!("could not hide the main window: {err}");
+    }
+    refresh(app);
+    true
+}
@@ -0,0 +1,135 @@
+use serde::Serialize;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::error::{AppError, ErrorCode};
+
+/// Rules applied to free-text command arguments before a handler uses them.
+///
+/// Rules are `const` so each command can declare its limits next to the
+/// handler, e.g. [`NAME`].
+#[derive(Debug, Clone, Copy)]
+pub struct TextRule {
+    min_chars: usize,
+    max_chars: usize,
+    trim: bool,
+    allow_newlines: bool,
+}
+
+/// Bytes of input allowed per character before normalization: four code
+/// points of at most four bytes each.
+const MAX_BYTES_PER_CHAR: usize = 4 * 4;
+
+/// Person names as typed into the greet form.
+pub const NAME: TextRule = TextRule::new(1, 64);
+
+impl TextRule {
+    pub const fn new(min_chars: usize, max_chars: usize) -> Self {
+        Self {
+            min_chars,
+            max_chars,
+            trim: true,
+            allow_newlines: false,
+        }
+    }
+
+    pub const fn keep_whitespace(mut self) -> Self {
+        self.trim = false;
+        self
+    }
+
+    pub const fn allow_newlines(mut self) -> Self {
+        self.allow_newlines = true;
+        self
+    }
+
+    /// Validates `input` and returns its trimmed, NFC-normalized form.
+    pub fn check(&self, input: &str) -> Result<String, ValidationError> {
+        let input = if self.trim { input.trim() } else { input };
+        // Only guards against abuse before we spend time normalizing; the
+        // exact limit is checked afterwards. Decomposed input can take
+        // several code points of up to four bytes each per character, such
+        // as NFD Hangul at nine bytes per syllable, so the bound is generous.
+        if input.len() > self.max_chars.saturating_mul(MAX_BYTES_PER_CHAR) {
+            return Err(ValidationError::TooLong {
+                max: self.max_chars,
+            });
+        }
+
+        let value: String = input.nfc().collect();
+
+        for (position, ch) in value.chars().enumerate() {
+            if is_bidi_control(ch) {
+                return Err(ValidationError::BidiControl { position });
+            }
+            if ch.is_control() && !(self.allow_newlines && matches!(ch, '\n' | '\r' | '\t')) {
+                return Err(ValidationError::ControlCharacter { position });
+            }
+        }
+
+        let len = value.chars().count();
+        if len == 0 && self.min_chars > 0 {
+            return Err(ValidationError::Empty);
+        }
+        if len < self.min_chars {
+            return Err(ValidationError::TooShort {
+                min: self.min_chars,
+            });
+        }
+        if len > self.max_chars {
+            return Err(ValidationError::TooLong {
+                max: self.max_chars,
+            });
+        }
+
+        Ok(value)
+    }
+
+    /// Like [`TextRule::check`], but reports failures as an [`AppError`]
+    /// naming the offending argument.
+    pub fn apply(&self, field: &'static str, input: &str) -> Result<String, AppError> {
+        self.check(input)
+            .map_err(|reason| reason.into_app_error(field))
+    }
+}
+
+/// Why a value was rejected. Serialized into `AppError::details`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, thiserror::Error)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum ValidationError {
+    #[error("must not be empty")]
+    Empty,
+    #[error("must be at least {min} characters")]
+    TooShort { min: usize },
+    #[error("must be at most {max} characters")]
+    TooLong { max: usize },
+    #[error("contains a control character at position {position}")]
+    ControlCharacter { position: usize },
+    #[error("contains a bidirectional control character at position {position}")]
+    BidiControl { position: usize },
+}
+
+impl ValidationError {
+    pub fn into_app_error(self, field: &'static str) -> AppError {
+        #[derive(Serialize)]
+        struct Details<'a> {
+            field: &'static str,
+            #[serde(flatten)]
+            reason: &'a ValidationError,
+        }
+
+        AppError::new(ErrorCode::InvalidInput, format!("`{field}` {self}")).with_details(Details {
+            field,
+            reason: &self,
+        })
+    }
+}
+
+/// Embedding, override and isolate controls, and the implicit direction
+/// marks, which can make displayed text read differently from what was
+/// stored.
+fn is_bidi_control(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}' | '\u{200E}' | '\u{200F}' | '\u{061C}'
+    )
+}
@@ -0,0 +1,378 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{Manager, PhysicalPosition, PhysicalSize, Runtime, Window, WindowEvent};
+
+use super::WindowRole;
+use crate::error::AppResult;
+use crate::files;
+
+/// Name of the saved window geometry in the app config directory.
+pub const WINDOW_STATE_FILE: &str = "window-state.json";
+
+/// Key shared by the geometry of every document window; see [`key`].
+pub const DOCUMENT_KEY: &str = "document";
+
+/// How long a window must stay put after moving or resizing before its
+/// geometry is written to disk.
+pub const SAVE_DELAY: Duration = Duration::from_millis(500);
+
+/// Smallest size a window is restored at, in physical pixels.
+pub const MIN_SIZE: (u32, u32) = (200, 150);
+
+/// How much of a window, in physical pixels each way, must overlap a monitor
+/// for the window to count as on that monitor.
+pub const MIN_VISIBLE: u32 = 64;
+
+/// A rectangle in physical pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rect {
+    /// Width and height of the part of `self` inside `other`.
+    pub fn overlap(&self, other: &Rect) -> (u32, u32) {
+        let span = |start: i32, len: u32, other_start: i32, other_len: u32| {
+            let from = i64::from(start).max(i64::from(other_start));
+            let to = (i64::from(start) + i64::from(len))
+                .min(i64::from(other_start) + i64::from(other_len));
+            (to - from).max(0) as u32
+        };
+        (
+            span(self.x, self.width, other.x, other.width),
+            span(self.y, self.height, other.y, other.height),
+        )
+    }
+}
+
+/// The usable area of a connected monitor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MonitorArea {
+    pub name: Option<String>,
+    /// The monitor minus taskbars and docks.
+    pub work_area: Rect,
+}
+
+/// Where a window was and how it was shown.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowGeometry {
+    /// Outer position and inner size, as last seen while neither maximized
+    /// nor fullscreen.
+    pub rect: Rect,
+    pub maximized: bool,
+    pub fullscreen: bool,
+    /// Name of the monitor the window was on, if it had one.
+    pub monitor: Option<String>,
+}
+
+/// Shrinks `rect` to fit `area` if needed, but not below [`MIN_SIZE`], and
+/// moves it fully inside.
+pub fn fit(rect: Rect, area: &Rect) -> Rect {
+    let width = rect.width.max(MIN_SIZE.0).min(area.width);
+    let height = rect.height.max(MIN_SIZE.1).min(area.height);
+    let clamp = |pos: i32, len: u32, start: i32, area_len: u32| {
+        let last = i64::from(start) + i64::from(area_len) - i64::from(len);
+        i64::from(pos).clamp(i64::from(start), last) as i32
+    };
+    Rect {
+        x: clamp(rect.x, width, area.x, area.width),
+        y: clamp(rect.y, height, area.y, area.height),
+        width,
+        height,
+    }
+}
+
+/// `rect`'s size fitted to `area`, centred in it.
+pub fn center(rect: Rect, area: &Rect) -> Rect {
+    let fitted = fit(rect, area);
+    Rect {
+        x: area.x + ((area.width - fitted.width) / 2) as i32,
+        y: area.y + ((area.height - fitted.height) / 2) as i32,
+        ..fitted
+    }
+}
+
+/// Where to show a window saved as `saved`, given the monitors connected now,
+/// primary first.
+///
+/// A window goes back to its monitor if that is still connected, otherwise to
+/// whichever monitor it mostly overlaps. A window left on no monitor is
+/// centred on the primary one. Either way it ends up fully on-screen. With no
+/// monitors known, only the size is checked.
+pub fn restore(saved: &WindowGeometry, monitors: &[MonitorArea]) -> WindowGeometry {
+    let min_size = |rect: Rect| Rect {
+        width: rect.width.max(MIN_SIZE.0),
+        height: rect.height.max(MIN_SIZE.1),
+        ..rect
+    };
+    let Some(primary) = monitors.first() else {
+        return WindowGeometry {
+            rect: min_size(saved.rect),
+            ..saved.clone()
+        };
+    };
+
+    let by_name = saved.monitor.as_ref().and_then(|name| {
+        monitors
+            .iter()
+            .find(|monitor| monitor.name.as_ref() == Some(name))
+    });
+    let by_overlap = || {
+        monitors
+            .iter()
+            .map(|monitor| (monitor, saved.rect.overlap(&monitor.work_area)))
+            .filter(|(_, (width, height))| *width >= MIN_VISIBLE && *height >= MIN_VISIBLE)
+            .max_by_key(|(_, (width, height))| u64::from(*width) * u64::from(*height))
+            .map(|(monitor, _)| monitor)
+    };
+
+    let (monitor, rect) = match by_name.or_else(by_overlap) {
+        Some(monitor) => (monitor, fit(saved.rect, &monitor.work_area)),
+        None => (primary, center(saved.rect, &primary.work_area)),
+    };
+    WindowGeometry {
+        rect,
+        monitor: monitor.name.clone(),
+        ..saved.clone()
+    }
+}
+
+/// Key the geometry of the window labelled `label` is kept under, or `None`
+/// for labels of no known role. Windows with a fixed label, such as `main`
+/// or `settings`, each have their own entry. Document window labels are
+/// numbered per session, so the same label means a different document next
+/// time; instead all of them share [`DOCUMENT_KEY`], and a new document
+/// window opens where the last one was.
+pub fn key(label: &str) -> Option<&str> {
+    match WindowRole::of(label)? {
+        WindowRole::Document => Some(DOCUMENT_KEY),
+        _ => Some(label),
+    }
+}
+
+/// Saved geometry of every window, by [`key`]. Held in managed state.
+pub struct GeometryStore {
+    path: PathBuf,
+    windows: Mutex<BTreeMap<String, WindowGeometry>>,
+    /// Bumped on every change, so a delayed save can tell it is stale.
+    changes: AtomicU64,
+    /// Held while saving, so an older snapshot never replaces a newer one.
+    saving: Mutex<()>,
+}
+
+impl GeometryStore {
+    /// Loads saved geometry from `path`. A missing or unreadable file just
+    /// means windows open at their defaults. Entries older versions saved
+    /// per document window label are moved to [`DOCUMENT_KEY`], unless it
+    /// already has one.
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let saved: BTreeMap<String, WindowGeometry> = match fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_else(|err| {
+                tracing::warn!(path = %path.display(), "ignoring saved window state: {err}");
+                BTreeMap::new()
+            }),
+            Err(err) => {
+                if err.kind() != std::io::ErrorKind::NotFound {
+                    tracing::warn!(path = %path.display(), "could not read window state: {err}");
+                }
+                BTreeMap::new()
+            }
+        };
+        let mut windows = BTreeMap::new();
+        // Sorted, so `document` comes before any `document-N`.
+        for (label, geometry) in saved {
+            let key = if label == DOCUMENT_KEY {
+                Some(DOCUMENT_KEY)
+            } else {
+                key(&label)
+            };
+            if let Some(key) = key {
+                windows.entry(key.to_owned()).or_insert(geometry);
+            }
+        }
+        Self {
+            path,
+            windows: Mutex::new(windows),
+            changes: AtomicU64::new(0),
+            saving: Mutex::new(()),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Saved geometry for the window labelled `label`, under its [`key`].
+    pub fn get(&self, label: &str) -> Option<WindowGeometry> {
+        let key = key(label)?;
+        self.windows.lock().unwrap().get(key).cloned()
+    }
+
+    /// Records `geometry` for `label`, under its [`key`], in memory; see
+    /// [`GeometryStore::save`]. Does nothing for labels with no key.
+    pub fn set(&self, label: &str, geometry: WindowGeometry) {
+        let Some(key) = key(label) else {
+            return;
+        };
+        self.windows.lock().unwrap().insert(key.into(), geometry);
+        self.changes.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Writes every window's geometry to disk. Windows keep moving while
+    /// the file is written.
+    pub fn save(&self) -> AppResult<()> {
+        let _saving = self.saving.lock().unwrap();
+        let snapshot = self.windows.lock().unwrap().clone();
+        let json = serde_json::to_vec_pretty(&snapshot)?;
+        files::write_atomic(&self.path, &json)
+    }
+
+    /// Saves once nothing has changed for [`SAVE_DELAY`].
+    fn save_later<R: Runtime>(&self, window: &Window<R>) {
+        let seen = self.changes.load(Ordering::Relaxed);
+        let app = window.app_handle().clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(SAVE_DELAY).await;
+            let store = app.state::<GeometryStore>();
+            if store.changes.load(Ordering::Relaxed) == seen {
+                if let Err(err) = store.save() {
+                    tracing::warn!("could not save window state: {err}");
+                }
+            }
+        });
+    }
+}
+
+fn monitors<R: Runtime>(window: &Window<R>) -> Vec<MonitorArea> {
+    let primary = window.primary_monitor().ok().flatten();
+    let mut monitors: Vec<MonitorArea> = window
+        .available_monitors()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|monitor| {
+            let area = monitor.work_area();
+            MonitorArea {
+                name: monitor.name().cloned(),
+                work_area: Rect {
+                    x: area.position.x,
+                    y: area.position.y,
+                    width: area.size.width,
+                    height: area.size.height,
+                },
+            }
+        })
+        .collect();
+    if let Some(name) = primary.as_ref().and_then(|monitor| monitor.name()) {
+        if let Some(index) = monitors
+            .iter()
+            .position(|monitor| monitor.name.as_ref() == Some(name))
+        {
+            monitors.swap(0, index);
+        }
+    }
+    monitors
+}
+
+/// The window's geometry as it is now, or `None` while it is minimized.
+/// While maximized or fullscreen the last normal position and size are kept.
+pub fn capture<R: Runtime>(window: &Window<R>) -> AppResult<Option<WindowGeometry>> {
+    if window.is_minimized()? {
+        return Ok(None);
+    }
+    let maximized = window.is_maximized()?;
+    let fullscreen = window.is_fullscreen()?;
+    let previous = window.state::<GeometryStore>().get(window.label());
+    let rect = match previous {
+        Some(previous) if maximized || fullscreen => previous.rect,
+        _ => {
+            let position = window.outer_position()?;
+            let size = window.inner_size()?;
+            Rect {
+                x: position.x,
+                y: position.y,
+                width: size.width,
+                height: size.height,
+            }
+        }
+    };
+    let monitor = window
+        .current_monitor()?
+        .and_then(|monitor| monitor.name().cloned());
+    Ok(Some(WindowGeometry {
+        rect,
+        maximized,
+        fullscreen,
+        monitor,
+    }))
+}
+
+/// Puts a newly created window where it was last time, if it has been open
+/// before.
+pub fn apply<R: Runtime>(window: &Window<R>) -> AppResult<()> {
+    let Some(saved) = window.state::<GeometryStore>().get(window.label()) else {
+        return Ok(());
+    };
+    let geometry = restore(&saved, &monitors(window));
+    window.set_position(PhysicalPosition::new(geometry.rect.x, geometry.rect.y))?;
+    window.set_size(PhysicalSize::new(geometry.rect.width, geometry.rect.height))?;
+    if geometry.fullscreen {
+        window.set_fullscreen(true)?;
+    } else if geometry.maximized {
+        window.maximize()?;
+    }
+    if geometry != saved {
+        tracing::info!(label = window.label(), "moved window back on-screen");
+    }
+    Ok(())
+}
+
+fn record<R: Runtime>(window: &Window<R>) -> bool {
+    match capture(window) {
+        Ok(Some(geometry)) => {
+            window
+                .state::<GeometryStore>()
+                .set(window.label(), geometry);
+            true
+        }
+        Ok(None) => false,
+        Err(err) => {
+            tracing::warn!(
+                label = window.label(),
+                "could not read window geometry: {err}"
+            );
+            false
+        }
+    }
+}
+
+/// Records geometry as windows move and resize, saving it shortly after they
+/// settle, and right away when one is about to close. Windows with no
+/// [`key`] are left alone.
+pub fn on_window_event<R: Runtime>(window: &Window<R>, event: &WindowEvent) {
+    if key(window.label()).is_none() {
+        return;
+    }
+    match event {
+        WindowEvent::Moved(_) | WindowEvent::Resized(_) if record(window) => {
+            window.state::<GeometryStore>().save_later(window);
+        }
+        WindowEvent::CloseRequested { .. } => {
+            record(window);
+            if let Err(err) = window.state::<GeometryStore>().save() {
+                tracing::warn!("could not save window state: {err}");
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,417 @@
+pub mod geometry;
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::ipc::Invoke;
+use tauri::{
+    AppHandle, Manager, Runtime, State, WebviewUrl, WebviewWindow, WebviewWindowBuilder, Window,
+    WindowEvent,
+};
+
+use crate::documents::Documents;
+use crate::error::{AppError, AppResult, ErrorCode};
+use crate::events::AppEvent;
+use crate::i18n::I18n;
+
+/// Label of the window created from `tauri.conf.json`.
+pub const MAIN_WINDOW: &str = "main";
+
+/// Document windows are labelled `document-<n>`.
+const DOCUMENT_PREFIX: &str = "document-";
+
+/// App commands any window may call.
+pub const COMMON_COMMANDS: &[&str] = &["translate", "get_settings", "log_from_frontend"];
+
+/// App commands the settings window may call besides [`COMMON_COMMANDS`].
+pub const SETTINGS_COMMANDS: &[&str] = &[
+    "update_settings",
+    "read_logs",
+    "list_recent_files",
+    "clear_recent_files",
+    "clear_history",
+];
+
+/// What a window is for, which decides how it is created and which app
+/// commands it may call. Plugin permissions are granted per role by the
+/// files in `capabilities/`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowRole {
+    Main,
+    Document,
+    Settings,
+    About,
+}
+
+/// How a window of a given role is created.
+struct RoleDefaults {
+    /// Message id of the title; document windows use the document's name.
+    title: &'static str,
+    /// The frontend page, with the route `src/main.tsx` picks the view by,
+    /// so each role only shows what it may call commands for.
+    page: &'static str,
+    size: (f64, f64),
+    min_size: Option<(f64, f64)>,
+    resizable: bool,
+}
+
+impl WindowRole {
+    /// The role of the window labelled `label`, if it is one of ours.
+    pub fn of(label: &str) -> Option<Self> {
+        match label {
+            MAIN_WINDOW => Some(Self::Main),
+            "settings" => Some(Self::Settings),
+            "about" => Some(Self::About),
+            _ => label
+                .strip_prefix(DOCUMENT_PREFIX)
+                .filter(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+                .map(|_| Self::Document),
+        }
+    }
+
+    /// Whether at most one window of this role may be open; its label is
+    /// then fixed.
+    pub fn singleton(self) -> bool {
+        self != Self::Document
+    }
+
+    /// Whether a window of this role may call the app command `command`.
+    pub fn allows(self, command: &str) -> bool {
+        match self {
+            Self::Main | Self::Document => true,
+            Self::Settings => {
+                COMMON_COMMANDS.contains(&command) || SETTINGS_COMMANDS.contains(&command)
+            }
+            Self::About => COMMON_COMMANDS.contains(&command),
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Main => MAIN_WINDOW,
+            Self::Document => DOCUMENT_PREFIX,
+            Self::Settings => "settings",
+            Self::About => "about",
+        }
+    }
+
+    fn defaults(self) -> RoleDefaults {
+        match self {
+            Self::Main => RoleDefaults {
+                title: "window-title-main",
+                page: "index.html",
+                size: (800.0, 600.0),
+                min_size: None,
+                resizable: true,
+            },
+            Self::Document => RoleDefaults {
+                title: "document-untitled",
+                page: "index.html",
+                size: (900.0, 700.0),
+                min_size: Some((400.0, 300.0)),
+                resizable: true,
+            },
+            Self::Settings => RoleDefaults {
+                title: "window-title-settings",
+                page: "index.html#/settings",
+                size: (640.0, 480.0),
+                min_size: Some((480.0, 360.0)),
+                resizable: true,
+            },
+            Self::About => RoleDefaults {
+                title: "window-title-about",
+                page: "index.html#/about",
+                size: (360.0, 280.0),
+                min_size: None,
+                resizable: false,
+            },
+        }
+    }
+}
+
+/// A window opened through the [`WindowManager`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowInfo {
+    pub label: String,
+    pub role: WindowRole,
+    /// The document shown by a document window.
+    pub document: Option<u64>,
+}
+
+/// The app's windows, by label. Held in managed state.
+pub struct WindowManager {
+    next_document: AtomicU64,
+    windows: Mutex<BTreeMap<String, WindowInfo>>,
+}
+
+impl Default for WindowManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WindowManager {
+    pub fn new() -> Self {
+        Self {
+            next_document: AtomicU64::new(1),
+            windows: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Starts tracking a window created elsewhere, e.g. from the config.
+    pub fn track(&self, label: &str, document: Option<u64>) -> AppResult<WindowInfo> {
+        let role = WindowRole::of(label).ok_or_else(|| {
+            AppError::new(
+                ErrorCode::InvalidInput,
+                format!("`{label}` is not a window label the app uses"),
+            )
+            .with_details(serde_json::json!({ "field": "label", "reason": "invalid" }))
+        })?;
+        let info = WindowInfo {
+            label: label.into(),
+            role,
+            document,
+        };
+        self.windows
+            .lock()
+            .unwrap()
+            .insert(info.label.clone(), info.clone());
+        Ok(info)
+    }
+
+    fn untrack(&self, label: &str) {
+        self.windows.lock().unwrap().remove(label);
+    }
+
+    /// Every tracked window, by label.
+    pub fn list(&self) -> Vec<WindowInfo> {
+        self.windows.lock().unwrap().values().cloned().collect()
+    }
+
+    pub fn get(&self, label: &str) -> Option<WindowInfo> {
+        self.windows.lock().unwrap().get(label).cloned()
+    }
+
+    /// Every tracked window's label, or `None` while the list is being
+    /// changed. Never blocks, so a panic hook may call it.
+    pub fn try_labels(&self) -> Option<Vec<String>> {
+        Some(self.windows.try_lock().ok()?.keys().cloned().collect())
+    }
+
+    /// Labels of the windows that have `role`.
+    pub fn labels(&self, role: WindowRole) -> Vec<String> {
+        self.find(|info| info.role == role)
+    }
+
+    /// Labels of the windows showing `document`.
+    pub fn document_labels(&self, document: u64) -> Vec<String> {
+        self.find(|info| info.document == Some(document))
+    }
+
+    fn find(&self, matches: impl Fn(&WindowInfo) -> bool) -> Vec<String> {
+        let windows = self.windows.lock().unwrap();
+        windows
+            .values()
+            .filter(|info| matches(info))
+            .map(|info| info.label.clone())
+            .collect()
+    }
+
+    /// Opens a window for `role`, or focuses the one already open if the role
+    /// is a singleton or `document` is already shown. Document windows may
+    /// show an open document; other roles must not be given one.
+    pub fn open<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        role: WindowRole,
+        document: Option<u64>,
+    ) -> AppResult<WindowInfo> {
+        let existing = match (role, document) {
+            (WindowRole::Document, Some(id)) => self.document_labels(id).into_iter().next(),
+            (WindowRole::Document, None) => None,
+            (_, Some(_)) => {
+                return Err(AppError::new(
+                    ErrorCode::InvalidInput,
+                    "only document windows can show a document",
+                )
+                .with_details(serde_json::json!({ "field": "document", "reason": "role" })));
+            }
+            (role, None) => Some(role.label().to_string()),
+        };
+        if let Some(window) = existing.and_then(|label| app.get_webview_window(&label)) {
+            focus(&window);
+            return match self.get(window.label()) {
+                Some(info) => Ok(info),
+                None => self.track(window.label(), document),
+            };
+        }
+
+        let defaults = role.defaults();
+        let i18n = app.state::<I18n>();
+        let title = match document {
+            Some(id) => match app.state::<Documents>().get(id)?.name() {
+                Some(name) => name,
+                None => i18n.translate(None, defaults.title, None)?,
+            },
+            None => i18n.translate(None, defaults.title, None)?,
+        };
+        let label = if role.singleton() {
+            role.label().to_string()
+        } else {
+            let n = self.next_document.fetch_add(1, Ordering::Relaxed);
+            format!("{DOCUMENT_PREFIX}{n}")
+        };
+
+        let info = WindowInfo {
+            label,
+            role,
+            document,
+        };
+        if let Some(claimed) = self.claim(&info) {
+            return Ok(claimed);
+        }
+        let label = info.label.as_str();
+
+        let (width, height) = defaults.size;
+        // Hidden until moved to where it was last time.
+        let url = WebviewUrl::App(defaults.page.into());
+        let mut builder = WebviewWindowBuilder::new(app, label, url)
+            .title(title)
+            .inner_size(width, height)
+            .resizable(defaults.resizable)
+            .center()
+            .visible(false);
+        if let Some((width, height)) = defaults.min_size {
+            builder = builder.min_inner_size(width, height);
+        }
+        if role == WindowRole::About {
+            builder = builder.maximizable(false).minimizable(false);
+        }
+        let window = builder.build().inspect_err(|_| self.untrack(label))?;
+        restore_and_show(&window);
+        tracing::info!(label = %label, ?role, document, "window opened");
+        Ok(info)
+    }
+
+    /// Tracks `info` before its window is built, unless a window with the
+    /// same label or document is already tracked, which is returned instead.
+    /// Concurrent opens of one singleton or document thus build one window
+    /// between them without holding a lock across `build`, which waits for
+    /// the main thread.
+    fn claim(&self, info: &WindowInfo) -> Option<WindowInfo> {
+        let mut windows = self.windows.lock().unwrap();
+        let taken = windows.values().find(|other| {
+            other.label == info.label
+                || (info.document.is_some() && other.document == info.document)
+        });
+        if let Some(other) = taken {
+            return Some(other.clone());
+        }
+        windows.insert(info.label.clone(), info.clone());
+        None
+    }
+}
+
+/// Restores the saved geometry of a hidden window, then shows it.
+pub fn restore_and_show<R: Runtime>(window: &WebviewWindow<R>) {
+    if let Err(err) = geometry::apply(&window.as_ref().window()) {
+        tracing::warn!(
+            label = window.label(),
+            "could not restore window geometry: {err}"
+        );
+    }
+    if let Err(err) = window.show() {
+        tracing::warn!(label = window.label(), "could not show window: {err}");
+    }
+}
+
+fn focus<R: Runtime>(window: &WebviewWindow<R>) {
+    let shown = window
+        .unminimize()
+        .and_then(|_| window.show())
+        .and_then(|_| window.set_focus());
+    if let Err(err) = shown {
+        tracing::warn!(label = window.label(), "could not focus window: {err}");
+    }
+}
+
+/// Sends `event` to every window that has `role`.
+pub fn emit_to_role<R: Runtime>(
+    app: &AppHandle<R>,
+    role: WindowRole,
+    event: &AppEvent,
+) -> AppResult<()> {
+    for label in app.state::<WindowManager>().labels(role) {
+        event.emit_to(app, &label)?;
+    }
+    Ok(())
+}
+
+/// Sends `event` to every window showing `document`.
+pub fn emit_to_document<R: Runtime>(
+    app: &AppHandle<R>,
+    document: u64,
+    event: &AppEvent,
+) -> AppResult<()> {
+    for label in app.state::<WindowManager>().document_labels(document) {
+        event.emit_to(app, &label)?;
+    }
+    Ok(())
+}
+
+/// Lets `invoke` through if the calling window's role allows the command;
+/// otherwise rejects it with `permission_denied` and returns `None`.
+pub fn authorize<R: Runtime>(invoke: Invoke<R>) -> Option<Invoke<R>> {
+    let webview = invoke.message.webview();
+    let label = webview.label();
+    let command = invoke.message.command();
+    if WindowRole::of(label).is_some_and(|role| role.allows(command)) {
+        return Some(invoke);
+    }
+
+    tracing::warn!(label, command, "refused command from window");
+    let err = AppError::new(
+        ErrorCode::PermissionDenied,
+        format!("window `{label}` may not call `{command}`"),
+    )
+    .with_details(serde_json::json!({ "command": command, "reason": "window" }));
+    invoke.resolver.reject(err);
+    None
+}
+
+/// Stops tracking windows once they are destroyed.
+pub fn on_window_event<R: Runtime>(window: &Window<R>, event: &WindowEvent) {
+    if let WindowEvent::Destroyed = event {
+        window.state::<WindowManager>().untrack(window.label());
+    }
+}
+
+/// Opens a settings, about or document window, or focuses the open one.
+#[tauri::command]
+#[specta::specta]
+pub async fn open_window<R: Runtime>(
+    app: AppHandle<R>,
+    manager: State<'_, WindowManager>,
+    role: WindowRole,
+    document: Option<u64>,
+) -> AppResult<WindowInfo> {
+    if role == WindowRole::Main {
+        return Err(AppError::new(
+            ErrorCode::InvalidInput,
+            "the main window cannot be opened again",
+        )
+        .with_details(serde_json::json!({ "field": "role", "reason": "main" })));
+    }
+    manager.open(&app, role, document)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn list_windows(manager: State<'_, WindowManager>) -> AppResult<Vec<WindowInfo>> {
+    Ok(manager.list())
+}
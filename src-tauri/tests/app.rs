@@ -0,0 +1,59 @@
+mod common;
+
+use common::TestApp;
+use serde_json::json;
+use tauri::test::MockRuntime;
+use tauri::Manager;
+use tauri_app_lib::i18n::I18n;
+use tauri_app_lib::Plugins;
+use tauri_plugin_dialog::Dialog;
+use tauri_plugin_shell::Shell;
+
+#[test]
+fn registers_all_plugins_by_default() {
+    let app = TestApp::new();
+
+    assert!(app.app.try_state::<Dialog<MockRuntime>>().is_some());
+    assert!(app.app.try_state::<Shell<MockRuntime>>().is_some());
+}
+
+#[test]
+fn plugins_can_be_disabled() {
+    let app = TestApp::with(|builder| {
+        builder.with_plugins(Plugins {
+            dialog: false,
+            ..Plugins::ALL
+        })
+    });
+
+    assert!(app.app.try_state::<Dialog<MockRuntime>>().is_none());
+    assert!(app.app.try_state::<Shell<MockRuntime>>().is_some());
+}
+
+#[test]
+fn injected_state_replaces_defaults() {
+    let app = TestApp::with(|builder| {
+        builder
+            .with_plugins(Plugins::NONE)
+            .with_state(I18n::new(&[("en-US", "greeting = Hi { $name }")], None))
+    });
+
+    assert_eq!(
+        app.invoke("greet", json!({ "name": "Ada" })),
+        Ok(json!("Hi Ada"))
+    );
+}
+
+#[test]
+fn the_last_state_of_a_type_wins() {
+    let app = TestApp::with(|builder| {
+        builder
+            .with_state(I18n::new(&[("en-US", "greeting = Hi { $name }")], None))
+            .with_state(I18n::new(&[("en-US", "greeting = Hello { $name }")], None))
+    });
+
+    assert_eq!(
+        app.invoke("greet", json!({ "name": "Ada" })),
+        Ok(json!("Hello Ada"))
+    );
+}
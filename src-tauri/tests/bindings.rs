@@ -0,0 +1,14 @@
+use std::path::Path;
+
+use tauri_app_lib::bindings::{self, BINDINGS_FILE};
+
+#[test]
+fn checked_in_bindings_are_current() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(BINDINGS_FILE);
+    let checked_in = std::fs::read_to_string(&path).unwrap_or_default();
+    assert!(
+        checked_in == bindings::typescript().unwrap(),
+        "{} is stale; run `cargo run --bin export_types`",
+        path.display()
+    );
+}
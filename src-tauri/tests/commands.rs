@@ -0,0 +1,335 @@
+mod common;
+
+use common::TestApp;
+use serde_json::json;
+use tauri::WebviewWindowBuilder;
+use tauri_app_lib::events::{NOTIFICATIONS_CHANGED, SETTINGS_CHANGED};
+use tauri_app_lib::logging::Logging;
+use tauri_app_lib::settings::SettingsStore;
+use tauri_app_lib::Plugins;
+
+#[test]
+fn greet_returns_localized_greeting() {
+    let app = TestApp::new();
+
+    assert_eq!(
+        app.invoke("greet", json!({ "name": "Ada", "locale": "en-US" })),
+        Ok(json!("Hello, Ada! You've been greeted from Rust!"))
+    );
+    assert_eq!(
+        app.invoke("greet", json!({ "name": "Ada", "locale": "es" })),
+        Ok(json!("¡Hola, Ada! Te han saludado desde Rust."))
+    );
+}
+
+#[test]
+fn greet_rejects_invalid_names() {
+    let app = TestApp::new();
+
+    assert_eq!(
+        app.invoke("greet", json!({ "name": " " })),
+        Err(json!({
+            "code": "invalid_input",
+            "message": "`name` must not be empty",
+            "details": { "field": "name", "reason": "empty" }
+        }))
+    );
+}
+
+#[test]
+fn greet_records_history_with_source_window() {
+    let app = TestApp::new();
+    let document = WebviewWindowBuilder::new(&app.app, "document-1", Default::default())
+        .build()
+        .unwrap();
+
+    app.invoke("greet", json!({ "name": "Ada", "locale": "fr-CA" }))
+        .unwrap();
+    app.invoke_from(&document, "greet", json!({ "name": "Alan" }))
+        .unwrap();
+
+    let page = app.invoke("list_greetings", json!({})).unwrap();
+    assert_eq!(page["total"], 2);
+    assert_eq!(page["items"][0]["name"], "Alan");
+    assert_eq!(page["items"][0]["window"], "document-1");
+    assert_eq!(page["items"][1]["name"], "Ada");
+    assert_eq!(page["items"][1]["locale"], "fr");
+
+    let found = app
+        .invoke("search_greetings", json!({ "prefix": "al" }))
+        .unwrap();
+    assert_eq!(found.as_array().unwrap().len(), 1);
+
+    assert_eq!(app.invoke("clear_history", json!({})), Ok(json!(2)));
+}
+
+#[test]
+fn update_settings_persists_and_emits_event() {
+    let app = TestApp::new();
+    app.capture(SETTINGS_CHANGED);
+
+    let updated = app
+        .invoke("update_settings", json!({ "patch": { "theme": "dark" } }))
+        .unwrap();
+
+    assert_eq!(updated["theme"], "dark");
+    assert_eq!(app.invoke("get_settings", json!({})), Ok(updated.clone()));
+    assert_eq!(
+        app.events(SETTINGS_CHANGED),
+        [json!({ "version": 1, "payload": updated })]
+    );
+}
+
+#[test]
+fn update_settings_prunes_history_when_retention_changes() {
+    let app = TestApp::new();
+    for name in ["Ada", "Alan", "Grace"] {
+        app.invoke("greet", json!({ "name": name })).unwrap();
+    }
+
+    app.invoke("update_settings", json!({ "patch": { "theme": "dark" } }))
+        .unwrap();
+    let page = app.invoke("list_greetings", json!({})).unwrap();
+    assert_eq!(page["total"], 3);
+
+    app.invoke(
+        "update_settings",
+        json!({ "patch": { "history": { "maxEntries": 1 } } }),
+    )
+    .unwrap();
+    let page = app.invoke("list_greetings", json!({})).unwrap();
+    assert_eq!(page["total"], 1);
+    assert_eq!(page["items"][0]["name"], "Grace");
+}
+
+#[test]
+fn update_settings_applies_locale() {
+    let app = TestApp::new();
+
+    app.invoke("update_settings", json!({ "patch": { "locale": "de" } }))
+        .unwrap();
+
+    assert_eq!(
+        app.invoke(
+            "translate",
+            json!({ "key": "greeting", "args": { "name": "Ada" } })
+        ),
+        Ok(json!("Hallo, Ada! Rust lässt grüßen."))
+    );
+
+    let err = app
+        .invoke("update_settings", json!({ "patch": { "locale": "??" } }))
+        .unwrap_err();
+    assert_eq!(err["code"], "invalid_input");
+}
+
+#[test]
+fn contacts_round_trip_over_ipc() {
+    let app = TestApp::new();
+
+    let created = app
+        .invoke(
+            "create_contact",
+            json!({ "contact": { "name": "Ada", "email": "ada@example.com" } }),
+        )
+        .unwrap();
+    let id = created["id"].clone();
+
+    assert_eq!(app.invoke("get_contact", json!({ "id": id })), Ok(created));
+    assert_eq!(
+        app.invoke("delete_contact", json!({ "id": id })),
+        Ok(json!(null))
+    );
+    assert_eq!(
+        app.invoke("get_contact", json!({ "id": id })).unwrap_err()["code"],
+        "not_found"
+    );
+}
+
+#[test]
+fn frontend_logs_are_accepted_and_logs_are_readable() {
+    let app = TestApp::new();
+
+    assert_eq!(
+        app.invoke(
+            "log_from_frontend",
+            json!({ "level": "error", "message": "Uncaught TypeError", "context": { "line": 3 } }),
+        ),
+        Ok(json!(null))
+    );
+    assert!(app
+        .invoke(
+            "log_from_frontend",
+            json!({ "level": "loud", "message": "hi" })
+        )
+        .is_err());
+
+    let dir = app.state::<Logging>().dir().to_owned();
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("app.2026-03-01.log"),
+        json!({
+            "timestamp": "2026-03-01T10:00:00Z",
+            "level": "ERROR",
+            "fields": { "message": "Uncaught TypeError", "window": "main" },
+            "target": "frontend",
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let logs = app
+        .invoke(
+            "read_logs",
+            json!({ "filter": { "level": "warn" }, "limit": 10 }),
+        )
+        .unwrap();
+    assert_eq!(
+        logs,
+        json!([{
+            "timestamp": "2026-03-01T10:00:00Z",
+            "level": "error",
+            "target": "frontend",
+            "message": "Uncaught TypeError",
+            "fields": { "window": "main" },
+        }])
+    );
+}
+
+#[test]
+fn update_settings_rejects_invalid_log_filters() {
+    let app = TestApp::new();
+
+    let err = app
+        .invoke(
+            "update_settings",
+            json!({ "patch": { "logging": { "modules": { "bad[": "debug" } } } }),
+        )
+        .unwrap_err();
+    assert_eq!(err["code"], "invalid_input");
+    assert!(app
+        .state::<SettingsStore>()
+        .get()
+        .logging
+        .modules
+        .is_empty());
+}
+
+#[test]
+fn notifications_can_be_scheduled_over_ipc() {
+    let app = TestApp::new();
+
+    let scheduled = app
+        .invoke(
+            "schedule_notification",
+            json!({
+                "notification": { "title": "Stand-up", "body": "In five minutes" },
+                "trigger": { "kind": "cron", "expression": "55 9 * * 1-5" },
+            }),
+        )
+        .unwrap();
+    assert_eq!(scheduled["title"], "Stand-up");
+    assert_eq!(scheduled["trigger"]["utc"], false);
+    let id = scheduled["id"].clone();
+
+    let moved = app
+        .invoke(
+            "reschedule_notification",
+            json!({ "id": id, "trigger": { "kind": "at", "at": "2030-01-01T09:00:00Z" } }),
+        )
+        .unwrap();
+    assert_eq!(moved["nextRun"], "2030-01-01T09:00:00Z");
+
+    let listed = app
+        .invoke("list_scheduled_notifications", json!({}))
+        .unwrap();
+    assert_eq!(listed, json!([moved]));
+
+    assert_eq!(
+        app.invoke("cancel_scheduled_notification", json!({ "id": id })),
+        Ok(json!(null))
+    );
+    assert_eq!(
+        app.invoke("cancel_scheduled_notification", json!({ "id": id }))
+            .unwrap_err()["code"],
+        "not_found"
+    );
+}
+
+#[test]
+fn frontend_notifications_are_recorded() {
+    let app = TestApp::with(|builder| builder.with_plugins(Plugins::NONE));
+    app.capture(NOTIFICATIONS_CHANGED);
+
+    let sent = app
+        .invoke(
+            "send_notification",
+            json!({ "notification": { "title": "Saved", "category": "documents" } }),
+        )
+        .unwrap();
+    // Without the plugin the delivery fails, but is still recorded.
+    assert_eq!(sent["status"], "failed");
+
+    let page = app
+        .invoke(
+            "list_notifications",
+            json!({ "query": { "category": "documents" } }),
+        )
+        .unwrap();
+    assert_eq!(page["total"], 1);
+    assert_eq!(page["items"][0]["title"], "Saved");
+
+    let groups = app.invoke("list_notification_groups", json!({})).unwrap();
+    assert_eq!(groups[0]["category"], "documents");
+    assert_eq!(groups[0]["count"], 1);
+
+    // Failed deliveries never count as unread.
+    assert_eq!(
+        app.invoke("mark_notifications_read", json!({})),
+        Ok(json!(0))
+    );
+    assert_eq!(
+        app.events(NOTIFICATIONS_CHANGED),
+        vec![json!({ "version": 1, "payload": { "unread": 0 } }); 2]
+    );
+}
+
+#[test]
+fn template_notifications_follow_category_preferences() {
+    let app = TestApp::with(|builder| builder.with_plugins(Plugins::NONE));
+    app.invoke("update_settings", json!({ "patch": { "locale": "es" } }))
+        .unwrap();
+
+    let args = json!({ "template": "greeting.received", "args": { "name": "Ada" } });
+    let sent = app.invoke("send_template_notification", &args).unwrap();
+    assert_eq!(sent["title"], "Nuevo saludo de Ada");
+    assert_eq!(sent["category"], "greetings");
+    assert_eq!(sent["status"], "failed");
+
+    app.invoke(
+        "update_settings",
+        json!({ "patch": { "notifications": { "categories": { "greetings": { "enabled": false } } } } }),
+    )
+    .unwrap();
+    let sent = app.invoke("send_template_notification", &args).unwrap();
+    assert_eq!(sent["status"], "disabled");
+
+    let err = app
+        .invoke(
+            "send_template_notification",
+            json!({ "template": "greeting.received" }),
+        )
+        .unwrap_err();
+    assert_eq!(err["code"], "invalid_input");
+}
+
+#[test]
+fn open_external_refuses_links_outside_the_policy() {
+    let app = TestApp::with(|builder| builder.with_plugins(Plugins::NONE));
+
+    let err = app
+        .invoke("open_external", json!({ "url": "file:///etc/passwd" }))
+        .unwrap_err();
+    assert_eq!(err["code"], "permission_denied");
+    assert_eq!(err["details"]["reason"], "scheme");
+}
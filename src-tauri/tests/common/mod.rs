@@ -0,0 +1,119 @@
+//! Test harness that drives commands through the real IPC path on Tauri's
+//! mock runtime.
+
+#![allow(dead_code)]
+
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::ipc::{CallbackFn, InvokeBody};
+use tauri::test::{mock_builder, mock_context, noop_assets, MockRuntime, INVOKE_KEY};
+use tauri::webview::InvokeRequest;
+use tauri::{App, Listener, Manager, WebviewWindow, WebviewWindowBuilder};
+use tauri_app_lib::crash::CrashReporter;
+use tauri_app_lib::db::Database;
+use tauri_app_lib::logging::Logging;
+use tauri_app_lib::process::manager::ProcessManager;
+use tauri_app_lib::process::runner::ProcessRunner;
+use tauri_app_lib::process::Allowlist;
+use tauri_app_lib::settings::{SettingsStore, SETTINGS_FILE};
+use tauri_app_lib::windows::geometry::{GeometryStore, WINDOW_STATE_FILE};
+use tauri_app_lib::AppBuilder;
+use tempfile::TempDir;
+
+pub struct TestApp {
+    pub app: App<MockRuntime>,
+    pub window: WebviewWindow<MockRuntime>,
+    events: Arc<Mutex<Vec<(String, Value)>>>,
+    // Keeps the temporary directory alive for the lifetime of the app.
+    _dir: TempDir,
+}
+
+impl TestApp {
+    /// Builds the app as `run()` does, but with settings, logs, crash
+    /// reports and window state in a temporary directory, an in-memory
+    /// database and an empty command allowlist, so nothing is read from the
+    /// real app config directory.
+    pub fn new() -> Self {
+        Self::with(|builder| builder)
+    }
+
+    /// Like [`TestApp::new`], letting the test adjust the builder first.
+    pub fn with(
+        configure: impl FnOnce(AppBuilder<MockRuntime>) -> AppBuilder<MockRuntime>,
+    ) -> Self {
+        let dir = tempfile::tempdir().unwrap();
+        let builder = AppBuilder::with_builder(mock_builder())
+            .with_state(SettingsStore::load(dir.path().join(SETTINGS_FILE)).unwrap())
+            .with_state(Database::open_in_memory().unwrap())
+            .with_state(Logging::new(dir.path().join("logs")))
+            .with_state(CrashReporter::new(dir.path().join("crashes")))
+            .with_state(GeometryStore::load(dir.path().join(WINDOW_STATE_FILE)))
+            .with_state(ProcessRunner::new(Allowlist::default()))
+            .with_state(ProcessManager::new(Allowlist::default(), Box::new(|_| {})));
+        let app = configure(builder)
+            .build(mock_context(noop_assets()))
+            .unwrap();
+        let window = WebviewWindowBuilder::new(&app, "main", Default::default())
+            .build()
+            .unwrap();
+
+        Self {
+            app,
+            window,
+            events: Default::default(),
+            _dir: dir,
+        }
+    }
+
+    pub fn state<T: Send + Sync + 'static>(&self) -> tauri::State<'_, T> {
+        self.app.state::<T>()
+    }
+
+    /// Invokes `cmd` from the main window with `args` as the JSON body and
+    /// returns the serialized response or error.
+    pub fn invoke(&self, cmd: &str, args: impl Serialize) -> Result<Value, Value> {
+        self.invoke_from(&self.window, cmd, args)
+    }
+
+    pub fn invoke_from(
+        &self,
+        window: &WebviewWindow<MockRuntime>,
+        cmd: &str,
+        args: impl Serialize,
+    ) -> Result<Value, Value> {
+        let request = InvokeRequest {
+            cmd: cmd.into(),
+            callback: CallbackFn(0),
+            error: CallbackFn(1),
+            url: "http://tauri.localhost".parse().unwrap(),
+            body: InvokeBody::Json(serde_json::to_value(args).unwrap()),
+            headers: Default::default(),
+            invoke_key: INVOKE_KEY.to_string(),
+        };
+        tauri::test::get_ipc_response(window, request)
+            .map(|body| body.deserialize::<Value>().unwrap())
+    }
+
+    /// Starts recording every `event` emitted from now on.
+    pub fn capture(&self, event: &str) {
+        let events = self.events.clone();
+        let name = event.to_string();
+        self.app.listen_any(event, move |event| {
+            let payload = serde_json::from_str(event.payload()).unwrap();
+            events.lock().unwrap().push((name.clone(), payload));
+        });
+    }
+
+    /// Payloads captured for `event`, in emission order.
+    pub fn events(&self, event: &str) -> Vec<Value> {
+        self.events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(name, _)| name == event)
+            .map(|(_, payload)| payload.clone())
+            .collect()
+    }
+}
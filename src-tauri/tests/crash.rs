@@ -0,0 +1,102 @@
+mod common;
+
+use std::panic;
+
+use common::TestApp;
+use serde_json::json;
+use tauri_app_lib::crash::{install_panic_hook, CrashReport, CrashReporter};
+use tauri_app_lib::error::ErrorCode;
+use tauri_app_lib::windows::WindowManager;
+
+#[test]
+fn pending_reports_can_be_saved_or_discarded() {
+    let dir = tempfile::tempdir().unwrap();
+    let reporter = CrashReporter::new(dir.path().join("crashes"));
+    assert!(reporter.pending().unwrap().is_empty());
+
+    let mut first = CrashReport::new("1.0.0", "first".into(), None);
+    first.id = "a".into();
+    let mut second = CrashReport::new("1.0.0", "second".into(), None);
+    second.id = "b".into();
+    reporter.write(&second).unwrap();
+    reporter.write(&first).unwrap();
+
+    let pending = reporter.pending().unwrap();
+    assert_eq!(pending, [first, second]);
+
+    let saved = reporter.save_to("a", dir.path()).unwrap();
+    assert_eq!(saved, dir.path().join("crash-a.json"));
+    let copy: CrashReport = serde_json::from_slice(&std::fs::read(saved).unwrap()).unwrap();
+    assert_eq!(copy.message, "first");
+
+    // Neither an unreadable nor a corrupt report hides the others.
+    std::fs::create_dir(dir.path().join("crashes/unreadable.json")).unwrap();
+    std::fs::write(dir.path().join("crashes/corrupt.json"), "{").unwrap();
+    assert_eq!(reporter.pending().unwrap().len(), 1);
+
+    reporter.discard("b").unwrap();
+    assert!(reporter.pending().unwrap().is_empty());
+    assert_eq!(reporter.discard("b").unwrap_err().code, ErrorCode::NotFound);
+}
+
+#[test]
+fn rejects_ids_outside_the_crash_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    let reporter = CrashReporter::new(dir.path());
+
+    for id in ["", "../settings", ".hidden", "a/b"] {
+        assert_eq!(
+            reporter.discard(id).unwrap_err().code,
+            ErrorCode::InvalidInput,
+            "{id:?}"
+        );
+    }
+}
+
+#[test]
+fn panic_hook_writes_a_report_for_the_next_launch() {
+    let app = TestApp::new();
+    // As the app does for the windows created from its config.
+    app.state::<WindowManager>().track("main", None).unwrap();
+    install_panic_hook(app.app.handle());
+    // A panic on another thread, as in an async task, does not end the app
+    // but is reported all the same.
+    let spawned = std::thread::Builder::new().name("worker".into());
+    assert!(spawned.spawn(|| panic!("caught")).unwrap().join().is_err());
+    let result = panic::catch_unwind(|| panic!("boom"));
+    let _ = panic::take_hook();
+    assert!(result.is_err());
+
+    let reports = app.invoke("get_pending_crash_reports", json!({})).unwrap();
+    assert_eq!(reports.as_array().unwrap().len(), 2);
+    let by_message = |message: &str| {
+        let reports = reports.as_array().unwrap();
+        reports
+            .iter()
+            .find(|report| report["message"] == message)
+            .unwrap()
+    };
+    let caught = by_message("caught");
+    assert_eq!(caught["thread"], "worker");
+    assert_eq!(caught["mainThread"], false);
+    let report = by_message("boom");
+    assert_eq!(report["mainThread"], true);
+    assert!(report["location"].as_str().unwrap().contains("crash.rs"));
+    assert_eq!(report["windows"], json!(["main"]));
+    assert_eq!(
+        report["appVersion"],
+        app.app.package_info().version.to_string()
+    );
+    assert!(!report["backtrace"].as_str().unwrap().is_empty());
+
+    for report in reports.as_array().unwrap() {
+        assert_eq!(
+            app.invoke("discard_crash_report", json!({ "id": report["id"] })),
+            Ok(json!(null))
+        );
+    }
+    assert_eq!(
+        app.invoke("get_pending_crash_reports", json!({})),
+        Ok(json!([]))
+    );
+}
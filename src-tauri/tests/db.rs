@@ -0,0 +1,166 @@
+use tauri_app_lib::db::contacts::{self, ContactPatch, NewContact};
+use tauri_app_lib::db::migrations::{self, Migration, MIGRATIONS};
+use tauri_app_lib::db::Database;
+use tauri_app_lib::error::ErrorCode;
+
+fn new_contact(name: &str) -> NewContact {
+    NewContact {
+        name: name.into(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn migrations_bring_schema_to_latest_version() {
+    let db = Database::open_in_memory().unwrap();
+    let conn = db.conn().unwrap();
+
+    assert_eq!(
+        migrations::schema_version(&conn).unwrap(),
+        MIGRATIONS.last().unwrap().version
+    );
+}
+
+#[test]
+fn migrations_are_applied_once_and_in_order() {
+    let mut conn = rusqlite::Connection::open_in_memory().unwrap();
+    let steps = [
+        Migration {
+            version: 1,
+            name: "create",
+            sql: "CREATE TABLE t (a INTEGER);",
+        },
+        Migration {
+            version: 2,
+            name: "alter",
+            sql: "ALTER TABLE t ADD COLUMN b TEXT;",
+        },
+    ];
+
+    assert_eq!(
+        migrations::run_migrations(&mut conn, &steps[..1]).unwrap(),
+        1
+    );
+    assert_eq!(migrations::run_migrations(&mut conn, &steps).unwrap(), 1);
+    assert_eq!(migrations::run_migrations(&mut conn, &steps).unwrap(), 0);
+    assert_eq!(migrations::schema_version(&conn).unwrap(), 2);
+    conn.execute("INSERT INTO t (a, b) VALUES (1, 'x')", [])
+        .unwrap();
+}
+
+#[test]
+fn failed_migrations_roll_back() {
+    let mut conn = rusqlite::Connection::open_in_memory().unwrap();
+    let steps = [Migration {
+        version: 1,
+        name: "broken",
+        sql: "CREATE TABLE t (a INTEGER); NOT SQL;",
+    }];
+
+    let err = migrations::run_migrations(&mut conn, &steps).unwrap_err();
+
+    assert_eq!(err.code, ErrorCode::Database);
+    assert_eq!(migrations::schema_version(&conn).unwrap(), 0);
+    assert!(conn.prepare("SELECT * FROM t").is_err());
+}
+
+#[test]
+fn refuses_databases_from_newer_versions() {
+    let mut conn = rusqlite::Connection::open_in_memory().unwrap();
+    conn.pragma_update(None, "user_version", 99).unwrap();
+
+    assert!(migrations::run(&mut conn).is_err());
+}
+
+#[test]
+fn contacts_crud_round_trip() {
+    let db = Database::open_in_memory().unwrap();
+    let conn = db.conn().unwrap();
+
+    let created = contacts::insert(
+        &conn,
+        NewContact {
+            name: " Ada Lovelace ".into(),
+            email: Some("ada@example.com".into()),
+            notes: Some("first programmer".into()),
+        },
+    )
+    .unwrap();
+    assert_eq!(created.name, "Ada Lovelace");
+    assert_eq!(contacts::get(&conn, created.id).unwrap(), created);
+
+    let updated = contacts::update(
+        &conn,
+        created.id,
+        ContactPatch {
+            email: Some(String::new()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(updated.email, None);
+    assert_eq!(updated.notes.as_deref(), Some("first programmer"));
+    assert_eq!(contacts::get(&conn, created.id).unwrap(), updated);
+
+    contacts::delete(&conn, created.id).unwrap();
+    assert_eq!(
+        contacts::get(&conn, created.id).unwrap_err().code,
+        ErrorCode::NotFound
+    );
+    assert_eq!(
+        contacts::delete(&conn, created.id).unwrap_err().code,
+        ErrorCode::NotFound
+    );
+}
+
+#[test]
+fn lists_and_filters_contacts_by_name() {
+    let db = Database::open_in_memory().unwrap();
+    let conn = db.conn().unwrap();
+    for name in ["grace", "Ada", "Alan", "100%"] {
+        contacts::insert(&conn, new_contact(name)).unwrap();
+    }
+
+    let names = |query| -> Vec<String> {
+        contacts::list(&conn, query)
+            .unwrap()
+            .into_iter()
+            .map(|contact| contact.name)
+            .collect()
+    };
+    assert_eq!(names(None), ["100%", "Ada", "Alan", "grace"]);
+    assert_eq!(names(Some("a")), ["Ada", "Alan", "grace"]);
+    assert_eq!(names(Some("%")), ["100%"]);
+}
+
+#[test]
+fn pooled_connections_share_the_in_memory_database() {
+    let db = Database::open_in_memory().unwrap();
+    let first = db.conn().unwrap();
+    let second = db.conn().unwrap();
+
+    contacts::insert(&first, new_contact("Ada")).unwrap();
+
+    assert_eq!(contacts::list(&second, None).unwrap().len(), 1);
+}
+
+#[test]
+fn rejects_invalid_contacts() {
+    let db = Database::open_in_memory().unwrap();
+    let conn = db.conn().unwrap();
+
+    let err = contacts::insert(&conn, new_contact("")).unwrap_err();
+    assert_eq!(err.code, ErrorCode::InvalidInput);
+
+    let err = contacts::insert(
+        &conn,
+        NewContact {
+            name: "Ada".into(),
+            email: Some("not-an-email".into()),
+            notes: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err.code, ErrorCode::InvalidInput);
+    assert!(contacts::list(&conn, None).unwrap().is_empty());
+}
@@ -0,0 +1,253 @@
+mod common;
+
+use chrono::Utc;
+use common::TestApp;
+use serde_json::json;
+use tauri_app_lib::db::recent_files;
+use tauri_app_lib::db::Database;
+use tauri_app_lib::documents::{Documents, Encoding};
+use tauri_app_lib::error::ErrorCode;
+
+#[test]
+fn round_trips_supported_encodings() {
+    let text = "Grüße, 世界";
+    for encoding in [
+        Encoding::Utf8,
+        Encoding::Utf8Bom,
+        Encoding::Utf16Le,
+        Encoding::Utf16Be,
+    ] {
+        let bytes = encoding.encode(text);
+        assert_eq!(
+            Encoding::decode(&bytes).unwrap(),
+            (encoding, text.to_string())
+        );
+    }
+
+    let err = Encoding::decode(&[0xC3, 0x28]).unwrap_err();
+    assert_eq!(err.code, ErrorCode::InvalidInput);
+    assert_eq!(err.details.unwrap()["reason"], "encoding");
+    let err = Encoding::decode(&[0xFF, 0xFE, 0x41]).unwrap_err();
+    assert_eq!(err.code, ErrorCode::InvalidInput);
+}
+
+#[test]
+fn concurrent_opens_of_one_file_share_a_document() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("shared.txt");
+    std::fs::write(&path, "shared").unwrap();
+    let documents = Documents::new();
+
+    let ids: Vec<u64> = std::thread::scope(|scope| {
+        let opens: Vec<_> = (0..8)
+            .map(|_| scope.spawn(|| documents.open(&path).unwrap().id))
+            .collect();
+        opens.into_iter().map(|open| open.join().unwrap()).collect()
+    });
+    assert!(ids.iter().all(|id| *id == ids[0]));
+    assert_eq!(documents.list().len(), 1);
+}
+
+#[test]
+fn tracks_edits_saves_and_reverts() {
+    let dir = tempfile::tempdir().unwrap();
+    let documents = Documents::new();
+
+    let draft = documents.create();
+    assert_eq!(draft.path, None);
+    assert!(!draft.dirty);
+    let draft = documents.edit(draft.id, "draft".into()).unwrap();
+    assert!(draft.dirty);
+    assert_eq!(documents.dirty()[0].id, draft.id);
+
+    let err = documents.save(draft.id, false).unwrap_err();
+    assert_eq!(err.details.unwrap()["reason"], "untitled");
+    let path = dir.path().join("draft.txt");
+    let saved = documents.save_as(draft.id, &path).unwrap();
+    assert!(!saved.dirty);
+    assert_eq!(saved.path, Some(path.canonicalize().unwrap()));
+    assert!(saved.modified_at.is_some());
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "draft");
+
+    // Opening a file that is already open returns the same document.
+    assert_eq!(documents.open(&path).unwrap().id, draft.id);
+    // Nor can another document take it over.
+    let other = documents.create();
+    let err = documents.save_as(other.id, &path).unwrap_err();
+    assert_eq!(err.code, ErrorCode::Conflict);
+    assert_eq!(err.details.unwrap()["reason"], "open");
+    assert_eq!(documents.save_as(draft.id, &path).unwrap().id, draft.id);
+    documents.close(other.id, false).unwrap();
+
+    documents.edit(draft.id, "edited".into()).unwrap();
+    let reverted = documents.revert(draft.id).unwrap();
+    assert_eq!(reverted.content, "draft");
+    assert!(!reverted.dirty);
+
+    documents.close(draft.id, false).unwrap();
+    assert_eq!(
+        documents.get(draft.id).unwrap_err().code,
+        ErrorCode::NotFound
+    );
+}
+
+#[test]
+fn one_file_is_one_document_however_it_is_spelled() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("sub")).unwrap();
+    let path = dir.path().join("a.txt");
+    std::fs::write(&path, "a").unwrap();
+    let documents = Documents::new();
+
+    let opened = documents.open(&path).unwrap();
+    assert_eq!(opened.path, Some(path.canonicalize().unwrap()));
+    for spelling in [
+        dir.path().join(".").join("a.txt"),
+        dir.path().join("sub").join("..").join("a.txt"),
+    ] {
+        assert_eq!(documents.open(&spelling).unwrap().id, opened.id);
+    }
+    #[cfg(unix)]
+    {
+        let link = dir.path().join("link.txt");
+        std::os::unix::fs::symlink(&path, &link).unwrap();
+        assert_eq!(documents.open(&link).unwrap().id, opened.id);
+    }
+
+    // Nor can it be saved over under another spelling.
+    let other = documents.create();
+    let err = documents
+        .save_as(other.id, &dir.path().join("sub/../a.txt"))
+        .unwrap_err();
+    assert_eq!(err.code, ErrorCode::Conflict);
+    // A new file, even in a directory that does not exist yet, is stored
+    // under its resolved path too.
+    let new = dir.path().join("sub/../new/b.txt");
+    let saved = documents.save_as(other.id, &new).unwrap();
+    assert_eq!(
+        saved.path,
+        Some(dir.path().canonicalize().unwrap().join("new/b.txt"))
+    );
+    assert_eq!(documents.open(&new).unwrap().id, other.id);
+}
+
+#[test]
+fn keeps_the_encoding_of_opened_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("notes.txt");
+    std::fs::write(&path, Encoding::Utf16Le.encode("hello")).unwrap();
+
+    let documents = Documents::new();
+    let opened = documents.open(&path).unwrap();
+    assert_eq!(opened.encoding, Encoding::Utf16Le);
+    assert_eq!(opened.content, "hello");
+
+    documents.edit(opened.id, "goodbye".into()).unwrap();
+    documents.save(opened.id, false).unwrap();
+    assert_eq!(
+        std::fs::read(&path).unwrap(),
+        Encoding::Utf16Le.encode("goodbye")
+    );
+}
+
+#[test]
+fn refuses_to_overwrite_external_changes() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("shared.txt");
+    std::fs::write(&path, "original").unwrap();
+
+    let documents = Documents::new();
+    let opened = documents.open(&path).unwrap();
+    assert!(!documents.modified_externally(opened.id).unwrap());
+
+    std::fs::write(&path, "changed elsewhere").unwrap();
+    assert!(documents.modified_externally(opened.id).unwrap());
+    documents.edit(opened.id, "mine".into()).unwrap();
+    let err = documents.save(opened.id, false).unwrap_err();
+    assert_eq!(err.code, ErrorCode::Conflict);
+    assert_eq!(err.details.unwrap()["reason"], "modified");
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "changed elsewhere");
+
+    // A dirty document is only closed when its changes may be discarded.
+    let err = documents.close(opened.id, false).unwrap_err();
+    assert_eq!(err.details.unwrap()["reason"], "dirty");
+
+    let saved = documents.save(opened.id, true).unwrap();
+    assert!(!saved.dirty);
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "mine");
+    assert!(!documents.modified_externally(opened.id).unwrap());
+
+    std::fs::remove_file(&path).unwrap();
+    assert!(documents.modified_externally(opened.id).unwrap());
+}
+
+#[test]
+fn opens_only_recent_files_without_a_dialog() {
+    let app = TestApp::new();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("recent.md");
+    std::fs::write(&path, "# Recent").unwrap();
+
+    let err = app
+        .invoke("open_document", json!({ "path": path }))
+        .unwrap_err();
+    assert_eq!(err["code"], "permission_denied");
+    assert_eq!(err["details"]["reason"], "not_recent");
+
+    let conn = app.state::<Database>().conn().unwrap();
+    recent_files::touch(&conn, &path, Utc::now()).unwrap();
+    let opened = app
+        .invoke("open_document", json!({ "path": path }))
+        .unwrap();
+    assert_eq!(opened["content"], "# Recent");
+    assert_eq!(opened["encoding"], "utf-8");
+    assert_eq!(opened["dirty"], false);
+
+    let id = opened["id"].clone();
+    let edited = app
+        .invoke("edit_document", json!({ "id": id, "content": "# Edited" }))
+        .unwrap();
+    assert_eq!(edited["dirty"], true);
+    let saved = app.invoke("save_document", json!({ "id": id })).unwrap();
+    assert_eq!(saved["dirty"], false);
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "# Edited");
+
+    let listed = app.invoke("list_documents", json!({})).unwrap();
+    assert_eq!(listed.as_array().unwrap().len(), 1);
+    app.invoke("close_document", json!({ "id": id })).unwrap();
+    assert_eq!(app.invoke("list_documents", json!({})), Ok(json!([])));
+}
+
+#[cfg(unix)]
+#[test]
+fn saving_keeps_file_modes_and_symlinks() {
+    use std::os::unix::fs::{symlink, PermissionsExt};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("shared.txt");
+    std::fs::write(&path, "original").unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+    let link = dir.path().join("link.txt");
+    symlink(&path, &link).unwrap();
+
+    let documents = Documents::new();
+    let opened = documents.open(&path).unwrap();
+    documents.edit(opened.id, "direct".into()).unwrap();
+    documents.save(opened.id, false).unwrap();
+    let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o644);
+    documents.close(opened.id, false).unwrap();
+
+    let linked = documents.open(&link).unwrap();
+    documents
+        .edit(linked.id, "through the link".into())
+        .unwrap();
+    documents.save(linked.id, false).unwrap();
+    assert!(std::fs::symlink_metadata(&link)
+        .unwrap()
+        .file_type()
+        .is_symlink());
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "through the link");
+    let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o644);
+}
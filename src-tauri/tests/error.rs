@@ -0,0 +1,51 @@
+use serde_json::json;
+use tauri_app_lib::error::{AppError, ErrorCode};
+
+#[test]
+fn serializes_code_and_message() {
+    let err = AppError::new(ErrorCode::Internal, "something broke");
+
+    assert_eq!(
+        serde_json::to_value(&err).unwrap(),
+        json!({ "code": "internal", "message": "something broke" })
+    );
+}
+
+#[test]
+fn serializes_details_when_present() {
+    let err = AppError::internal("bad").with_details(json!({ "field": "name" }));
+
+    assert_eq!(
+        serde_json::to_value(&err).unwrap(),
+        json!({ "code": "internal", "message": "bad", "details": { "field": "name" } })
+    );
+}
+
+#[test]
+fn converts_io_errors() {
+    let io = std::io::Error::new(std::io::ErrorKind::NotFound, "missing file");
+    let err = AppError::from(io);
+
+    assert_eq!(
+        serde_json::to_value(&err).unwrap(),
+        json!({ "code": "io", "message": "missing file", "details": { "kind": "NotFound" } })
+    );
+}
+
+#[test]
+fn converts_json_errors() {
+    let parse = serde_json::from_str::<serde_json::Value>("{").unwrap_err();
+    let value = serde_json::to_value(AppError::from(parse)).unwrap();
+
+    assert_eq!(value["code"], "json");
+    assert_eq!(value["details"], json!({ "line": 1, "column": 1 }));
+}
+
+#[test]
+fn converts_plugin_errors() {
+    let shell = tauri_plugin_shell::Error::UnknownProgramName("foo".into());
+    assert_eq!(AppError::from(shell).code, ErrorCode::Shell);
+
+    let dialog = tauri_plugin_dialog::Error::Io(std::io::Error::other("denied"));
+    assert_eq!(AppError::from(dialog).code, ErrorCode::Dialog);
+}
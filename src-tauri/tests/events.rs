@@ -0,0 +1,115 @@
+mod common;
+
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use chrono::Utc;
+use common::TestApp;
+use serde_json::json;
+use tauri::{Listener, Manager};
+use tauri_app_lib::events::{
+    self, AppEvent, DOCUMENTS_CHANGED, EVENTS, NOTIFICATIONS_CHANGED, TYPESCRIPT_FILE,
+};
+use tauri_app_lib::process::manager::{ProcessInfo, ProcessState};
+use tauri_app_lib::settings::Settings;
+use tauri_app_lib::windows::{WindowManager, WindowRole};
+
+fn every_event() -> Vec<AppEvent> {
+    vec![
+        AppEvent::SettingsChanged(Settings::default()),
+        AppEvent::NotificationsChanged { unread: 3 },
+        AppEvent::RecentFilesChanged,
+        AppEvent::DocumentsChanged,
+        AppEvent::ProcessStatus(ProcessInfo {
+            name: "worker".into(),
+            command: "echo".into(),
+            args: vec![],
+            pid: Some(42),
+            state: ProcessState::Running,
+            exit_code: None,
+            started_at: Utc::now(),
+            exited_at: None,
+            uptime_secs: 0,
+            restarts: 0,
+        }),
+    ]
+}
+
+#[test]
+fn every_event_has_a_name_and_version() {
+    let events = every_event();
+    let names: BTreeSet<_> = events.iter().map(AppEvent::name).collect();
+    assert_eq!(names.len(), events.len());
+    assert_eq!(
+        names,
+        EVENTS
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<BTreeSet<_>>()
+    );
+
+    for event in events {
+        // The name is the tag the TypeScript union is keyed on.
+        assert_eq!(serde_json::to_value(&event).unwrap()["event"], event.name());
+        assert_eq!(event.envelope().unwrap().version, 1);
+    }
+}
+
+#[test]
+fn emits_versioned_envelopes_to_every_listener() {
+    let app = TestApp::new();
+    let handle = app.app.handle();
+    app.capture(NOTIFICATIONS_CHANGED);
+    app.capture(DOCUMENTS_CHANGED);
+
+    AppEvent::NotificationsChanged { unread: 3 }
+        .emit(handle)
+        .unwrap();
+    AppEvent::DocumentsChanged.emit(handle).unwrap();
+
+    assert_eq!(
+        app.events(NOTIFICATIONS_CHANGED),
+        [json!({ "version": 1, "payload": { "unread": 3 } })]
+    );
+    assert_eq!(
+        app.events(DOCUMENTS_CHANGED),
+        [json!({ "version": 1, "payload": null })]
+    );
+}
+
+#[test]
+fn emits_to_a_single_window_by_label() {
+    let app = TestApp::new();
+    let handle = app.app.handle();
+    app.state::<WindowManager>()
+        .open(handle, WindowRole::Settings, None)
+        .unwrap();
+    app.capture(DOCUMENTS_CHANGED);
+
+    let received = Arc::new(Mutex::new(Vec::new()));
+    for (label, window) in app.app.webview_windows() {
+        let received = received.clone();
+        window.listen(DOCUMENTS_CHANGED, move |_| {
+            received.lock().unwrap().push(label.clone());
+        });
+    }
+
+    AppEvent::DocumentsChanged
+        .emit_to(handle, "settings")
+        .unwrap();
+    assert_eq!(*received.lock().unwrap(), ["settings"]);
+    // Rust listeners for any target still see it.
+    assert_eq!(app.events(DOCUMENTS_CHANGED).len(), 1);
+}
+
+#[test]
+fn checked_in_typescript_is_current() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(TYPESCRIPT_FILE);
+    let checked_in = std::fs::read_to_string(&path).unwrap_or_default();
+    assert!(
+        checked_in == events::typescript().unwrap(),
+        "{} is stale; run `cargo run --bin export_types`",
+        path.display()
+    );
+}
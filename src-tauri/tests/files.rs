@@ -0,0 +1,108 @@
+mod common;
+
+use chrono::{Duration, TimeZone, Utc};
+use common::TestApp;
+use serde_json::json;
+use tauri_app_lib::db::recent_files::{self, MAX_RECENT_FILES};
+use tauri_app_lib::db::Database;
+use tauri_app_lib::error::ErrorCode;
+use tauri_app_lib::files::{self, FileFilter};
+use tauri_plugin_fs::FsExt;
+
+fn filter(name: &str, extensions: &[&str]) -> FileFilter {
+    FileFilter {
+        name: name.into(),
+        extensions: extensions.iter().map(|ext| ext.to_string()).collect(),
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn recent_files_must_be_valid_utf8() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let db = Database::open_in_memory().unwrap();
+    let conn = db.conn().unwrap();
+
+    let path = dir.path().join(OsStr::from_bytes(b"caf\xe9.txt"));
+    let err = recent_files::touch(&conn, &path, Utc::now()).unwrap_err();
+    assert_eq!(err.code, ErrorCode::InvalidInput);
+    assert_eq!(err.details.unwrap()["reason"], "encoding");
+    assert!(recent_files::list(&conn).unwrap().is_empty());
+}
+
+#[test]
+fn validates_file_filters() {
+    assert!(filter("Text", &["txt", "md"]).validated().is_ok());
+    assert!(filter("All files", &["*"]).validated().is_ok());
+
+    for bad in [
+        filter("", &["txt"]),
+        filter("Text", &[]),
+        filter("Text", &[".txt"]),
+        filter("Text", &["t/xt"]),
+    ] {
+        assert_eq!(bad.validated().unwrap_err().code, ErrorCode::InvalidInput);
+    }
+}
+
+#[test]
+fn recent_files_are_deduplicated_capped_and_pruned() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = Database::open_in_memory().unwrap();
+    let conn = db.conn().unwrap();
+    let start = Utc.with_ymd_and_hms(2026, 3, 2, 8, 0, 0).unwrap();
+
+    let paths: Vec<_> = (0..MAX_RECENT_FILES + 2)
+        .map(|n| {
+            let path = dir.path().join(format!("{n}.txt"));
+            std::fs::write(&path, "").unwrap();
+            path
+        })
+        .collect();
+    for (n, path) in paths.iter().enumerate() {
+        recent_files::touch(&conn, path, start + Duration::minutes(n as i64)).unwrap();
+    }
+    // Opening a file again moves it to the top instead of adding it twice.
+    recent_files::touch(&conn, &paths[5], start + Duration::hours(1)).unwrap();
+
+    let listed = recent_files::list(&conn).unwrap();
+    assert_eq!(listed.len(), MAX_RECENT_FILES as usize);
+    assert_eq!(listed[0].path, paths[5]);
+    assert_eq!(listed[1].path, *paths.last().unwrap());
+    assert!(!listed.iter().any(|file| file.path == paths[0]));
+
+    std::fs::remove_file(&paths[5]).unwrap();
+    let listed = recent_files::list(&conn).unwrap();
+    assert_eq!(listed.len(), MAX_RECENT_FILES as usize - 1);
+    assert!(!listed.iter().any(|file| file.path == paths[5]));
+
+    assert_eq!(
+        recent_files::clear(&conn).unwrap(),
+        MAX_RECENT_FILES as usize - 1
+    );
+    assert!(recent_files::list(&conn).unwrap().is_empty());
+}
+
+#[test]
+fn picked_files_are_in_scope_and_listed_over_ipc() {
+    let app = TestApp::new();
+    let dir = tempfile::tempdir().unwrap();
+    let picked = dir.path().join("notes.md");
+    let other = dir.path().join("secret.md");
+    std::fs::write(&picked, "# Notes").unwrap();
+    std::fs::write(&other, "").unwrap();
+
+    let scope = app.app.fs_scope();
+    assert!(!scope.is_allowed(&picked));
+    files::remember(app.app.handle(), &picked).unwrap();
+    assert!(scope.is_allowed(&picked));
+    assert!(!scope.is_allowed(&other));
+
+    let recent = app.invoke("list_recent_files", json!({})).unwrap();
+    assert_eq!(recent[0]["path"], picked.to_str().unwrap());
+    assert_eq!(app.invoke("clear_recent_files", json!({})), Ok(json!(1)));
+    assert_eq!(app.invoke("list_recent_files", json!({})), Ok(json!([])));
+}
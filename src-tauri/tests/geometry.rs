@@ -0,0 +1,217 @@
+use tauri_app_lib::windows::geometry::{
+    center, fit, key, restore, GeometryStore, MonitorArea, Rect, WindowGeometry, DOCUMENT_KEY,
+    MIN_SIZE, WINDOW_STATE_FILE,
+};
+
+fn rect(x: i32, y: i32, width: u32, height: u32) -> Rect {
+    Rect {
+        x,
+        y,
+        width,
+        height,
+    }
+}
+
+fn monitor(name: &str, work_area: Rect) -> MonitorArea {
+    MonitorArea {
+        name: Some(name.into()),
+        work_area,
+    }
+}
+
+fn saved(rect: Rect, monitor: Option<&str>) -> WindowGeometry {
+    WindowGeometry {
+        rect,
+        maximized: false,
+        fullscreen: false,
+        monitor: monitor.map(Into::into),
+    }
+}
+
+/// A laptop screen with a larger monitor to its right.
+fn monitors() -> Vec<MonitorArea> {
+    vec![
+        monitor("laptop", rect(0, 0, 1440, 900)),
+        monitor("external", rect(1440, -200, 2560, 1400)),
+    ]
+}
+
+#[test]
+fn measures_overlap() {
+    let area = rect(0, 0, 1000, 800);
+    assert_eq!(rect(100, 100, 200, 100).overlap(&area), (200, 100));
+    assert_eq!(rect(900, 700, 300, 300).overlap(&area), (100, 100));
+    assert_eq!(rect(-500, 0, 400, 300).overlap(&area), (0, 300));
+    assert_eq!(rect(2000, 2000, 10, 10).overlap(&area), (0, 0));
+}
+
+#[test]
+fn fits_windows_inside_an_area() {
+    let area = rect(0, 0, 1440, 900);
+
+    // Already inside: untouched.
+    assert_eq!(fit(rect(100, 50, 800, 600), &area), rect(100, 50, 800, 600));
+    // Hanging off the bottom right: moved in.
+    assert_eq!(
+        fit(rect(1000, 700, 800, 600), &area),
+        rect(640, 300, 800, 600)
+    );
+    // Too large: shrunk to the area.
+    assert_eq!(fit(rect(-50, -50, 3000, 2000), &area), area);
+    // Too small: grown to the minimum size.
+    assert_eq!(
+        fit(rect(10, 10, 0, 0), &area),
+        rect(10, 10, MIN_SIZE.0, MIN_SIZE.1)
+    );
+    assert_eq!(
+        center(rect(5000, 5000, 400, 300), &area),
+        rect(520, 300, 400, 300)
+    );
+}
+
+#[test]
+fn restores_windows_to_their_monitor() {
+    let on_external = saved(rect(2000, 0, 1200, 800), Some("external"));
+    assert_eq!(restore(&on_external, &monitors()), on_external);
+
+    // The external monitor became smaller.
+    let smaller = [
+        monitor("laptop", rect(0, 0, 1440, 900)),
+        monitor("external", rect(1440, 0, 1920, 1080)),
+    ];
+    assert_eq!(
+        restore(&on_external, &smaller).rect,
+        rect(2000, 0, 1200, 800)
+    );
+    let wide = saved(rect(2000, 0, 2400, 800), Some("external"));
+    assert_eq!(restore(&wide, &smaller).rect, rect(1440, 0, 1920, 800));
+
+    // Maximized and fullscreen state is kept.
+    let maximized = WindowGeometry {
+        maximized: true,
+        ..on_external.clone()
+    };
+    assert!(restore(&maximized, &monitors()).maximized);
+}
+
+#[test]
+fn moves_windows_back_on_screen_when_their_monitor_is_gone() {
+    let laptop_only = [monitor("laptop", rect(0, 0, 1440, 900))];
+
+    // Entirely on the unplugged monitor: centred on the primary one.
+    let on_external = saved(rect(2000, 0, 1200, 800), Some("external"));
+    let restored = restore(&on_external, &laptop_only);
+    assert_eq!(restored.rect, rect(120, 50, 1200, 800));
+    assert_eq!(restored.monitor.as_deref(), Some("laptop"));
+
+    // Mostly on the laptop: stays there, pulled fully on-screen.
+    let straddling = saved(rect(1000, 100, 800, 600), Some("external"));
+    let restored = restore(&straddling, &laptop_only);
+    assert_eq!(restored.rect, rect(640, 100, 800, 600));
+    assert_eq!(restored.monitor.as_deref(), Some("laptop"));
+
+    // Barely visible counts as off-screen.
+    let sliver = saved(rect(1400, 100, 800, 600), None);
+    assert_eq!(
+        restore(&sliver, &laptop_only).rect,
+        rect(320, 150, 800, 600)
+    );
+
+    // Unknown monitors: only the size is checked.
+    let tiny = saved(rect(-9000, 0, 10, 10), None);
+    assert_eq!(
+        restore(&tiny, &[]).rect,
+        rect(-9000, 0, MIN_SIZE.0, MIN_SIZE.1)
+    );
+}
+
+#[test]
+fn persists_geometry_per_window() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(WINDOW_STATE_FILE);
+
+    let store = GeometryStore::load(&path);
+    assert_eq!(store.get("main"), None);
+    let main = saved(rect(10, 20, 800, 600), Some("laptop"));
+    let settings = WindowGeometry {
+        fullscreen: true,
+        ..saved(rect(30, 40, 640, 480), None)
+    };
+    store.set("main", main.clone());
+    store.set("settings", settings.clone());
+    // Document labels are numbered per session, so they share one entry:
+    // the next document window opens where the last one was.
+    let document = saved(rect(50, 60, 900, 700), None);
+    store.set("document-1", document.clone());
+    assert_eq!(store.get("document-2"), Some(document.clone()));
+    store.set("unknown", saved(rect(0, 0, 300, 200), None));
+    assert_eq!(store.get("unknown"), None);
+    store.save().unwrap();
+
+    let reloaded = GeometryStore::load(&path);
+    assert_eq!(reloaded.get("main"), Some(main.clone()));
+    assert_eq!(reloaded.get("settings"), Some(settings));
+    assert_eq!(reloaded.get("document-7"), Some(document.clone()));
+
+    let mut file: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+    assert_eq!(file.as_object().unwrap().len(), 3);
+    assert!(file.get(DOCUMENT_KEY).is_some());
+
+    // Entries saved per document label by older versions are kept under
+    // the shared key, unless it already has one.
+    file["document-3"] = file["main"].clone();
+    std::fs::write(&path, file.to_string()).unwrap();
+    assert_eq!(GeometryStore::load(&path).get("document-1"), Some(document));
+    file.as_object_mut().unwrap().remove(DOCUMENT_KEY);
+    std::fs::write(&path, file.to_string()).unwrap();
+    let migrated = GeometryStore::load(&path);
+    assert_eq!(migrated.get("document-1"), Some(main));
+    migrated.save().unwrap();
+    let file: serde_json::Value = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+    assert!(file.get("document-3").is_none());
+    assert!(file.get(DOCUMENT_KEY).is_some());
+
+    std::fs::write(&path, "not json").unwrap();
+    assert_eq!(GeometryStore::load(&path).get("main"), None);
+}
+
+#[test]
+fn keys_geometry_by_window_role() {
+    assert_eq!(key("main"), Some("main"));
+    assert_eq!(key("about"), Some("about"));
+    assert_eq!(key("document-12"), Some(DOCUMENT_KEY));
+    assert_eq!(key("document-"), None);
+    assert_eq!(key("stray"), None);
+}
+
+#[test]
+fn concurrent_saves_leave_a_whole_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(WINDOW_STATE_FILE);
+    let store = GeometryStore::load(&path);
+
+    let labels = ["main", "settings", "about"];
+
+    std::thread::scope(|scope| {
+        for n in 0..8 {
+            let store = &store;
+            scope.spawn(move || {
+                for i in 0..20 {
+                    store.set(labels[n % labels.len()], saved(rect(i, i, 800, 600), None));
+                    store.save().unwrap();
+                }
+            });
+        }
+    });
+
+    let reloaded = GeometryStore::load(&path);
+    for label in labels {
+        assert_eq!(
+            reloaded.get(label),
+            Some(saved(rect(19, 19, 800, 600), None))
+        );
+    }
+    // No temp files are left behind.
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+}
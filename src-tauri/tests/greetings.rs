@@ -0,0 +1,187 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use tauri_app_lib::db::greetings::{self, GreetingQuery, NewGreeting};
+use tauri_app_lib::db::{Connection, Database};
+use tauri_app_lib::error::ErrorCode;
+use tauri_app_lib::settings::HistorySettings;
+
+fn start() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap()
+}
+
+fn keep_all() -> HistorySettings {
+    HistorySettings {
+        max_entries: u32::MAX,
+        max_age_days: None,
+    }
+}
+
+fn seed(conn: &Connection, entries: &[(&str, &str, &str)]) {
+    for (minute, (name, locale, window)) in entries.iter().enumerate() {
+        greetings::record(
+            conn,
+            NewGreeting {
+                name,
+                locale,
+                window,
+            },
+            start() + Duration::minutes(minute as i64),
+            &keep_all(),
+        )
+        .unwrap();
+    }
+}
+
+fn names(page: &[greetings::Greeting]) -> Vec<&str> {
+    page.iter().map(|greeting| greeting.name.as_str()).collect()
+}
+
+#[test]
+fn lists_newest_first_with_pagination() {
+    let db = Database::open_in_memory().unwrap();
+    let conn = db.conn().unwrap();
+    seed(
+        &conn,
+        &[
+            ("Ada", "en-US", "main"),
+            ("Alan", "en-US", "main"),
+            ("Grace", "fr", "settings"),
+        ],
+    );
+
+    let page = greetings::list(
+        &conn,
+        &GreetingQuery {
+            limit: Some(2),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(names(&page.items), ["Grace", "Alan"]);
+    assert_eq!(page.total, 3);
+
+    let page = greetings::list(
+        &conn,
+        &GreetingQuery {
+            limit: Some(2),
+            offset: 2,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(names(&page.items), ["Ada"]);
+}
+
+#[test]
+fn filters_by_name_locale_window_and_time() {
+    let db = Database::open_in_memory().unwrap();
+    let conn = db.conn().unwrap();
+    seed(
+        &conn,
+        &[
+            ("Ada", "en-US", "main"),
+            ("Alan", "fr", "main"),
+            ("Grace", "fr", "settings"),
+        ],
+    );
+
+    let query = |query: GreetingQuery| greetings::list(&conn, &query).unwrap();
+
+    let page = query(GreetingQuery {
+        name: Some("a".into()),
+        ..Default::default()
+    });
+    assert_eq!(names(&page.items), ["Alan", "Ada"]);
+
+    let page = query(GreetingQuery {
+        locale: Some("fr".into()),
+        window: Some("main".into()),
+        ..Default::default()
+    });
+    assert_eq!(names(&page.items), ["Alan"]);
+    assert_eq!(page.total, 1);
+
+    let page = query(GreetingQuery {
+        since: Some(start() + Duration::minutes(1)),
+        until: Some(start() + Duration::minutes(2)),
+        ..Default::default()
+    });
+    assert_eq!(names(&page.items), ["Alan"]);
+}
+
+#[test]
+fn searches_by_literal_name_prefix() {
+    let db = Database::open_in_memory().unwrap();
+    let conn = db.conn().unwrap();
+    seed(
+        &conn,
+        &[
+            ("Ada", "en-US", "main"),
+            ("adam", "en-US", "main"),
+            ("Nada", "en-US", "main"),
+            ("A_b", "en-US", "main"),
+        ],
+    );
+
+    let found = greetings::search(&conn, "ad", None).unwrap();
+    assert_eq!(names(&found), ["adam", "Ada"]);
+
+    let found = greetings::search(&conn, "a_", None).unwrap();
+    assert_eq!(names(&found), ["A_b"]);
+}
+
+#[test]
+fn caps_history_by_count_and_age() {
+    let db = Database::open_in_memory().unwrap();
+    let conn = db.conn().unwrap();
+    let retention = HistorySettings {
+        max_entries: 2,
+        max_age_days: Some(7),
+    };
+
+    for (days, name) in [(0, "old"), (10, "a"), (11, "b"), (12, "c")] {
+        greetings::record(
+            &conn,
+            NewGreeting {
+                name,
+                locale: "en-US",
+                window: "main",
+            },
+            start() + Duration::days(days),
+            &retention,
+        )
+        .unwrap();
+    }
+    let page = greetings::list(&conn, &GreetingQuery::default()).unwrap();
+    assert_eq!(names(&page.items), ["c", "b"]);
+
+    let removed = greetings::prune(&conn, &retention, start() + Duration::days(19)).unwrap();
+    assert_eq!(removed, 1);
+}
+
+#[test]
+fn deletes_and_clears_history() {
+    let db = Database::open_in_memory().unwrap();
+    let conn = db.conn().unwrap();
+    seed(
+        &conn,
+        &[("Ada", "en-US", "main"), ("Alan", "en-US", "main")],
+    );
+
+    let first = greetings::list(&conn, &GreetingQuery::default())
+        .unwrap()
+        .items[0]
+        .id;
+    greetings::delete(&conn, first).unwrap();
+    assert_eq!(
+        greetings::delete(&conn, first).unwrap_err().code,
+        ErrorCode::NotFound
+    );
+
+    assert_eq!(greetings::clear(&conn).unwrap(), 1);
+    assert_eq!(
+        greetings::list(&conn, &GreetingQuery::default())
+            .unwrap()
+            .total,
+        0
+    );
+}
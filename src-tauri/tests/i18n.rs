@@ -0,0 +1,115 @@
+use fluent_bundle::FluentArgs;
+use tauri_app_lib::error::ErrorCode;
+use tauri_app_lib::i18n::{I18n, EMBEDDED_RESOURCES};
+
+fn name_args(name: &str) -> FluentArgs<'_> {
+    let mut args = FluentArgs::new();
+    args.set("name", name);
+    args
+}
+
+#[test]
+fn embedded_catalogues_define_the_same_messages() {
+    let i18n = I18n::new(EMBEDDED_RESOURCES, None);
+    for locale in i18n.available_locales() {
+        let locale = locale.to_string();
+        for key in [
+            "greeting",
+            "greeting-count",
+            "greeting-received",
+            "notification-greeting-received-title",
+        ] {
+            assert!(
+                i18n.translate(Some(&locale), key, None).is_ok(),
+                "`{key}` missing for {locale}"
+            );
+        }
+    }
+}
+
+#[test]
+fn uses_requested_locale() {
+    let i18n = I18n::new(EMBEDDED_RESOURCES, Some("en-US"));
+    let text = i18n.translate(Some("es"), "greeting", Some(&name_args("Ada")));
+    assert_eq!(text.unwrap(), "¡Hola, Ada! Te han saludado desde Rust.");
+}
+
+#[test]
+fn falls_back_from_region_to_language() {
+    let i18n = I18n::new(EMBEDDED_RESOURCES, None);
+    let text = i18n.translate(Some("fr-CA"), "greeting", Some(&name_args("Ada")));
+    assert_eq!(text.unwrap(), "Bonjour, Ada ! Rust vous salue.");
+}
+
+#[test]
+fn falls_back_through_preferred_system_and_default() {
+    let i18n = I18n::new(EMBEDDED_RESOURCES, Some("de-AT"));
+    assert_eq!(i18n.current_locale().to_string(), "de");
+
+    i18n.set_preferred_locale(Some("es-MX")).unwrap();
+    assert_eq!(i18n.current_locale().to_string(), "es");
+    let chain: Vec<String> = i18n
+        .fallback_chain(Some(&"fr".parse().unwrap()))
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(chain, ["fr", "es", "de", "en-US"]);
+
+    i18n.set_preferred_locale(None).unwrap();
+    assert_eq!(i18n.current_locale().to_string(), "de");
+
+    let unsupported = I18n::new(EMBEDDED_RESOURCES, Some("ja-JP"));
+    assert_eq!(unsupported.current_locale().to_string(), "en-US");
+}
+
+#[test]
+fn falls_back_per_message() {
+    let i18n = I18n::new(
+        &[
+            ("en-US", "hello = Hello\nbye = Goodbye"),
+            ("es", "hello = Hola"),
+        ],
+        None,
+    );
+
+    assert_eq!(i18n.translate(Some("es"), "hello", None).unwrap(), "Hola");
+    assert_eq!(i18n.translate(Some("es"), "bye", None).unwrap(), "Goodbye");
+}
+
+#[test]
+fn selects_plural_and_gender_variants() {
+    let i18n = I18n::new(EMBEDDED_RESOURCES, None);
+
+    let mut args = FluentArgs::new();
+    args.set("count", 1);
+    assert_eq!(
+        i18n.translate(None, "greeting-count", Some(&args)).unwrap(),
+        "You have greeted one person."
+    );
+    args.set("count", 3);
+    assert_eq!(
+        i18n.translate(None, "greeting-count", Some(&args)).unwrap(),
+        "You have greeted 3 people."
+    );
+
+    let mut args = name_args("Ada");
+    args.set("gender", "female");
+    assert_eq!(
+        i18n.translate(Some("de"), "greeting-received", Some(&args))
+            .unwrap(),
+        "Ada hat dich begrüßt. Sag ihr hallo!"
+    );
+}
+
+#[test]
+fn reports_unknown_keys_and_locales() {
+    let i18n = I18n::new(EMBEDDED_RESOURCES, None);
+
+    let err = i18n.translate(None, "no-such-message", None).unwrap_err();
+    assert_eq!(err.code, ErrorCode::NotFound);
+
+    let err = i18n
+        .translate(Some("not a locale!"), "greeting", None)
+        .unwrap_err();
+    assert_eq!(err.code, ErrorCode::InvalidInput);
+}
@@ -0,0 +1,101 @@
+use tauri_app_lib::error::ErrorCode;
+use tauri_app_lib::links::{LinkPolicy, LINKS_FILE};
+
+fn reason(policy: &LinkPolicy, url: &str) -> serde_json::Value {
+    let err = policy.check(url).unwrap_err();
+    assert_eq!(err.code, ErrorCode::PermissionDenied, "{url}");
+    err.details.unwrap()["reason"].clone()
+}
+
+#[test]
+fn allows_only_https_by_default() {
+    let policy = LinkPolicy::default();
+    let link = policy.check("https://tauri.app/start/").unwrap();
+    assert_eq!(link.url.as_str(), "https://tauri.app/start/");
+    assert!(!link.trusted);
+
+    for url in [
+        "http://tauri.app",
+        "file:///etc/passwd",
+        "javascript:alert(1)",
+        "mailto:ada@example.com",
+        "smb://server/share",
+    ] {
+        assert_eq!(reason(&policy, url), "scheme");
+    }
+    let err = policy.check("not a url").unwrap_err();
+    assert_eq!(err.code, ErrorCode::InvalidInput);
+
+    let policy = policy.allow_mailto(true);
+    assert!(policy.check("mailto:ada@example.com").unwrap().trusted);
+}
+
+#[test]
+fn loads_the_policy_from_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(LINKS_FILE);
+    assert_eq!(LinkPolicy::load(&path).unwrap(), LinkPolicy::default());
+
+    std::fs::write(
+        &path,
+        r#"{ "mailto": true, "hosts": ["Docs.rs", "*.tauri.app"], "confirmUnknown": false }"#,
+    )
+    .unwrap();
+    let policy = LinkPolicy::load(&path).unwrap();
+    assert!(policy.check("mailto:ada@example.com").unwrap().trusted);
+    assert!(policy.check("https://docs.rs/tauri").unwrap().trusted);
+    assert!(policy.check("https://v2.tauri.app/").unwrap().trusted);
+    assert_eq!(reason(&policy, "https://example.com/"), "host");
+    assert_eq!(reason(&policy, "http://docs.rs/"), "scheme");
+    // Unset fields keep their defaults.
+    let link = policy.check("https://docs.rs/?utm_source=app").unwrap();
+    assert_eq!(link.url.as_str(), "https://docs.rs/");
+
+    std::fs::write(&path, r#"{ "hosts": "docs.rs" }"#).unwrap();
+    assert_eq!(LinkPolicy::load(&path).unwrap_err().code, ErrorCode::Json);
+}
+
+#[test]
+fn trusts_allowed_hosts_and_their_subdomains() {
+    let policy = LinkPolicy::default()
+        .allow_host("tauri.app")
+        .allow_host("*.Example.com");
+    let trusted = |url: &str| policy.check(url).unwrap().trusted;
+
+    assert!(trusted("https://tauri.app/"));
+    assert!(trusted("https://TAURI.app/"));
+    assert!(trusted("https://docs.example.com/"));
+    assert!(trusted("https://a.b.example.com/"));
+    assert!(!trusted("https://example.com/"));
+    assert!(!trusted("https://evilexample.com/"));
+    assert!(!trusted("https://docs.tauri.app/"));
+    assert!(!trusted("https://tauri.app@evil.test/"));
+
+    let strict = policy.clone().confirm_unknown(false);
+    assert!(strict.check("https://tauri.app/").is_ok());
+    assert_eq!(reason(&strict, "https://evil.test/"), "host");
+}
+
+#[test]
+fn strips_tracking_parameters() {
+    let policy = LinkPolicy::default();
+    let clean = |url: &str| policy.check(url).unwrap().url.to_string();
+
+    assert_eq!(
+        clean("https://tauri.app/blog?utm_source=x&utm_medium=y&fbclid=z"),
+        "https://tauri.app/blog"
+    );
+    assert_eq!(
+        clean("https://tauri.app/search?q=rust+gui&gclid=1&page=2#top"),
+        "https://tauri.app/search?q=rust+gui&page=2#top"
+    );
+
+    let keep = LinkPolicy::default().strip_tracking(false);
+    assert_eq!(
+        keep.check("https://tauri.app/?utm_source=x")
+            .unwrap()
+            .url
+            .as_str(),
+        "https://tauri.app/?utm_source=x"
+    );
+}
@@ -0,0 +1,202 @@
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use chrono::{TimeZone, Utc};
+use serde_json::json;
+use tauri_app_lib::crash::RECENT_LOG_ENTRIES;
+use tauri_app_lib::error::ErrorCode;
+use tauri_app_lib::logging::{
+    env_filter, read, LogEntry, LogFilter, LogLevel, LogSettings, RecentLines, FRONTEND_TARGET,
+};
+use tracing_subscriber::fmt::MakeWriter;
+
+fn line(day: u32, secs: u32, level: &str, target: &str, message: &str) -> String {
+    json!({
+        "timestamp": format!("2026-03-{day:02}T10:00:{secs:02}.000000Z"),
+        "level": level,
+        "fields": { "message": message },
+        "target": target,
+    })
+    .to_string()
+}
+
+fn write_log(dir: &Path, day: u32, lines: &[String]) {
+    fs::create_dir_all(dir).unwrap();
+    let path = dir.join(format!("app.2026-03-{day:02}.log"));
+    fs::write(path, lines.join("\n") + "\n").unwrap();
+}
+
+#[derive(Clone, Default)]
+struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for Buffer {
+    type Writer = Self;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+#[test]
+fn parses_lines_written_by_the_json_formatter() {
+    let buffer = Buffer::default();
+    let subscriber = tracing_subscriber::fmt()
+        .json()
+        .with_current_span(false)
+        .with_span_list(false)
+        .with_writer(buffer.clone())
+        .finish();
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::warn!(target: "tauri_app_lib::db", version = 2, "applied migration");
+    });
+
+    let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    let entry = LogEntry::parse(output.trim()).unwrap();
+    assert_eq!(entry.level, LogLevel::Warn);
+    assert_eq!(entry.target, "tauri_app_lib::db");
+    assert_eq!(entry.message, "applied migration");
+    assert_eq!(entry.fields["version"], 2);
+}
+
+#[test]
+fn keeps_the_most_recent_lines_in_memory() {
+    let recent = RecentLines::default();
+    let subscriber = tracing_subscriber::fmt()
+        .json()
+        .with_writer(recent.clone())
+        .finish();
+    tracing::subscriber::with_default(subscriber, || {
+        for n in 0..RECENT_LOG_ENTRIES + 5 {
+            tracing::info!("line {n}");
+        }
+    });
+
+    let entries = recent.try_entries().unwrap();
+    assert_eq!(entries.len(), RECENT_LOG_ENTRIES);
+    assert_eq!(entries[0].message, "line 5");
+    assert_eq!(
+        entries.last().unwrap().message,
+        format!("line {}", RECENT_LOG_ENTRIES + 4)
+    );
+}
+
+#[test]
+fn reads_newest_entries_across_files_oldest_first() {
+    let dir = tempfile::tempdir().unwrap();
+    write_log(
+        dir.path(),
+        1,
+        &[
+            line(1, 0, "INFO", "tauri_app_lib", "one"),
+            line(1, 1, "INFO", "tauri_app_lib", "two"),
+        ],
+    );
+    write_log(
+        dir.path(),
+        2,
+        &[
+            line(2, 0, "INFO", "tauri_app_lib", "three"),
+            "{\"timestamp\":\"2026-03-02T1".into(),
+        ],
+    );
+    fs::write(dir.path().join("notes.txt"), "not a log").unwrap();
+
+    let all = read(dir.path(), &LogFilter::default(), None, None).unwrap();
+    let messages: Vec<_> = all.iter().map(|entry| entry.message.as_str()).collect();
+    assert_eq!(messages, ["one", "two", "three"]);
+
+    let latest = read(dir.path(), &LogFilter::default(), None, Some(2)).unwrap();
+    let messages: Vec<_> = latest.iter().map(|entry| entry.message.as_str()).collect();
+    assert_eq!(messages, ["two", "three"]);
+
+    let since = Utc.with_ymd_and_hms(2026, 3, 1, 10, 0, 1).unwrap();
+    assert_eq!(
+        read(dir.path(), &LogFilter::default(), Some(since), None)
+            .unwrap()
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn filters_by_level_target_and_text() {
+    let dir = tempfile::tempdir().unwrap();
+    write_log(
+        dir.path(),
+        1,
+        &[
+            line(1, 0, "DEBUG", "tauri_app_lib::db", "Opened pool"),
+            line(1, 1, "WARN", "tauri_app_lib::db", "Slow query"),
+            line(1, 2, "ERROR", FRONTEND_TARGET, "Uncaught TypeError"),
+        ],
+    );
+
+    let at_least_warn = LogFilter {
+        level: Some(LogLevel::Warn),
+        ..Default::default()
+    };
+    assert_eq!(
+        read(dir.path(), &at_least_warn, None, None).unwrap().len(),
+        2
+    );
+
+    let db = LogFilter {
+        target: Some("tauri_app_lib::db".into()),
+        ..Default::default()
+    };
+    assert_eq!(read(dir.path(), &db, None, None).unwrap().len(), 2);
+
+    let text = LogFilter {
+        text: Some("typeerror".into()),
+        ..Default::default()
+    };
+    let found = read(dir.path(), &text, None, None).unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].target, FRONTEND_TARGET);
+}
+
+#[test]
+fn missing_directory_reads_as_empty() {
+    let dir = tempfile::tempdir().unwrap();
+    let logs = read(&dir.path().join("logs"), &LogFilter::default(), None, None).unwrap();
+    assert!(logs.is_empty());
+}
+
+#[test]
+fn levels_parse_case_insensitively() {
+    assert_eq!("INFO".parse::<LogLevel>().unwrap(), LogLevel::Info);
+    assert_eq!("warn".parse::<LogLevel>().unwrap(), LogLevel::Warn);
+    assert_eq!(
+        "loud".parse::<LogLevel>().unwrap_err().code,
+        ErrorCode::InvalidInput
+    );
+    assert!(LogLevel::Error > LogLevel::Trace);
+}
+
+#[test]
+fn settings_build_per_module_filters() {
+    let mut settings = LogSettings::default();
+    settings
+        .modules
+        .insert("tauri_app_lib::db".into(), LogLevel::Debug);
+    let filter = env_filter(&settings).unwrap().to_string();
+    assert!(filter.contains("tauri_app_lib::db=debug"), "{filter}");
+
+    settings.modules.insert("bad[".into(), LogLevel::Trace);
+    assert_eq!(
+        env_filter(&settings).unwrap_err().code,
+        ErrorCode::InvalidInput
+    );
+}
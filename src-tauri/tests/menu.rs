@@ -0,0 +1,150 @@
+mod common;
+
+use std::path::PathBuf;
+
+use chrono::Utc;
+use common::TestApp;
+use serde_json::json;
+use tauri::Manager;
+use tauri_app_lib::db::recent_files;
+use tauri_app_lib::db::Database;
+use tauri_app_lib::documents::Documents;
+use tauri_app_lib::events::{DOCUMENTS_CHANGED, RECENT_FILES_CHANGED};
+use tauri_app_lib::i18n::{I18n, EMBEDDED_RESOURCES};
+use tauri_app_lib::menu::{self, MenuAction, MenuEntry, MenuModel};
+use tauri_app_lib::windows::{WindowManager, WindowRole};
+
+fn enabled(entries: &[MenuEntry], action: MenuAction) -> bool {
+    match MenuEntry::find(entries, &action.id()) {
+        Some(MenuEntry::Item { enabled, .. }) => *enabled,
+        other => panic!("no item for {action:?}: {other:?}"),
+    }
+}
+
+#[test]
+fn actions_round_trip_through_menu_ids() {
+    for action in [
+        MenuAction::New,
+        MenuAction::Open,
+        MenuAction::OpenRecent(PathBuf::from("/tmp/a b.txt")),
+        MenuAction::ClearRecent,
+        MenuAction::Save,
+        MenuAction::SaveAs,
+        MenuAction::CloseWindow,
+        MenuAction::Quit,
+        MenuAction::Settings,
+        MenuAction::ToggleFullscreen,
+        MenuAction::About,
+    ] {
+        assert_eq!(MenuAction::parse(&action.id()), Some(action));
+    }
+    assert_eq!(MenuAction::parse("recent-empty"), None);
+}
+
+#[test]
+fn items_follow_the_app_state() {
+    let i18n = I18n::new(EMBEDDED_RESOURCES, Some("en-US"));
+
+    let idle = MenuModel::default();
+    let entries = idle.entries(&i18n).unwrap();
+    let tops: Vec<_> = entries.iter().filter_map(MenuEntry::label).collect();
+    assert_eq!(tops, ["File", "Edit", "View", "Help"]);
+    assert!(!enabled(&entries, MenuAction::Save));
+    assert!(!enabled(&entries, MenuAction::SaveAs));
+    assert!(enabled(&entries, MenuAction::New));
+    assert!(MenuEntry::find(&entries, &MenuAction::ClearRecent.id()).is_none());
+    assert_eq!(
+        MenuEntry::find(&entries, &MenuAction::Save.id()),
+        Some(&MenuEntry::Item {
+            id: "save".into(),
+            label: "Save".into(),
+            enabled: false,
+            accelerator: Some("CmdOrCtrl+S".into()),
+        })
+    );
+
+    // Outside a document window, Save saves every unsaved document.
+    let unsaved = MenuModel {
+        dirty: vec![1, 2],
+        recent: vec!["/tmp/a.txt".into()],
+        ..Default::default()
+    };
+    assert_eq!(unsaved.save_targets(), [1, 2]);
+    let entries = unsaved.entries(&i18n).unwrap();
+    assert!(enabled(&entries, MenuAction::Save));
+    assert!(enabled(&entries, MenuAction::ClearRecent));
+    assert!(enabled(
+        &entries,
+        MenuAction::OpenRecent("/tmp/a.txt".into())
+    ));
+
+    // In one, only its own document.
+    let clean = MenuModel {
+        focused_document: Some(3),
+        ..unsaved.clone()
+    };
+    assert!(clean.save_targets().is_empty());
+    let entries = clean.entries(&i18n).unwrap();
+    assert!(!enabled(&entries, MenuAction::Save));
+    assert!(enabled(&entries, MenuAction::SaveAs));
+    let dirty = MenuModel {
+        focused_document: Some(2),
+        ..unsaved
+    };
+    assert_eq!(dirty.save_targets(), [2]);
+}
+
+#[test]
+fn dispatches_to_the_command_handlers() {
+    let app = TestApp::new();
+    let handle = app.app.handle();
+    app.capture(DOCUMENTS_CHANGED);
+    app.capture(RECENT_FILES_CHANGED);
+
+    menu::dispatch(handle, MenuAction::New).unwrap();
+    let documents = app.state::<Documents>().list();
+    assert_eq!(documents.len(), 1);
+    let windows = app
+        .state::<WindowManager>()
+        .document_labels(documents[0].id);
+    assert_eq!(windows.len(), 1);
+    assert!(app.app.get_webview_window(&windows[0]).is_some());
+
+    menu::dispatch(handle, MenuAction::Settings).unwrap();
+    assert_eq!(
+        app.state::<WindowManager>().labels(WindowRole::Settings),
+        ["settings"]
+    );
+
+    // A recent file opens in its own window, as the same document the
+    // command would return.
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("notes.txt");
+    std::fs::write(&path, "notes").unwrap();
+    let conn = app.state::<Database>().conn().unwrap();
+    recent_files::touch(&conn, &path, Utc::now()).unwrap();
+    menu::dispatch(handle, MenuAction::OpenRecent(path.clone())).unwrap();
+    let opened = app
+        .invoke("open_document", json!({ "path": path }))
+        .unwrap();
+    let id = opened["id"].as_u64().unwrap();
+    assert_eq!(app.state::<WindowManager>().document_labels(id).len(), 1);
+
+    // Without a focused document window, Save saves every unsaved document.
+    app.invoke("edit_document", json!({ "id": id, "content": "edited" }))
+        .unwrap();
+    assert!(MenuModel::current(handle).unwrap().dirty.contains(&id));
+    menu::dispatch(handle, MenuAction::Save).unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "edited");
+    assert!(MenuModel::current(handle).unwrap().dirty.is_empty());
+    // Opened twice, then dirtied and saved.
+    assert_eq!(app.events(DOCUMENTS_CHANGED).len(), 4);
+
+    menu::dispatch(handle, MenuAction::ClearRecent).unwrap();
+    assert!(MenuModel::current(handle).unwrap().recent.is_empty());
+    assert_eq!(app.invoke("list_recent_files", json!({})), Ok(json!([])));
+    assert_eq!(
+        app.events(RECENT_FILES_CHANGED),
+        vec![json!({ "version": 1, "payload": null }); 3]
+    );
+}
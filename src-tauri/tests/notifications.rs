@@ -0,0 +1,789 @@
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use fluent_bundle::FluentArgs;
+use tauri_app_lib::db::notifications::{self, DeliveryStatus, NotificationQuery};
+use tauri_app_lib::db::{schedules, Database};
+use tauri_app_lib::error::{AppError, AppResult, ErrorCode};
+use tauri_app_lib::i18n::{I18n, EMBEDDED_RESOURCES};
+use tauri_app_lib::notifications::clock::FakeClock;
+use tauri_app_lib::notifications::cron::Cron;
+use tauri_app_lib::notifications::dispatch::{
+    digest_retry_delay, Dispatcher, QuietHours, QuietMode, DIGEST_CATEGORY, DIGEST_RETRY,
+    MAX_DIGEST_RETRY,
+};
+use tauri_app_lib::notifications::scheduler::{Scheduler, Trigger, MAX_SLEEP};
+use tauri_app_lib::notifications::templates::{Template, TemplateRegistry};
+use tauri_app_lib::notifications::{
+    CategoryPreferences, Notification, NotificationSettings, Notifier, Priority,
+};
+
+fn utc(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+}
+
+fn next(expr: &str, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    expr.parse::<Cron>().unwrap().next_after(&after)
+}
+
+/// Records what would have been shown, and whether with sound; titles
+/// starting with "fail" error.
+#[derive(Default)]
+struct Recorder(Mutex<Vec<(Notification, bool)>>);
+
+impl Recorder {
+    fn titles(&self) -> Vec<String> {
+        let shown = self.0.lock().unwrap();
+        shown.iter().map(|(n, _)| n.title.clone()).collect()
+    }
+
+    fn sounds(&self) -> Vec<bool> {
+        let shown = self.0.lock().unwrap();
+        shown.iter().map(|(_, sound)| *sound).collect()
+    }
+}
+
+impl Notifier for Recorder {
+    fn send(
+        &self,
+        notification: &Notification,
+        preferences: &CategoryPreferences,
+    ) -> AppResult<()> {
+        if notification.title.starts_with("fail") {
+            return Err(AppError::new(ErrorCode::Notification, "permission denied"));
+        }
+        let shown = (notification.clone(), preferences.sound);
+        self.0.lock().unwrap().push(shown);
+        Ok(())
+    }
+}
+
+fn notification(title: &str) -> Notification {
+    Notification::new(title)
+}
+
+fn in_category(title: &str, category: &str) -> Notification {
+    Notification {
+        category: Some(category.into()),
+        ..Notification::new(title)
+    }
+}
+
+fn prefer(settings: &mut NotificationSettings, category: &str, preferences: CategoryPreferences) {
+    settings.categories.insert(category.into(), preferences);
+}
+
+struct Harness {
+    clock: Arc<FakeClock>,
+    recorder: Arc<Recorder>,
+    scheduler: Scheduler,
+    dispatcher: Dispatcher,
+    settings: NotificationSettings,
+}
+
+impl Harness {
+    fn new() -> Self {
+        let clock = Arc::new(FakeClock::new(utc(2026, 3, 2, 8, 0)));
+        let recorder = Arc::new(Recorder::default());
+        Self {
+            scheduler: Scheduler::new(clock.clone()),
+            dispatcher: Dispatcher::new(clock.clone(), recorder.clone()),
+            clock,
+            recorder,
+            settings: NotificationSettings::default(),
+        }
+    }
+
+    fn tick(&self, conn: &rusqlite::Connection) -> usize {
+        self.scheduler
+            .tick(conn, &self.dispatcher, &self.settings)
+            .unwrap()
+            .len()
+    }
+
+    fn send(&self, conn: &rusqlite::Connection, notification: Notification) -> DeliveryStatus {
+        self.dispatcher
+            .send(conn, notification, &self.settings)
+            .unwrap()
+            .status
+    }
+}
+
+/// Quiet from 22:00 to 07:00 (UTC, per `FakeClock`).
+fn overnight(mode: QuietMode) -> NotificationSettings {
+    NotificationSettings {
+        quiet_hours: Some(QuietHours {
+            start: "22:00".parse().unwrap(),
+            end: "07:00".parse().unwrap(),
+            mode,
+        }),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn cron_finds_next_matching_minute() {
+    // 2026-03-02 is a Monday.
+    let monday = utc(2026, 3, 2, 8, 0);
+    assert_eq!(next("* * * * *", monday), Some(utc(2026, 3, 2, 8, 1)));
+    assert_eq!(next("*/15 * * * *", monday), Some(utc(2026, 3, 2, 8, 15)));
+    assert_eq!(next("30 9 * * *", monday), Some(utc(2026, 3, 2, 9, 30)));
+    assert_eq!(next("0 8 * * *", monday), Some(utc(2026, 3, 3, 8, 0)));
+    assert_eq!(next("0 9 * * 6,7", monday), Some(utc(2026, 3, 7, 9, 0)));
+    assert_eq!(next("0 0 1 */3 *", monday), Some(utc(2026, 4, 1, 0, 0)));
+    assert_eq!(next("0 12 29 2 *", monday), Some(utc(2028, 2, 29, 12, 0)));
+    assert_eq!(next("0 0 31 2 *", monday), None);
+}
+
+#[test]
+fn cron_matches_either_restricted_day_field() {
+    // The 15th, or any Friday.
+    let after = utc(2026, 3, 2, 8, 0);
+    assert_eq!(next("0 9 15 * 5", after), Some(utc(2026, 3, 6, 9, 0)));
+    assert_eq!(
+        next("0 9 15 * 5", utc(2026, 3, 13, 10, 0)),
+        Some(utc(2026, 3, 15, 9, 0))
+    );
+}
+
+#[test]
+fn cron_rejects_malformed_expressions() {
+    for expr in [
+        "",
+        "* * * *",
+        "60 * * * *",
+        "* 24 * * *",
+        "*/0 * * * *",
+        "5-1 * * * *",
+        "a * * * *",
+    ] {
+        let err = expr.parse::<Cron>().unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidInput, "{expr:?}");
+    }
+}
+
+#[test]
+fn fires_one_off_notifications_when_due() {
+    let h = Harness::new();
+    let db = Database::open_in_memory().unwrap();
+    let conn = db.conn().unwrap();
+
+    h.scheduler
+        .schedule(
+            &conn,
+            notification("at"),
+            Trigger::At {
+                at: utc(2026, 3, 2, 9, 0),
+            },
+        )
+        .unwrap();
+    let after = h
+        .scheduler
+        .schedule(&conn, notification("after"), Trigger::After { seconds: 90 })
+        .unwrap();
+    assert_eq!(
+        after.next_run,
+        utc(2026, 3, 2, 8, 0) + Duration::seconds(90)
+    );
+
+    assert_eq!(h.tick(&conn), 0);
+    h.clock.advance(Duration::minutes(2));
+    assert_eq!(h.tick(&conn), 1);
+    h.clock.set(utc(2026, 3, 2, 9, 0));
+    assert_eq!(h.tick(&conn), 1);
+
+    assert_eq!(h.recorder.titles(), ["after", "at"]);
+    assert!(schedules::list(&conn).unwrap().is_empty());
+}
+
+#[test]
+fn a_schedule_that_cannot_be_sent_does_not_hold_up_the_others() {
+    let h = Harness::new();
+    let db = Database::open_in_memory().unwrap();
+    let conn = db.conn().unwrap();
+
+    // Stored without validation, so sending it fails.
+    let now = h.scheduler.now();
+    let hourly = Trigger::Cron {
+        expression: "0 * * * *".into(),
+        utc: true,
+    };
+    let broken = schedules::insert(&conn, &notification(""), &hourly, now, now).unwrap();
+    h.scheduler
+        .schedule(&conn, notification("fine"), Trigger::After { seconds: 0 })
+        .unwrap();
+
+    assert_eq!(h.tick(&conn), 1);
+    assert_eq!(h.recorder.titles(), ["fine"]);
+    // Moved on all the same, so it is not retried on every tick.
+    assert_eq!(
+        schedules::get(&conn, broken.id).unwrap().next_run,
+        utc(2026, 3, 2, 9, 0)
+    );
+    assert_eq!(h.tick(&conn), 0);
+}
+
+#[test]
+fn a_schedule_that_cannot_be_moved_on_is_stopped() {
+    let h = Harness::new();
+    let db = Database::open_in_memory().unwrap();
+    let conn = db.conn().unwrap();
+
+    // Stored without validation, as by an older version with other rules.
+    let now = h.scheduler.now();
+    let unparseable = Trigger::Cron {
+        expression: "every hour".into(),
+        utc: true,
+    };
+    let broken = schedules::insert(&conn, &notification("broken"), &unparseable, now, now).unwrap();
+
+    assert_eq!(h.tick(&conn), 0);
+    assert!(h.recorder.titles().is_empty());
+    let stopped = schedules::get(&conn, broken.id).unwrap();
+    assert!(stopped.error.is_some());
+    // No longer due, so the background task does not wake right away.
+    assert_eq!(h.scheduler.until_next(&conn).unwrap(), MAX_SLEEP);
+    assert_eq!(h.tick(&conn), 0);
+
+    let hourly = Trigger::Cron {
+        expression: "0 * * * *".into(),
+        utc: true,
+    };
+    let restarted = h.scheduler.reschedule(&conn, broken.id, hourly).unwrap();
+    assert_eq!(restarted.error, None);
+    assert_eq!(restarted.next_run, utc(2026, 3, 2, 9, 0));
+}
+
+#[test]
+fn recurring_notifications_fire_once_per_wakeup() {
+    let h = Harness::new();
+    let db = Database::open_in_memory().unwrap();
+    let conn = db.conn().unwrap();
+
+    let hourly = h
+        .scheduler
+        .schedule(
+            &conn,
+            notification("hourly"),
+            Trigger::Cron {
+                expression: "0 * * * *".into(),
+                utc: true,
+            },
+        )
+        .unwrap();
+    assert_eq!(hourly.next_run, utc(2026, 3, 2, 9, 0));
+
+    // Asleep through three runs: only one notification, then back on track.
+    h.clock.set(utc(2026, 3, 2, 11, 30));
+    assert_eq!(h.tick(&conn), 1);
+    assert_eq!(h.recorder.titles().len(), 1);
+    assert_eq!(
+        schedules::get(&conn, hourly.id).unwrap().next_run,
+        utc(2026, 3, 2, 12, 0)
+    );
+    assert_eq!(h.scheduler.until_next(&conn).unwrap(), MAX_SLEEP);
+    h.clock.set(utc(2026, 3, 2, 11, 59) + Duration::seconds(30));
+    assert_eq!(
+        h.scheduler.until_next(&conn).unwrap(),
+        std::time::Duration::from_secs(30)
+    );
+}
+
+#[test]
+fn schedules_can_be_rescheduled_and_cancelled() {
+    let h = Harness::new();
+    let db = Database::open_in_memory().unwrap();
+    let conn = db.conn().unwrap();
+
+    let id = h
+        .scheduler
+        .schedule(&conn, notification("later"), Trigger::After { seconds: 60 })
+        .unwrap()
+        .id;
+    let moved = h
+        .scheduler
+        .reschedule(&conn, id, Trigger::After { seconds: 3600 })
+        .unwrap();
+    assert_eq!(moved.next_run, utc(2026, 3, 2, 9, 0));
+
+    h.clock.advance(Duration::minutes(5));
+    assert_eq!(h.tick(&conn), 0);
+
+    h.scheduler.cancel(&conn, id).unwrap();
+    assert_eq!(
+        h.scheduler.cancel(&conn, id).unwrap_err().code,
+        ErrorCode::NotFound
+    );
+    h.clock.advance(Duration::hours(2));
+    assert_eq!(h.tick(&conn), 0);
+    assert!(h.recorder.titles().is_empty());
+}
+
+#[test]
+fn rejects_invalid_schedules() {
+    let h = Harness::new();
+    let db = Database::open_in_memory().unwrap();
+    let conn = db.conn().unwrap();
+
+    let never = Trigger::Cron {
+        expression: "0 0 30 2 *".into(),
+        utc: true,
+    };
+    assert_eq!(
+        h.scheduler
+            .schedule(&conn, notification("never"), never)
+            .unwrap_err()
+            .code,
+        ErrorCode::InvalidInput
+    );
+    assert_eq!(
+        h.scheduler
+            .schedule(&conn, notification(" "), Trigger::After { seconds: 1 })
+            .unwrap_err()
+            .code,
+        ErrorCode::InvalidInput
+    );
+}
+
+#[test]
+fn schedules_survive_a_restart() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.db");
+    {
+        let h = Harness::new();
+        let db = Database::open(&path).unwrap();
+        let conn = db.conn().unwrap();
+        h.scheduler
+            .schedule(
+                &conn,
+                notification("persisted"),
+                Trigger::After { seconds: 60 },
+            )
+            .unwrap();
+    }
+
+    let h = Harness::new();
+    let db = Database::open(&path).unwrap();
+    let conn = db.conn().unwrap();
+    assert_eq!(schedules::list(&conn).unwrap().len(), 1);
+    h.clock.advance(Duration::minutes(1));
+    h.tick(&conn);
+    assert_eq!(h.recorder.titles(), ["persisted"]);
+}
+
+#[test]
+fn quiet_hours_may_span_midnight() {
+    let at = |time: &str| time.parse().unwrap();
+    let overnight = overnight(QuietMode::Defer).quiet_hours.unwrap();
+    assert!(overnight.contains(at("23:30")));
+    assert!(overnight.contains(at("06:59")));
+    assert!(!overnight.contains(at("07:00")));
+    assert!(!overnight.contains(at("12:00")));
+
+    let lunch = QuietHours {
+        start: at("12:00"),
+        end: at("13:00"),
+        mode: QuietMode::Defer,
+    };
+    assert!(lunch.contains(at("12:00")));
+    assert!(!lunch.contains(at("13:00")));
+}
+
+#[test]
+fn records_every_notification_with_its_outcome() {
+    let h = Harness::new();
+    let db = Database::open_in_memory().unwrap();
+    let conn = db.conn().unwrap();
+
+    let mut greeting = notification("Ada says hi");
+    greeting.category = Some("greeting".into());
+    assert_eq!(h.send(&conn, greeting), DeliveryStatus::Delivered);
+    assert_eq!(
+        h.send(&conn, notification("failing")),
+        DeliveryStatus::Failed
+    );
+
+    let page = notifications::list(&conn, &NotificationQuery::default()).unwrap();
+    assert_eq!(page.total, 2);
+    assert_eq!(page.items[0].error.as_deref(), Some("permission denied"));
+    assert_eq!(
+        page.items[1].notification.category.as_deref(),
+        Some("greeting")
+    );
+
+    let greetings = notifications::list(
+        &conn,
+        &NotificationQuery {
+            category: Some("greeting".into()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(greetings.total, 1);
+
+    let groups = notifications::groups(&conn).unwrap();
+    assert_eq!(groups.len(), 2);
+    assert!(groups.iter().all(|group| group.count == 1));
+}
+
+#[test]
+fn quiet_hours_defer_until_a_digest_unless_urgent() {
+    let mut h = Harness::new();
+    h.settings = overnight(QuietMode::Defer);
+    let db = Database::open_in_memory().unwrap();
+    let conn = db.conn().unwrap();
+    let i18n = I18n::new(EMBEDDED_RESOURCES, Some("en-US"));
+
+    h.clock.set(utc(2026, 3, 2, 23, 0));
+    assert_eq!(h.send(&conn, notification("one")), DeliveryStatus::Deferred);
+    assert_eq!(h.send(&conn, notification("two")), DeliveryStatus::Deferred);
+    let mut alarm = notification("alarm");
+    alarm.urgent = true;
+    assert_eq!(h.send(&conn, alarm), DeliveryStatus::Delivered);
+    assert_eq!(h.recorder.titles(), ["alarm"]);
+
+    // Still quiet: nothing to show yet.
+    assert!(h
+        .dispatcher
+        .send_digest(&conn, &h.settings, &i18n)
+        .unwrap()
+        .is_none());
+
+    h.clock.set(utc(2026, 3, 3, 7, 0));
+    let digest = h
+        .dispatcher
+        .send_digest(&conn, &h.settings, &i18n)
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        digest.notification.title,
+        "2 notifications arrived during quiet hours"
+    );
+    assert_eq!(digest.notification.body.as_deref(), Some("one\ntwo"));
+    assert_eq!(
+        digest.notification.category.as_deref(),
+        Some(DIGEST_CATEGORY)
+    );
+    assert_eq!(h.recorder.titles().len(), 2);
+
+    let digested = notifications::list(
+        &conn,
+        &NotificationQuery {
+            status: Some(DeliveryStatus::Digested),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(digested.total, 2);
+    assert!(digested
+        .items
+        .iter()
+        .all(|record| record.digest_id == Some(digest.id)));
+    assert!(h
+        .dispatcher
+        .send_digest(&conn, &h.settings, &i18n)
+        .unwrap()
+        .is_none());
+}
+
+/// Fails to show anything.
+struct Broken;
+
+impl Notifier for Broken {
+    fn send(&self, _: &Notification, _: &CategoryPreferences) -> AppResult<()> {
+        Err(AppError::new(ErrorCode::Notification, "permission denied"))
+    }
+}
+
+#[test]
+fn a_digest_that_cannot_be_shown_is_retried() {
+    let mut h = Harness::new();
+    h.settings = overnight(QuietMode::Defer);
+    let db = Database::open_in_memory().unwrap();
+    let conn = db.conn().unwrap();
+    let i18n = I18n::new(EMBEDDED_RESOURCES, Some("en-US"));
+
+    h.clock.set(utc(2026, 3, 2, 23, 0));
+    assert_eq!(h.send(&conn, notification("one")), DeliveryStatus::Deferred);
+
+    h.clock.set(utc(2026, 3, 3, 7, 0));
+    let broken = Dispatcher::new(h.clock.clone(), Arc::new(Broken));
+    let failed = broken
+        .send_digest(&conn, &h.settings, &i18n)
+        .unwrap()
+        .unwrap();
+    assert_eq!(failed.status, DeliveryStatus::Failed);
+    assert_eq!(failed.error.as_deref(), Some("permission denied"));
+    let deferred = notifications::deferred(&conn).unwrap();
+    assert_eq!(deferred.len(), 1);
+    assert_eq!(deferred[0].digest_id, None);
+
+    // Not before the retry is due, and then in the same history entry.
+    assert!(h
+        .dispatcher
+        .send_digest(&conn, &h.settings, &i18n)
+        .unwrap()
+        .is_none());
+    h.clock.advance(DIGEST_RETRY);
+    let digest = h
+        .dispatcher
+        .send_digest(&conn, &h.settings, &i18n)
+        .unwrap()
+        .unwrap();
+    assert_eq!(digest.id, failed.id);
+    assert_eq!(digest.status, DeliveryStatus::Delivered);
+    assert_eq!(digest.error, None);
+    assert!(notifications::deferred(&conn).unwrap().is_empty());
+    assert!(notifications::failed_digest(&conn).unwrap().is_none());
+    assert_eq!(h.recorder.titles().len(), 1);
+}
+
+#[test]
+fn a_digest_that_keeps_failing_backs_off_in_one_entry() {
+    let mut h = Harness::new();
+    h.settings = overnight(QuietMode::Defer);
+    let db = Database::open_in_memory().unwrap();
+    let conn = db.conn().unwrap();
+    let i18n = I18n::new(EMBEDDED_RESOURCES, Some("en-US"));
+
+    h.clock.set(utc(2026, 3, 2, 23, 0));
+    assert_eq!(h.send(&conn, notification("one")), DeliveryStatus::Deferred);
+
+    h.clock.set(utc(2026, 3, 3, 7, 0));
+    let broken = Dispatcher::new(h.clock.clone(), Arc::new(Broken));
+    let mut attempts = 0;
+    for _ in 0..=30 {
+        if let Some(digest) = broken.send_digest(&conn, &h.settings, &i18n).unwrap() {
+            assert_eq!(digest.status, DeliveryStatus::Failed);
+            attempts += 1;
+        }
+        h.clock.advance(Duration::minutes(1));
+    }
+    // After 1, 2, 4 and 8 minutes.
+    assert_eq!(attempts, 5);
+    assert_eq!(digest_retry_delay(attempts), Duration::minutes(16));
+    assert_eq!(digest_retry_delay(100), MAX_DIGEST_RETRY);
+
+    let digests = notifications::list(
+        &conn,
+        &NotificationQuery {
+            category: Some(DIGEST_CATEGORY.into()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(digests.total, 1);
+    let failed = notifications::failed_digest(&conn).unwrap().unwrap();
+    assert_eq!(failed.id, digests.items[0].id);
+    assert_eq!(failed.attempts, 5);
+    assert_eq!(notifications::deferred(&conn).unwrap().len(), 1);
+}
+
+#[test]
+fn quiet_hours_can_suppress_instead() {
+    let mut h = Harness::new();
+    h.settings = overnight(QuietMode::Suppress);
+    let db = Database::open_in_memory().unwrap();
+    let conn = db.conn().unwrap();
+
+    h.clock.set(utc(2026, 3, 2, 23, 0));
+    h.scheduler
+        .schedule(&conn, notification("late"), Trigger::After { seconds: 0 })
+        .unwrap();
+    assert_eq!(h.tick(&conn), 1);
+    assert!(h.recorder.titles().is_empty());
+    assert!(notifications::deferred(&conn).unwrap().is_empty());
+
+    let page = notifications::list(&conn, &NotificationQuery::default()).unwrap();
+    assert_eq!(page.items[0].status, DeliveryStatus::Suppressed);
+}
+
+#[test]
+fn pausing_defers_and_unread_notifications_are_counted() {
+    let mut h = Harness::new();
+    let unread = Arc::new(Mutex::new(Vec::new()));
+    let seen = unread.clone();
+    h.dispatcher = h.dispatcher.with_listener(Box::new(move |conn, _| {
+        seen.lock()
+            .unwrap()
+            .push(notifications::unread_count(conn).unwrap());
+    }));
+    let db = Database::open_in_memory().unwrap();
+    let conn = db.conn().unwrap();
+    let i18n = I18n::new(EMBEDDED_RESOURCES, Some("en-US"));
+
+    h.settings.paused = true;
+    assert_eq!(h.send(&conn, notification("one")), DeliveryStatus::Deferred);
+    assert!(h
+        .dispatcher
+        .send_digest(&conn, &h.settings, &i18n)
+        .unwrap()
+        .is_none());
+
+    h.settings.paused = false;
+    assert_eq!(
+        h.send(&conn, notification("two")),
+        DeliveryStatus::Delivered
+    );
+    h.dispatcher
+        .send_digest(&conn, &h.settings, &i18n)
+        .unwrap()
+        .unwrap();
+    assert_eq!(*unread.lock().unwrap(), [0, 1, 2]);
+
+    assert_eq!(
+        notifications::mark_read(&conn, h.dispatcher.now()).unwrap(),
+        2
+    );
+    assert_eq!(notifications::unread_count(&conn).unwrap(), 0);
+    assert_eq!(
+        notifications::mark_read(&conn, h.dispatcher.now()).unwrap(),
+        0
+    );
+}
+
+#[test]
+fn renders_templates_in_the_requested_locale() {
+    let i18n = I18n::new(EMBEDDED_RESOURCES, Some("en-US"));
+    let templates = TemplateRegistry::builtin();
+    let mut args = FluentArgs::new();
+    args.set("name", "Ada");
+    args.set("gender", "female");
+
+    let english = templates
+        .render(&i18n, "greeting.received", Some(&args), None)
+        .unwrap();
+    assert_eq!(english.title, "New greeting from Ada");
+    assert_eq!(
+        english.body.as_deref(),
+        Some("Ada greeted you. Say hi to her!")
+    );
+    assert_eq!(english.category.as_deref(), Some("greetings"));
+
+    let german = templates
+        .render(&i18n, "greeting.received", Some(&args), Some("de"))
+        .unwrap();
+    assert_eq!(german.title, "Neuer Gruß von Ada");
+    assert_eq!(
+        german.body.as_deref(),
+        Some("Ada hat dich begrüßt. Sag ihr hallo!")
+    );
+}
+
+#[test]
+fn rejects_unknown_templates_and_missing_placeholders() {
+    let i18n = I18n::new(EMBEDDED_RESOURCES, Some("en-US"));
+    let templates = TemplateRegistry::builtin();
+    assert!(templates.names().contains(&"greeting.received"));
+
+    let err = templates.render(&i18n, "nope", None, None).unwrap_err();
+    assert_eq!(err.code, ErrorCode::NotFound);
+
+    let mut args = FluentArgs::new();
+    args.set("gender", "male");
+    let err = templates
+        .render(&i18n, "greeting.received", Some(&args), None)
+        .unwrap_err();
+    assert_eq!(err.code, ErrorCode::InvalidInput);
+    assert_eq!(err.details.unwrap()["field"], "name");
+
+    // Optional arguments fall back to the default variant.
+    let mut args = FluentArgs::new();
+    args.set("name", "Sam");
+    let rendered = templates
+        .render(&i18n, "greeting.received", Some(&args), None)
+        .unwrap();
+    assert_eq!(
+        rendered.body.as_deref(),
+        Some("Sam greeted you. Say hi to them!")
+    );
+
+    let custom = TemplateRegistry::new(&[Template {
+        name: "digest.empty",
+        category: "digest",
+        title: "crash-save",
+        body: None,
+        placeholders: &[],
+        urgent: true,
+    }]);
+    let rendered = custom.render(&i18n, "digest.empty", None, None).unwrap();
+    assert_eq!(rendered.title, "Save…");
+    assert_eq!(rendered.body, None);
+    assert!(rendered.urgent);
+}
+
+#[test]
+fn disabled_categories_are_recorded_but_not_shown() {
+    let mut h = Harness::new();
+    let db = Database::open_in_memory().unwrap();
+    let conn = db.conn().unwrap();
+    prefer(
+        &mut h.settings,
+        "greetings",
+        CategoryPreferences {
+            enabled: false,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(
+        h.send(&conn, in_category("hello", "greetings")),
+        DeliveryStatus::Disabled
+    );
+    assert_eq!(
+        h.send(&conn, in_category("reminder", "reminders")),
+        DeliveryStatus::Delivered
+    );
+    assert_eq!(
+        h.send(&conn, notification("plain")),
+        DeliveryStatus::Delivered
+    );
+    assert_eq!(h.recorder.titles(), ["reminder", "plain"]);
+}
+
+#[test]
+fn applies_sound_and_priority_preferences() {
+    let mut h = Harness::new();
+    let db = Database::open_in_memory().unwrap();
+    let conn = db.conn().unwrap();
+    h.settings = overnight(QuietMode::Defer);
+    prefer(
+        &mut h.settings,
+        "alarms",
+        CategoryPreferences {
+            sound: false,
+            priority: Priority::High,
+            ..Default::default()
+        },
+    );
+    prefer(
+        &mut h.settings,
+        "tips",
+        CategoryPreferences {
+            priority: Priority::Low,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(
+        h.send(&conn, in_category("tip", "tips")),
+        DeliveryStatus::Delivered
+    );
+    assert_eq!(h.recorder.sounds(), [true]);
+
+    h.clock.set(utc(2026, 3, 2, 23, 0));
+    assert_eq!(
+        h.send(&conn, in_category("alarm", "alarms")),
+        DeliveryStatus::Delivered
+    );
+    assert_eq!(
+        h.send(&conn, in_category("tip", "tips")),
+        DeliveryStatus::Suppressed
+    );
+    assert_eq!(
+        h.send(&conn, notification("other")),
+        DeliveryStatus::Deferred
+    );
+    assert_eq!(h.recorder.titles(), ["tip", "alarm"]);
+    assert_eq!(h.recorder.sounds(), [true, false]);
+}
@@ -0,0 +1,378 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use common::TestApp;
+use tauri::async_runtime::block_on;
+use tauri_app_lib::error::ErrorCode;
+use tauri_app_lib::process::manager::{
+    OutputStream, ProcessInfo, ProcessManager, ProcessState, OUTPUT_LINES,
+};
+use tauri_app_lib::process::runner::{ProcessEvent, ProcessRunner, MAX_LINE_BYTES};
+use tauri_app_lib::process::{self, Allowlist};
+use tauri_app_lib::Plugins;
+use tauri_plugin_shell::ShellExt;
+
+fn allowlist() -> Allowlist {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("commands.json");
+    std::fs::write(
+        &path,
+        serde_json::json!({
+            "commands": {
+                "echo": {
+                    "program": "/bin/echo",
+                    "args": ["hello", { "validator": "[a-z]+" }],
+                },
+                "env": {
+                    "program": "/usr/bin/env",
+                    "env": { "GREETING": "hi" },
+                },
+                "sleep": {
+                    "program": "/bin/sleep",
+                    "args": [{ "validator": "\\d+" }],
+                    "timeoutSecs": 1,
+                },
+                "false": { "program": "/bin/false" },
+                "seq": {
+                    "program": "/usr/bin/seq",
+                    "args": [{ "validator": "\\d+" }],
+                },
+                "long": {
+                    "program": "/bin/sh",
+                    "args": ["-c", LONG_SCRIPT],
+                },
+                "server": {
+                    "program": "/bin/sh",
+                    "args": ["-c", SERVER_SCRIPT],
+                },
+                "tree": {
+                    "program": "/bin/sh",
+                    "args": ["-c", TREE_SCRIPT],
+                },
+                "chatty": {
+                    "program": "/usr/bin/yes",
+                },
+            }
+        })
+        .to_string(),
+    )
+    .unwrap();
+    Allowlist::load(&path).unwrap()
+}
+
+const LONG_SCRIPT: &str = "head -c 100000 /dev/zero | tr '\\0' x; echo; echo after";
+
+const SERVER_SCRIPT: &str = "echo listening; echo warming up >&2; exec sleep 30";
+
+/// Prints the pid of a grandchild, then waits for it.
+const TREE_SCRIPT: &str = "sleep 30 & echo $!; wait";
+
+fn server() -> Vec<String> {
+    args(&["-c", SERVER_SCRIPT])
+}
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+#[test]
+fn loads_allowlists_and_rejects_relative_programs() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("commands.json");
+    assert!(Allowlist::load(&path).unwrap().names().is_empty());
+
+    std::fs::write(&path, r#"{ "commands": { "ls": { "program": "ls" } } }"#).unwrap();
+    let err = Allowlist::load(&path).unwrap_err();
+    assert_eq!(err.code, ErrorCode::InvalidInput);
+
+    std::fs::write(
+        &path,
+        r#"{ "commands": { "ls": { "program": "/bin/ls", "args": [{ "validator": "(" }] } } }"#,
+    )
+    .unwrap();
+    assert_eq!(Allowlist::load(&path).unwrap_err().code, ErrorCode::Json);
+
+    assert_eq!(
+        allowlist().names(),
+        ["chatty", "echo", "env", "false", "long", "seq", "server", "sleep", "tree"]
+    );
+}
+
+#[test]
+fn streams_output_and_reports_exit_status() {
+    let app = TestApp::new();
+    let shell = app.app.shell();
+    let runner = ProcessRunner::new(allowlist());
+
+    let mut events = Vec::new();
+    let exit = block_on(
+        runner.run(shell, "echo", &args(&["hello", "world"]), |event| {
+            events.push(event)
+        }),
+    )
+    .unwrap();
+    assert!(exit.success);
+    assert_eq!(exit.code, Some(0));
+    assert!(matches!(events[0], ProcessEvent::Started { id, pid: Some(_) } if id == exit.id));
+    assert_eq!(
+        events[1],
+        ProcessEvent::Stdout {
+            line: "hello world".into()
+        }
+    );
+
+    // Only the declared environment reaches the child.
+    let mut lines = Vec::new();
+    block_on(runner.run(shell, "env", &[], |event| {
+        if let ProcessEvent::Stdout { line } = event {
+            lines.push(line);
+        }
+    }))
+    .unwrap();
+    assert_eq!(lines, ["GREETING=hi"]);
+
+    // Overlong lines are cut short without losing the lines after them.
+    let mut lines = Vec::new();
+    block_on(
+        runner.run(shell, "long", &args(&["-c", LONG_SCRIPT]), |event| {
+            if let ProcessEvent::Stdout { line } = event {
+                lines.push(line);
+            }
+        }),
+    )
+    .unwrap();
+    assert_eq!(lines, ["x".repeat(MAX_LINE_BYTES), "after".into()]);
+
+    let exit = block_on(runner.run(shell, "false", &[], |_| {})).unwrap();
+    assert!(!exit.success);
+    assert_eq!(exit.code, Some(1));
+}
+
+#[test]
+fn builds_commands_with_the_shell_plugin() {
+    let app = TestApp::new();
+    assert!(process::shell(app.app.handle()).is_ok());
+
+    let app = TestApp::with(|builder| builder.with_plugins(Plugins::NONE));
+    assert!(matches!(
+        process::shell(app.app.handle()),
+        Err(err) if err.code == ErrorCode::Internal
+    ));
+}
+
+#[test]
+fn rejects_invocations_outside_the_allowlist() {
+    let app = TestApp::new();
+    let shell = app.app.shell();
+    let runner = ProcessRunner::new(allowlist());
+    let reason = |name: &str, list: &[&str]| {
+        let err = block_on(runner.run(shell, name, &args(list), |_| {})).unwrap_err();
+        assert_eq!(err.code, ErrorCode::PermissionDenied);
+        err.details.unwrap()["reason"].clone()
+    };
+
+    assert_eq!(reason("rm", &["-rf", "/"]), "not_allowed");
+    assert_eq!(reason("echo", &["hello"]), "argument_count");
+    assert_eq!(reason("echo", &["goodbye", "world"]), "argument");
+    // Patterns must match the whole argument.
+    assert_eq!(reason("echo", &["hello", "world; rm -rf /"]), "argument");
+    assert_eq!(reason("sleep", &["1s"]), "argument");
+}
+
+#[test]
+fn kills_processes_that_time_out_or_are_cancelled() {
+    let app = TestApp::new();
+    let shell = app.app.shell();
+    let runner = Arc::new(ProcessRunner::new(allowlist()));
+
+    let started = Instant::now();
+    let err = block_on(runner.run(shell, "sleep", &args(&["30"]), |_| {})).unwrap_err();
+    assert_eq!(err.code, ErrorCode::Timeout);
+    assert!(started.elapsed() < Duration::from_secs(10));
+
+    let started = Instant::now();
+    let canceller = runner.clone();
+    let err = block_on(runner.run(shell, "sleep", &args(&["30"]), move |event| {
+        if let ProcessEvent::Started { id, .. } = event {
+            canceller.cancel(id).unwrap();
+        }
+    }))
+    .unwrap_err();
+    assert_eq!(err.code, ErrorCode::Cancelled);
+    assert!(started.elapsed() < Duration::from_secs(1));
+
+    assert_eq!(runner.cancel(1).unwrap_err().code, ErrorCode::NotFound);
+}
+
+/// A manager whose status events are collected into the returned list.
+fn manager() -> (ProcessManager, Arc<Mutex<Vec<ProcessInfo>>>) {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = events.clone();
+    let manager = ProcessManager::new(
+        allowlist(),
+        Box::new(move |info| sink.lock().unwrap().push(info.clone())),
+    );
+    (manager, events)
+}
+
+fn states(events: &Mutex<Vec<ProcessInfo>>) -> Vec<(String, ProcessState)> {
+    let events = events.lock().unwrap();
+    events
+        .iter()
+        .map(|info| (info.name.clone(), info.state))
+        .collect()
+}
+
+#[test]
+fn manages_long_running_processes() {
+    let app = TestApp::new();
+    let shell = app.app.shell();
+    let (manager, events) = manager();
+    block_on(async {
+        let dev = manager
+            .spawn(shell, "dev", "server", &server())
+            .await
+            .unwrap();
+        assert_eq!(dev.state, ProcessState::Running);
+        assert!(dev.pid.is_some());
+
+        let err = manager
+            .spawn(shell, "dev", "server", &server())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidInput);
+        let err = manager
+            .spawn(shell, "other", "rm", &args(&["-rf", "/"]))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, ErrorCode::PermissionDenied);
+
+        let done = manager.spawn(shell, "check", "false", &[]).await.unwrap();
+        let done = manager.wait(&done.name).await.unwrap();
+        assert_eq!(done.state, ProcessState::Exited);
+        assert_eq!(done.exit_code, Some(1));
+        assert!(done.exited_at.is_some());
+
+        let restarted = manager.restart(shell, "dev").await.unwrap();
+        assert_eq!(restarted.restarts, 1);
+        assert_eq!(restarted.state, ProcessState::Running);
+        assert_ne!(restarted.pid, dev.pid);
+
+        let names: Vec<_> = manager.list().into_iter().map(|info| info.name).collect();
+        assert_eq!(names, ["check", "dev"]);
+
+        manager.kill_all().await;
+        let killed = manager.get("dev").unwrap();
+        assert_eq!(killed.state, ProcessState::Killed);
+        assert_eq!(killed.exit_code, None);
+        // Killing again is harmless.
+        assert_eq!(
+            manager.kill("dev").await.unwrap().state,
+            ProcessState::Killed
+        );
+    });
+
+    let states = states(&events);
+    let dev: Vec<_> = states
+        .iter()
+        .filter(|(name, _)| name == "dev")
+        .map(|(_, state)| *state)
+        .collect();
+    assert_eq!(
+        dev,
+        [
+            ProcessState::Running,
+            ProcessState::Killed,
+            ProcessState::Running,
+            ProcessState::Killed
+        ]
+    );
+    assert!(states.contains(&("check".into(), ProcessState::Exited)));
+    assert_eq!(manager.get("nope").unwrap_err().code, ErrorCode::NotFound);
+}
+
+#[test]
+fn keeps_a_bounded_tail_of_output() {
+    let app = TestApp::new();
+    let shell = app.app.shell();
+    let (manager, _) = manager();
+    block_on(async {
+        manager
+            .spawn(shell, "count", "seq", &args(&["1500"]))
+            .await
+            .unwrap();
+        manager.wait("count").await.unwrap();
+
+        manager
+            .spawn(shell, "dev", "server", &server())
+            .await
+            .unwrap();
+        let started = Instant::now();
+        while manager.output("dev").unwrap().len() < 2 {
+            assert!(started.elapsed() < Duration::from_secs(5));
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        manager.kill_all().await;
+    });
+
+    let output = manager.output("count").unwrap();
+    assert_eq!(output.len(), OUTPUT_LINES);
+    assert_eq!(output[0].line, "501");
+    assert_eq!(output.last().unwrap().line, "1500");
+
+    let mut output = manager.output("dev").unwrap();
+    output.sort_by_key(|line| line.line.clone());
+    assert_eq!(output[0].line, "listening");
+    assert_eq!(output[0].stream, OutputStream::Stdout);
+    assert_eq!(output[1].line, "warming up");
+    assert_eq!(output[1].stream, OutputStream::Stderr);
+}
+
+/// Whether `pid` is still running, rather than gone or a zombie.
+#[cfg(target_os = "linux")]
+fn alive(pid: u32) -> bool {
+    std::fs::read_to_string(format!("/proc/{pid}/stat")).is_ok_and(|stat| {
+        stat.rsplit(") ")
+            .next()
+            .is_some_and(|rest| !rest.starts_with('Z'))
+    })
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn kills_grandchildren_and_chatty_processes() {
+    let app = TestApp::new();
+    let shell = app.app.shell();
+    let (manager, _) = manager();
+    block_on(async {
+        manager
+            .spawn(shell, "tree", "tree", &args(&["-c", TREE_SCRIPT]))
+            .await
+            .unwrap();
+        let started = Instant::now();
+        let grandchild = loop {
+            if let Some(line) = manager.output("tree").unwrap().first() {
+                break line.line.parse::<u32>().unwrap();
+            }
+            assert!(started.elapsed() < Duration::from_secs(5));
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        };
+        assert!(alive(grandchild));
+
+        // Output that never stops must not hold up the kill.
+        manager.spawn(shell, "chatty", "chatty", &[]).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let started = Instant::now();
+        manager.kill_all().await;
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(manager.get("chatty").unwrap().state, ProcessState::Killed);
+        let started = Instant::now();
+        while alive(grandchild) {
+            assert!(started.elapsed() < Duration::from_secs(5));
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    });
+}
@@ -0,0 +1,114 @@
+use std::fs;
+
+use serde_json::{json, Value};
+use tauri_app_lib::error::ErrorCode;
+use tauri_app_lib::settings::{
+    decode, Migration, Settings, SettingsStore, Theme, CURRENT_VERSION, MIGRATIONS,
+};
+
+#[test]
+fn current_version_matches_migrations() {
+    assert_eq!(CURRENT_VERSION, MIGRATIONS.len() as u64 + 1);
+}
+
+#[test]
+fn missing_file_loads_defaults_without_writing() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("settings.json");
+
+    let store = SettingsStore::load(&path).unwrap();
+
+    assert_eq!(store.get(), Settings::default());
+    assert!(!path.exists());
+}
+
+#[test]
+fn updates_are_persisted_with_version() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("nested").join("settings.json");
+
+    let store = SettingsStore::load(&path).unwrap();
+    let updated = store.update(json!({ "theme": "dark" })).unwrap();
+    assert_eq!(updated.theme, Theme::Dark);
+
+    let stored: Value = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+    assert_eq!(stored["version"], CURRENT_VERSION);
+    assert_eq!(stored["theme"], "dark");
+    // No temp file is left behind.
+    let names: Vec<_> = fs::read_dir(path.parent().unwrap())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .collect();
+    assert_eq!(names, ["settings.json"]);
+
+    let reloaded = SettingsStore::load(&path).unwrap();
+    assert_eq!(reloaded.get(), updated);
+}
+
+#[test]
+fn invalid_patches_leave_settings_untouched() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SettingsStore::load(dir.path().join("settings.json")).unwrap();
+
+    for patch in [
+        json!({ "theme": "neon" }),
+        json!({ "locale": "??" }),
+        json!({ "logging": { "modules": { "bad[": "debug" } } }),
+    ] {
+        let err = store.update(patch.clone()).unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidInput, "{patch}");
+    }
+    assert_eq!(store.get(), Settings::default());
+}
+
+#[test]
+fn corrupt_files_are_backed_up_and_reset() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("settings.json");
+    fs::write(&path, "{ not json").unwrap();
+
+    let store = SettingsStore::load(&path).unwrap();
+
+    assert_eq!(store.get(), Settings::default());
+    let backups: Vec<_> = fs::read_dir(dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .filter(|name| name.starts_with("settings.json.corrupt-"))
+        .collect();
+    assert_eq!(backups.len(), 1);
+    assert_eq!(
+        fs::read_to_string(dir.path().join(&backups[0])).unwrap(),
+        "{ not json"
+    );
+    assert!(path.exists());
+}
+
+#[test]
+fn files_from_newer_versions_are_rejected() {
+    let err = decode(br#"{ "version": 99 }"#, MIGRATIONS).unwrap_err();
+    assert_eq!(err.code, ErrorCode::InvalidInput);
+}
+
+#[test]
+fn migrations_run_from_stored_version() {
+    fn rename_language(doc: &mut serde_json::Map<String, Value>) {
+        if let Some(language) = doc.remove("language") {
+            doc.insert("locale".into(), language);
+        }
+    }
+    fn default_theme(doc: &mut serde_json::Map<String, Value>) {
+        doc.entry("theme").or_insert(json!("light"));
+    }
+    let migrations: &[Migration] = &[rename_language, default_theme];
+
+    let v1 = decode(br#"{ "language": "fr" }"#, migrations).unwrap();
+    assert_eq!(v1.locale.as_deref(), Some("fr"));
+    assert_eq!(v1.theme, Theme::Light);
+
+    let v2 = decode(br#"{ "version": 2, "language": "fr" }"#, migrations).unwrap();
+    assert_eq!(v2.locale, None);
+    assert_eq!(v2.theme, Theme::Light);
+
+    let v3 = decode(br#"{ "version": 3, "theme": "dark" }"#, migrations).unwrap();
+    assert_eq!(v3.theme, Theme::Dark);
+}
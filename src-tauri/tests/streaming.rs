@@ -0,0 +1,181 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use common::TestApp;
+use serde_json::{json, Value};
+use tauri::async_runtime::block_on;
+use tauri::ipc::{Channel, InvokeResponseBody};
+use tauri_app_lib::error::{AppError, ErrorCode};
+use tauri_app_lib::streaming::{Batching, Requests, StreamMessage};
+
+/// A channel whose messages are collected into the returned list.
+fn collecting<T: serde::Serialize + Clone>() -> (Channel<StreamMessage<T>>, Arc<Mutex<Vec<Value>>>)
+{
+    let messages = Arc::new(Mutex::new(Vec::new()));
+    let sink = messages.clone();
+    let channel = Channel::new(move |body| {
+        if let InvokeResponseBody::Json(json) = body {
+            sink.lock()
+                .unwrap()
+                .push(serde_json::from_str(&json).unwrap());
+        }
+        Ok(())
+    });
+    (channel, messages)
+}
+
+fn batching(max_items: usize) -> Batching {
+    Batching {
+        max_items,
+        max_delay: Duration::from_secs(10),
+        buffer: 1,
+    }
+}
+
+#[test]
+fn batches_items_and_ends_with_done() {
+    let requests = Requests::new();
+    let (channel, messages) = collecting();
+
+    let count = block_on(
+        requests.stream("r1", &channel, batching(2), |items| async move {
+            for item in 0..5 {
+                items.send(item).await?;
+            }
+            Ok(())
+        }),
+    )
+    .unwrap();
+
+    assert_eq!(count, 5);
+    assert_eq!(
+        *messages.lock().unwrap(),
+        [
+            json!({ "kind": "batch", "items": [0, 1] }),
+            json!({ "kind": "batch", "items": [2, 3] }),
+            json!({ "kind": "batch", "items": [4] }),
+            json!({ "kind": "done", "count": 5 }),
+        ]
+    );
+    assert!(!requests.is_running("r1"));
+}
+
+#[test]
+fn delivers_what_was_produced_before_an_error() {
+    let requests = Requests::new();
+    let (channel, messages) = collecting();
+
+    let err = block_on(
+        requests.stream("r1", &channel, batching(10), |items| async move {
+            items.send("first").await?;
+            Err(AppError::new(ErrorCode::InvalidInput, "bad item"))
+        }),
+    )
+    .unwrap_err();
+
+    assert_eq!(err.code, ErrorCode::InvalidInput);
+    let messages = messages.lock().unwrap();
+    assert_eq!(messages[0], json!({ "kind": "batch", "items": ["first"] }));
+    assert_eq!(messages[1]["kind"], "error");
+    assert_eq!(messages[1]["error"]["code"], "invalid_input");
+    assert_eq!(messages.len(), 2);
+}
+
+#[test]
+fn cancels_requests_by_id() {
+    let requests = Requests::new();
+    let (channel, messages) = collecting::<u32>();
+    let quick = Batching {
+        max_delay: Duration::ZERO,
+        ..batching(10)
+    };
+
+    let (streamed, again) = block_on(async {
+        tokio::join!(
+            requests.stream("r1", &channel, quick, |items| async move {
+                items.send(1).await?;
+                // Runs until cancelled.
+                std::future::pending::<()>().await;
+                Ok(())
+            }),
+            async {
+                while messages.lock().unwrap().is_empty() {
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                }
+                // The id stays taken until the request ends.
+                let (other, _) = collecting::<u32>();
+                let again = requests
+                    .stream("r1", &other, quick, |_| async { Ok(()) })
+                    .await;
+                requests.cancel("r1").unwrap();
+                again
+            }
+        )
+    });
+
+    assert_eq!(streamed.unwrap_err().code, ErrorCode::Cancelled);
+    assert_eq!(again.unwrap_err().code, ErrorCode::Conflict);
+    let messages = messages.lock().unwrap();
+    assert_eq!(messages.len(), 2);
+    assert_eq!(messages[1]["error"]["code"], "cancelled");
+
+    assert!(!requests.is_running("r1"));
+    assert_eq!(requests.cancel("r1").unwrap_err().code, ErrorCode::NotFound);
+}
+
+#[test]
+fn cancels_requests_by_the_id_they_were_started_with() {
+    let requests = Requests::new();
+    let (channel, messages) = collecting::<u32>();
+    // Trimmed and composed to "café" when the request starts.
+    let id = " cafe\u{301} ";
+
+    let streamed = block_on(async {
+        let (streamed, ()) = tokio::join!(
+            requests.stream(id, &channel, batching(10), |_| async {
+                std::future::pending::<()>().await;
+                Ok(())
+            }),
+            async {
+                while !requests.is_running(id) {
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                }
+                assert!(requests.is_running("caf\u{e9}"));
+                requests.cancel(id).unwrap();
+            }
+        );
+        streamed
+    });
+
+    assert_eq!(streamed.unwrap_err().code, ErrorCode::Cancelled);
+    assert_eq!(messages.lock().unwrap()[0]["error"]["code"], "cancelled");
+    assert!(!requests.is_running(id));
+}
+
+#[test]
+fn greet_many_streams_a_greeting_per_name() {
+    let app = TestApp::new();
+    let on_event = "__CHANNEL__:1";
+
+    assert_eq!(
+        app.invoke(
+            "greet_many",
+            json!({ "requestId": "a", "names": ["Ada", "Linus"], "onEvent": on_event })
+        ),
+        Ok(json!(2))
+    );
+    let err = app
+        .invoke(
+            "greet_many",
+            json!({ "requestId": "b", "names": ["Ada", ""], "onEvent": on_event }),
+        )
+        .unwrap_err();
+    assert_eq!(err["code"], "invalid_input");
+
+    let err = app
+        .invoke("cancel_request", json!({ "id": "a" }))
+        .unwrap_err();
+    assert_eq!(err["code"], "not_found");
+}
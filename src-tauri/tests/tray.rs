@@ -0,0 +1,96 @@
+#![cfg(desktop)]
+
+use std::path::PathBuf;
+
+use tauri_app_lib::i18n::{I18n, EMBEDDED_RESOURCES};
+use tauri_app_lib::menu::{MenuAction, MenuEntry};
+use tauri_app_lib::tray::{TrayAction, TrayModel};
+
+fn labels(entries: &[MenuEntry]) -> Vec<&str> {
+    entries
+        .iter()
+        .map(|entry| entry.label().unwrap_or("-"))
+        .collect()
+}
+
+#[test]
+fn actions_round_trip_through_menu_ids() {
+    for action in [TrayAction::ToggleMain, TrayAction::TogglePause] {
+        assert_eq!(TrayAction::parse(action.id()), Some(action));
+        assert_eq!(MenuAction::parse(action.id()), None);
+    }
+    // Items shared with the menu bar are left to it.
+    assert_eq!(TrayAction::parse(&MenuAction::Quit.id()), None);
+    let recent = MenuAction::OpenRecent(PathBuf::from("/home/ada/notes: draft.txt"));
+    assert_eq!(TrayAction::parse(&recent.id()), None);
+    assert_eq!(MenuAction::parse(&recent.id()), Some(recent));
+    assert_eq!(TrayAction::parse("unread"), None);
+}
+
+#[test]
+fn menu_follows_the_model() {
+    let i18n = I18n::new(EMBEDDED_RESOURCES, Some("en-US"));
+    let empty = TrayModel::default();
+    let entries = empty.entries(&i18n).unwrap();
+    assert_eq!(
+        labels(&entries),
+        [
+            "Show tauri-app",
+            "-",
+            "Recent Documents",
+            "-",
+            "No unread notifications",
+            "Pause Notifications",
+            "-",
+            "Quit",
+        ]
+    );
+    assert_eq!(
+        entries[2],
+        MenuEntry::Submenu {
+            label: "Recent Documents".into(),
+            items: vec![MenuEntry::Item {
+                id: "recent-empty".into(),
+                label: "No Recent Documents".into(),
+                enabled: false,
+                accelerator: None,
+            }],
+        }
+    );
+    assert_eq!(empty.tooltip(&i18n).unwrap(), "tauri-app");
+
+    let busy = TrayModel {
+        main_visible: true,
+        recent: vec!["/tmp/b.md".into(), "/tmp/a.txt".into()],
+        unread: 3,
+        paused: true,
+    };
+    let entries = busy.entries(&i18n).unwrap();
+    assert_eq!(labels(&entries)[0], "Hide tauri-app");
+    assert_eq!(labels(&entries)[4], "3 unread notifications");
+    let MenuEntry::Submenu { items, .. } = &entries[2] else {
+        panic!("expected the recent documents submenu");
+    };
+    assert_eq!(labels(items), ["b.md", "a.txt"]);
+    assert_eq!(
+        items[0],
+        MenuEntry::Item {
+            id: MenuAction::OpenRecent("/tmp/b.md".into()).id(),
+            label: "b.md".into(),
+            enabled: true,
+            accelerator: None,
+        }
+    );
+    assert_eq!(
+        entries[5],
+        MenuEntry::Check {
+            id: TrayAction::TogglePause.id().into(),
+            label: "Pause Notifications".into(),
+            checked: true,
+        }
+    );
+    assert_eq!(
+        busy.tooltip(&i18n).unwrap(),
+        "tauri-app: 3 unread notifications"
+    );
+}
@@ -0,0 +1,116 @@
+use serde_json::json;
+use tauri_app_lib::error::ErrorCode;
+use tauri_app_lib::validation::{TextRule, ValidationError, NAME};
+
+#[test]
+fn trims_and_normalizes() {
+    // "e" followed by a combining acute accent composes to a single "é".
+    assert_eq!(NAME.check("  Rene\u{301}  ").unwrap(), "Ren\u{e9}");
+}
+
+#[test]
+fn rejects_empty_input() {
+    assert_eq!(NAME.check(""), Err(ValidationError::Empty));
+    assert_eq!(NAME.check("   \t "), Err(ValidationError::Empty));
+}
+
+#[test]
+fn rejects_too_short_input() {
+    let rule = TextRule::new(3, 10);
+    assert_eq!(rule.check("ab"), Err(ValidationError::TooShort { min: 3 }));
+}
+
+#[test]
+fn rejects_too_long_input() {
+    let long = "a".repeat(65);
+    assert_eq!(NAME.check(&long), Err(ValidationError::TooLong { max: 64 }));
+
+    let huge = "a".repeat(1024 * 1024);
+    assert_eq!(NAME.check(&huge), Err(ValidationError::TooLong { max: 64 }));
+
+    // Only what is kept counts: surrounding whitespace is trimmed first.
+    let padded = format!("{}Rene{}", " ".repeat(300), " ".repeat(300));
+    assert_eq!(NAME.check(&padded).unwrap(), "Rene");
+    let rule = TextRule::new(1, 64).keep_whitespace();
+    assert_eq!(
+        rule.check(&padded),
+        Err(ValidationError::TooLong { max: 64 })
+    );
+}
+
+#[test]
+fn counts_characters_not_bytes() {
+    let name = "\u{e9}".repeat(64);
+    assert_eq!(NAME.check(&name).unwrap(), name);
+}
+
+#[test]
+fn counts_decomposed_input_after_normalizing() {
+    // NFD "한" is three conjoining jamo of three bytes each.
+    let syllable = "\u{1112}\u{1161}\u{11AB}";
+    assert_eq!(
+        NAME.check(&syllable.repeat(64)).unwrap(),
+        "\u{D55C}".repeat(64)
+    );
+    assert_eq!(
+        NAME.check(&syllable.repeat(65)),
+        Err(ValidationError::TooLong { max: 64 })
+    );
+}
+
+#[test]
+fn rejects_control_characters() {
+    assert_eq!(
+        NAME.check("Ada\u{0}Lovelace"),
+        Err(ValidationError::ControlCharacter { position: 3 })
+    );
+    assert_eq!(
+        NAME.check("Ada\nLovelace"),
+        Err(ValidationError::ControlCharacter { position: 3 })
+    );
+    assert!(TextRule::new(1, 64)
+        .allow_newlines()
+        .check("Ada\nLovelace")
+        .is_ok());
+}
+
+#[test]
+fn rejects_bidi_overrides() {
+    assert_eq!(
+        NAME.check("abc\u{202E}fed"),
+        Err(ValidationError::BidiControl { position: 3 })
+    );
+    assert_eq!(
+        NAME.check("\u{2066}x"),
+        Err(ValidationError::BidiControl { position: 0 })
+    );
+    // Left-to-right, right-to-left and Arabic letter marks.
+    for mark in ['\u{200E}', '\u{200F}', '\u{061C}'] {
+        assert_eq!(
+            NAME.check(&format!("ab{mark}c")),
+            Err(ValidationError::BidiControl { position: 2 }),
+            "{mark:?}"
+        );
+    }
+}
+
+#[test]
+fn reports_field_in_app_error() {
+    let err = NAME.apply("name", "").unwrap_err();
+
+    assert_eq!(err.code, ErrorCode::InvalidInput);
+    assert_eq!(
+        serde_json::to_value(&err).unwrap(),
+        json!({
+            "code": "invalid_input",
+            "message": "`name` must not be empty",
+            "details": { "field": "name", "reason": "empty" }
+        })
+    );
+
+    let err = NAME.apply("name", &"a".repeat(100)).unwrap_err();
+    assert_eq!(
+        err.details,
+        Some(json!({ "field": "name", "reason": "too_long", "max": 64 }))
+    );
+}
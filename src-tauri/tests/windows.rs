@@ -0,0 +1,203 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use common::TestApp;
+use serde_json::json;
+use tauri::{Listener, Manager};
+use tauri_app_lib::bindings;
+use tauri_app_lib::documents::Documents;
+use tauri_app_lib::error::ErrorCode;
+use tauri_app_lib::events::{AppEvent, NOTIFICATIONS_CHANGED};
+use tauri_app_lib::windows::{self, WindowManager, WindowRole};
+
+#[test]
+fn roles_follow_labels_and_limit_commands() {
+    assert_eq!(WindowRole::of("main"), Some(WindowRole::Main));
+    assert_eq!(WindowRole::of("document-12"), Some(WindowRole::Document));
+    assert_eq!(WindowRole::of("settings"), Some(WindowRole::Settings));
+    assert_eq!(WindowRole::of("about"), Some(WindowRole::About));
+    assert_eq!(WindowRole::of("document-"), None);
+    assert_eq!(WindowRole::of("document-x"), None);
+    assert_eq!(WindowRole::of("popup"), None);
+
+    assert!(WindowRole::Document.allows("run_command"));
+    assert!(WindowRole::Settings.allows("update_settings"));
+    assert!(!WindowRole::Settings.allows("run_command"));
+    assert!(WindowRole::About.allows("translate"));
+    assert!(!WindowRole::About.allows("update_settings"));
+    assert!(!WindowRole::About.allows("spawn_process"));
+}
+
+#[test]
+fn allowed_commands_are_registered() {
+    // A typo would quietly lock every restricted window out of a command.
+    let registered = bindings::typescript().unwrap();
+    for command in windows::COMMON_COMMANDS
+        .iter()
+        .chain(windows::SETTINGS_COMMANDS)
+    {
+        assert!(
+            registered.contains(&format!("TAURI_INVOKE(\"{command}\"")),
+            "`{command}` is not a registered command"
+        );
+    }
+}
+
+#[test]
+fn opens_singletons_once_and_documents_per_document() {
+    let app = TestApp::new();
+    let handle = app.app.handle();
+    let manager = app.state::<WindowManager>();
+
+    let settings = manager.open(handle, WindowRole::Settings, None).unwrap();
+    assert_eq!(settings.label, "settings");
+    assert_eq!(
+        manager.open(handle, WindowRole::Settings, None).unwrap(),
+        settings
+    );
+    let about = app
+        .invoke("open_window", json!({ "role": "about" }))
+        .unwrap();
+    assert_eq!(about["label"], "about");
+
+    let document = app.state::<Documents>().create();
+    let shown = manager
+        .open(handle, WindowRole::Document, Some(document.id))
+        .unwrap();
+    assert_eq!(shown.role, WindowRole::Document);
+    assert_eq!(
+        manager
+            .open(handle, WindowRole::Document, Some(document.id))
+            .unwrap(),
+        shown
+    );
+    let blank = manager.open(handle, WindowRole::Document, None).unwrap();
+    assert_ne!(blank.label, shown.label);
+    assert_eq!(manager.document_labels(document.id), [shown.label.as_str()]);
+
+    let err = manager
+        .open(handle, WindowRole::About, Some(document.id))
+        .unwrap_err();
+    assert_eq!(err.code, ErrorCode::InvalidInput);
+    let err = manager
+        .open(handle, WindowRole::Document, Some(999))
+        .unwrap_err();
+    assert_eq!(err.code, ErrorCode::NotFound);
+    let err = app
+        .invoke("open_window", json!({ "role": "main" }))
+        .unwrap_err();
+    assert_eq!(err["details"]["reason"], "main");
+
+    let listed = app.invoke("list_windows", json!({})).unwrap();
+    assert_eq!(listed.as_array().unwrap().len(), 4);
+    // Each role loads its own view rather than the main window's.
+    let route = |label: &str| {
+        let window = app.app.get_webview_window(label).unwrap();
+        window.url().unwrap().fragment().map(str::to_owned)
+    };
+    assert_eq!(route("settings").as_deref(), Some("/settings"));
+    assert_eq!(route("about").as_deref(), Some("/about"));
+    assert_eq!(route(&shown.label), None);
+}
+
+#[test]
+fn concurrent_opens_share_one_window() {
+    let app = TestApp::new();
+    let manager = app.state::<WindowManager>();
+    let handle = app.app.handle();
+    let document = app.state::<Documents>().create();
+
+    std::thread::scope(|scope| {
+        let opens: Vec<_> = (0..8)
+            .map(|n| {
+                let manager = &manager;
+                scope.spawn(move || match n % 2 {
+                    0 => manager.open(handle, WindowRole::Settings, None),
+                    _ => manager.open(handle, WindowRole::Document, Some(document.id)),
+                })
+            })
+            .collect();
+        for open in opens {
+            open.join().unwrap().unwrap();
+        }
+    });
+    assert_eq!(manager.labels(WindowRole::Settings), ["settings"]);
+    assert_eq!(manager.document_labels(document.id).len(), 1);
+}
+
+#[test]
+fn rejects_commands_outside_the_window_role() {
+    let app = TestApp::new();
+    let manager = app.state::<WindowManager>();
+    manager
+        .open(app.app.handle(), WindowRole::About, None)
+        .unwrap();
+    let about = app.app.get_webview_window("about").unwrap();
+
+    let err = app
+        .invoke_from(&about, "run_command", json!({ "command": "ls" }))
+        .unwrap_err();
+    assert_eq!(err["code"], "permission_denied");
+    assert_eq!(err["details"]["reason"], "window");
+    assert_eq!(
+        app.invoke_from(
+            &about,
+            "translate",
+            json!({ "key": "window-title-about", "locale": "en-US" })
+        ),
+        Ok(json!("About"))
+    );
+
+    // Windows the app does not know about may call nothing.
+    let popup = tauri::WebviewWindowBuilder::new(&app.app, "popup", Default::default())
+        .build()
+        .unwrap();
+    let err = app
+        .invoke_from(&popup, "get_settings", json!({}))
+        .unwrap_err();
+    assert_eq!(err["code"], "permission_denied");
+}
+
+#[test]
+fn routes_events_to_windows_by_role_and_document() {
+    let app = TestApp::new();
+    let handle = app.app.handle();
+    let manager = app.state::<WindowManager>();
+    manager.track("main", None).unwrap();
+    manager.open(handle, WindowRole::Settings, None).unwrap();
+    let document = app.state::<Documents>().create();
+    let shown = manager
+        .open(handle, WindowRole::Document, Some(document.id))
+        .unwrap();
+
+    let received = Arc::new(Mutex::new(Vec::new()));
+    for (label, window) in app.app.webview_windows() {
+        let received = received.clone();
+        window.listen(NOTIFICATIONS_CHANGED, move |event| {
+            received
+                .lock()
+                .unwrap()
+                .push((label.clone(), event.payload().to_string()));
+        });
+    }
+
+    let unread = |unread| AppEvent::NotificationsChanged { unread };
+    windows::emit_to_role(handle, WindowRole::Settings, &unread(1)).unwrap();
+    windows::emit_to_document(handle, document.id, &unread(2)).unwrap();
+    let mut received = received.lock().unwrap().clone();
+    received.sort();
+    assert_eq!(
+        received,
+        [
+            (
+                shown.label.clone(),
+                r#"{"version":1,"payload":{"unread":2}}"#.to_string()
+            ),
+            (
+                "settings".to_string(),
+                r#"{"version":1,"payload":{"unread":1}}"#.to_string()
+            ),
+        ]
+    );
+}
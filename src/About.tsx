@@ -0,0 +1,25 @@
+import { useEffect, useState } from "react";
+import { getName, getTauriVersion, getVersion } from "@tauri-apps/api/app";
+import "./App.css";
+
+// Calls no app commands; the about window may only use `COMMON_COMMANDS`
+// in src-tauri/src/windows/mod.rs.
+function About() {
+  const [about, setAbout] = useState({ name: "", version: "", tauri: "" });
+
+  useEffect(() => {
+    Promise.all([getName(), getVersion(), getTauriVersion()]).then(([name, version, tauri]) =>
+      setAbout({ name, version, tauri }),
+    );
+  }, []);
+
+  return (
+    <main className="container">
+      <h1>{about.name}</h1>
+      <p>Version {about.version}</p>
+      <p>Built with Tauri {about.tauri}</p>
+    </main>
+  );
+}
+
+export default About;
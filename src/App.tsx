@@ -1,15 +1,25 @@
-import { useState } from "react";
+import { useEffect, useState } from "react";
 import reactLogo from "./assets/react.svg";
-import { invoke } from "@tauri-apps/api/core";
+import { commands } from "./bindings";
+import { onAppEvent } from "./events";
 import "./App.css";
 
 function App() {
   const [greetMsg, setGreetMsg] = useState("");
   const [name, setName] = useState("");
+  const [unread, setUnread] = useState(0);
+
+  useEffect(() => {
+    const unlisten = onAppEvent("notifications-changed", ({ unread }) => setUnread(unread));
+    return () => {
+      unlisten.then((stop) => stop());
+    };
+  }, []);
 
   async function greet() {
     // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
-    setGreetMsg(await invoke("greet", { name }));
+    const result = await commands.greet(name, null);
+    setGreetMsg(result.status === "ok" ? result.data : result.error.message);
   }
 
   return (
@@ -44,6 +54,7 @@ function App() {
         <button type="submit">Greet</button>
       </form>
       <p>{greetMsg}</p>
+      {unread > 0 && <p>{unread} unread notifications</p>}
     </main>
   );
 }
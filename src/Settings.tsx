@@ -0,0 +1,81 @@
+import { useEffect, useState } from "react";
+import { commands, type JsonValue, type Settings as SettingsValue } from "./bindings";
+import { onAppEvent } from "./events";
+import "./App.css";
+
+// Only calls commands the settings window may; see `SETTINGS_COMMANDS` in
+// src-tauri/src/windows/mod.rs.
+function Settings() {
+  const [settings, setSettings] = useState<SettingsValue | null>(null);
+  const [status, setStatus] = useState("");
+
+  useEffect(() => {
+    commands.getSettings().then((result) => {
+      if (result.status === "ok") {
+        setSettings(result.data);
+      } else {
+        setStatus(result.error.message);
+      }
+    });
+    const unlisten = onAppEvent("settings-changed", setSettings);
+    return () => {
+      unlisten.then((stop) => stop());
+    };
+  }, []);
+
+  async function update(patch: JsonValue) {
+    const result = await commands.updateSettings(patch);
+    if (result.status === "ok") {
+      setSettings(result.data);
+      setStatus("");
+    } else {
+      setStatus(result.error.message);
+    }
+  }
+
+  async function clearHistory() {
+    const result = await commands.clearHistory();
+    setStatus(result.status === "ok" ? `Removed ${result.data} greetings` : result.error.message);
+  }
+
+  if (settings === null) {
+    return <main className="container">{status}</main>;
+  }
+
+  return (
+    <main className="container">
+      <h1>Settings</h1>
+
+      <div className="row">
+        <label htmlFor="theme">Theme</label>
+        <select
+          id="theme"
+          value={settings.theme}
+          onChange={(e) => update({ theme: e.currentTarget.value })}
+        >
+          <option value="system">System</option>
+          <option value="light">Light</option>
+          <option value="dark">Dark</option>
+        </select>
+      </div>
+
+      <div className="row">
+        <label htmlFor="history">Greetings to keep</label>
+        <input
+          id="history"
+          type="number"
+          min={0}
+          value={settings.history.maxEntries}
+          onChange={(e) => update({ history: { maxEntries: e.currentTarget.valueAsNumber } })}
+        />
+        <button type="button" onClick={clearHistory}>
+          Clear history
+        </button>
+      </div>
+
+      <p>{status}</p>
+    </main>
+  );
+}
+
+export default Settings;
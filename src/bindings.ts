@@ -0,0 +1,960 @@
+// From src-tauri/src/bindings.rs; regenerate with `cargo run --bin export_types`.
+// @ts-nocheck
+/* eslint-disable */
+// This file was generated by [tauri-specta](https://github.com/oscartbeaumont/tauri-specta). Do not edit this file manually.
+
+/** user-defined commands **/
+
+
+export const commands = {
+async greet(name: string, locale: string | null) : Promise<Result<string, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("greet", { name, locale }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Streams a greeting for each of `names`, in order, over `on_event`.
+ * Cancel it with `cancel_request(request_id)`. Unlike `greet`, nothing is
+ * recorded in the history.
+ */
+async greetMany(requestId: string, names: string[], locale: string | null, onEvent: TAURI_CHANNEL<StreamMessage<string>>) : Promise<Result<number, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("greet_many", { requestId, names, locale, onEvent }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async cancelRequest(id: string) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("cancel_request", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async translate(key: string, args: Partial<{ [key in string]: ArgValue }> | null, locale: string | null) : Promise<Result<string, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("translate", { key, args, locale }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getSettings() : Promise<Result<Settings, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_settings") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateSettings(patch: JsonValue) : Promise<Result<Settings, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_settings", { patch }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Records a frontend `console` message under [`FRONTEND_TARGET`].
+ */
+async logFromFrontend(level: LogLevel, message: string, context: JsonValue | null) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("log_from_frontend", { level, message, context }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async readLogs(filter: LogFilter | null, since: string | null, limit: number | null) : Promise<Result<LogEntry[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("read_logs", { filter, since, limit }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getPendingCrashReports() : Promise<Result<CrashReport[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_pending_crash_reports") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async discardCrashReport(id: string) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("discard_crash_report", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async scheduleNotification(notification: Notification, trigger: Trigger) : Promise<Result<ScheduledNotification, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("schedule_notification", { notification, trigger }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listScheduledNotifications() : Promise<Result<ScheduledNotification[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_scheduled_notifications") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async rescheduleNotification(id: number, trigger: Trigger) : Promise<Result<ScheduledNotification, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("reschedule_notification", { id, trigger }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async cancelScheduledNotification(id: number) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("cancel_scheduled_notification", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Shows a notification on behalf of the frontend, so it is subject to quiet
+ * hours and recorded like any other.
+ */
+async sendNotification(notification: Notification) : Promise<Result<NotificationRecord, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("send_notification", { notification }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Shows a template on behalf of the frontend.
+ */
+async sendTemplateNotification(template: string, args: Partial<{ [key in string]: ArgValue }> | null) : Promise<Result<NotificationRecord, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("send_template_notification", { template, args }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Names of the commands the frontend may run.
+ */
+async listAllowedCommands() : Promise<Result<string[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_allowed_commands") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Runs an allowlisted command, streaming its output over `on_event`.
+ */
+async runCommand(command: string, args: string[] | null, onEvent: TAURI_CHANNEL<ProcessEvent>) : Promise<Result<ProcessExit, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("run_command", { command, args, onEvent }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async cancelCommand(id: number) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("cancel_command", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async spawnProcess(name: string, command: string, args: string[] | null) : Promise<Result<ProcessInfo, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("spawn_process", { name, command, args }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listProcesses() : Promise<Result<ProcessInfo[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_processes") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getProcessOutput(name: string) : Promise<Result<OutputLine[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_process_output", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async restartProcess(name: string) : Promise<Result<ProcessInfo, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("restart_process", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async killProcess(name: string) : Promise<Result<ProcessInfo, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("kill_process", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Opens `url` in the user's browser or mail client if [`LinkPolicy`]
+ * allows it. Returns `false` if the user declined to open an untrusted
+ * link.
+ */
+async openExternal(url: string) : Promise<Result<boolean, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("open_external", { url }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async openFileDialog(options: OpenDialogOptions | null) : Promise<Result<string[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("open_file_dialog", { options }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async saveFileDialog(options: SaveDialogOptions | null) : Promise<Result<string | null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_file_dialog", { options }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listRecentFiles() : Promise<Result<RecentFile[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_recent_files") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Returns how many entries were removed.
+ */
+async clearRecentFiles() : Promise<Result<number, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("clear_recent_files") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async newDocument() : Promise<Result<Document, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("new_document") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Opens `path`, which must be one of the recent files, or else the file the
+ * user picks. Returns `None` if the user cancelled.
+ */
+async openDocument(path: string | null) : Promise<Result<Document | null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("open_document", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listDocuments() : Promise<Result<Document[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_documents") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getDocument(id: number) : Promise<Result<Document, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_document", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async editDocument(id: number, content: string) : Promise<Result<Document, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("edit_document", { id, content }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Saves the document, asking for a path if it is untitled. Returns `None`
+ * if the user cancelled.
+ */
+async saveDocument(id: number, overwrite: boolean | null) : Promise<Result<Document | null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_document", { id, overwrite }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async saveDocumentAs(id: number) : Promise<Result<Document | null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_document_as", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async revertDocument(id: number) : Promise<Result<Document, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("revert_document", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async closeDocument(id: number, discard: boolean | null) : Promise<Result<Document, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("close_document", { id, discard }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Opens a settings, about or document window, or focuses the open one.
+ */
+async openWindow(role: WindowRole, document: number | null) : Promise<Result<WindowInfo, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("open_window", { role, document }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listWindows() : Promise<Result<WindowInfo[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_windows") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listNotifications(query: NotificationQuery | null) : Promise<Result<NotificationPage, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_notifications", { query }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listNotificationGroups() : Promise<Result<NotificationGroup[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_notification_groups") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Marks every notification as seen. Returns how many were unread.
+ */
+async markNotificationsRead() : Promise<Result<number, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("mark_notifications_read") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async createContact(contact: NewContact) : Promise<Result<Contact, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_contact", { contact }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getContact(id: number) : Promise<Result<Contact, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_contact", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listContacts(query: string | null) : Promise<Result<Contact[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_contacts", { query }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateContact(id: number, patch: ContactPatch) : Promise<Result<Contact, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_contact", { id, patch }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteContact(id: number) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_contact", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listGreetings(query: GreetingQuery | null) : Promise<Result<GreetingPage, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_greetings", { query }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async searchGreetings(prefix: string, limit: number | null) : Promise<Result<Greeting[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("search_greetings", { prefix, limit }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteGreeting(id: number) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_greeting", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async clearHistory() : Promise<Result<number, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("clear_history") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+}
+}
+
+/** user-defined events **/
+
+
+
+/** user-defined constants **/
+
+
+
+/** user-defined types **/
+
+/**
+ * The error type returned by every `#[tauri::command]`.
+ * 
+ * It reaches the frontend as `{ code, message, details? }`, so the React side
+ * can branch on `code` while showing `message` to the user.
+ */
+export type AppError = { code: ErrorCode; message: string; details?: JsonValue | null }
+/**
+ * A value passed into a Fluent placeable from the frontend.
+ */
+export type ArgValue = number | string
+/**
+ * What the user chose for one category of notifications.
+ */
+export type CategoryPreferences = { 
+/**
+ * Disabled categories are recorded in the history but never shown.
+ */
+enabled: boolean; 
+/**
+ * Play the platform's notification sound.
+ */
+sound: boolean; priority: Priority }
+export type Contact = { id: number; name: string; email: string | null; notes: string | null; createdAt: string; updatedAt: string }
+/**
+ * Fields to change on an existing contact. `None` leaves a field as is; an
+ * empty string clears an optional field.
+ */
+export type ContactPatch = { name: string | null; email: string | null; notes: string | null }
+/**
+ * Everything recorded about a panic. Written as `<id>.json` to the crash
+ * directory, where it stays until saved or discarded.
+ */
+export type CrashReport = { id: string; createdAt: string; appVersion: string; tauriVersion: string; os: OsInfo; 
+/**
+ * Name of the panicking thread, if it had one.
+ */
+thread: string | null; 
+/**
+ * Whether that was the main thread, so the panic ended the app.
+ * Earlier versions only reported those.
+ */
+mainThread?: boolean; message: string; 
+/**
+ * `file:line:column` of the panic.
+ */
+location: string | null; backtrace: string; 
+/**
+ * Empty if another thread was logging at the time.
+ */
+recentLogs: LogEntry[]; 
+/**
+ * Labels of the windows open at the time; empty if one was just
+ * opening or closing.
+ */
+windows: string[] }
+/**
+ * What happened to a notification the app tried to show.
+ */
+export type DeliveryStatus = "delivered" | 
+/**
+ * The notification plugin reported an error; see `error`.
+ */
+"failed" | 
+/**
+ * Held back by quiet hours, waiting for the next digest.
+ */
+"deferred" | 
+/**
+ * Dropped by quiet hours.
+ */
+"suppressed" | 
+/**
+ * Dropped because the user turned its category off.
+ */
+"disabled" | 
+/**
+ * Deferred, then shown as part of the digest `digest_id`.
+ */
+"digested"
+/**
+ * A text document open in the app.
+ */
+export type Document = { id: number; 
+/**
+ * `None` until the document is first saved.
+ */
+path: string | null; content: string; encoding: Encoding; 
+/**
+ * Whether `content` has changes that are not on disk.
+ */
+dirty: boolean; 
+/**
+ * Modification time of the file when it was last read or written here.
+ */
+modifiedAt: string | null }
+/**
+ * How a document's text is stored on disk. Kept when saving, so a file is
+ * written back the way it was read.
+ */
+export type Encoding = "utf-8" | 
+/**
+ * UTF-8 with a byte order mark.
+ */
+"utf-8-bom" | "utf-16le" | "utf-16be"
+/**
+ * Machine-readable error codes shared with the frontend.
+ * 
+ * These are serialized in `snake_case` and are part of the IPC contract, so
+ * existing variants must never be renamed; add new ones instead.
+ */
+export type ErrorCode = "internal" | "io" | "json" | "tauri" | "dialog" | "shell" | "invalid_input" | "not_found" | "database" | "notification" | "permission_denied" | "cancelled" | "timeout" | "conflict"
+/**
+ * A named group of extensions offered by a file dialog, e.g.
+ * `{ name: "Text", extensions: ["txt", "md"] }`.
+ */
+export type FileFilter = { name: string; 
+/**
+ * Without the leading dot; `*` matches any file.
+ */
+extensions: string[] }
+export type Greeting = { id: number; name: string; locale: string; 
+/**
+ * Label of the window the greeting was sent from.
+ */
+window: string; createdAt: string }
+export type GreetingPage = { items: Greeting[]; 
+/**
+ * Number of greetings matching the filters, across all pages.
+ */
+total: number; offset: number; limit: number }
+/**
+ * Filters and paging for [`list`]. Every filter is optional.
+ */
+export type GreetingQuery = { offset: number; 
+/**
+ * Defaults to [`DEFAULT_PAGE_SIZE`], capped at [`MAX_PAGE_SIZE`].
+ */
+limit: number | null; 
+/**
+ * Case-insensitive name prefix.
+ */
+name: string | null; locale: string | null; window: string | null; since: string | null; until: string | null }
+/**
+ * How much greeting history to keep.
+ */
+export type HistorySettings = { maxEntries: number; 
+/**
+ * Entries older than this are pruned; `None` keeps them regardless of age.
+ */
+maxAgeDays: number | null }
+export type JsonValue = null | boolean | number | string | JsonValue[] | Partial<{ [key in string]: JsonValue }>
+/**
+ * One line of a log file.
+ */
+export type LogEntry = { timestamp: string; level: LogLevel; 
+/**
+ * Module path of the code that logged, or [`FRONTEND_TARGET`].
+ */
+target: string; message: string; 
+/**
+ * Structured fields other than the message.
+ */
+fields: Partial<{ [key in string]: JsonValue }> }
+/**
+ * Which entries [`read`] returns. Every filter is optional.
+ */
+export type LogFilter = { 
+/**
+ * Minimum severity.
+ */
+level: LogLevel | null; 
+/**
+ * Target prefix, e.g. `tauri_app_lib::db` or `frontend`.
+ */
+target: string | null; 
+/**
+ * Case-insensitive substring of the message.
+ */
+text: string | null }
+/**
+ * Ordered from most to least verbose.
+ */
+export type LogLevel = "trace" | "debug" | "info" | "warn" | "error"
+/**
+ * Log verbosity, stored in the settings file. `RUST_LOG`, when set, takes
+ * precedence.
+ */
+export type LogSettings = { level: LogLevel; 
+/**
+ * Per-target overrides, e.g. `{ "tauri_app_lib::db": "debug" }`.
+ */
+modules: Partial<{ [key in string]: LogLevel }> }
+export type NewContact = { name: string; email: string | null; notes: string | null }
+/**
+ * What a notification shows.
+ */
+export type Notification = { title: string; body: string | null; 
+/**
+ * Groups related notifications, in the history and on platforms that
+ * thread them.
+ */
+category?: string | null; 
+/**
+ * Shown even during quiet hours.
+ */
+urgent?: boolean }
+/**
+ * Notifications sharing a category, summarized for a grouped view.
+ */
+export type NotificationGroup = { 
+/**
+ * `None` for notifications sent without a category.
+ */
+category: string | null; count: number; latestAt: string }
+export type NotificationPage = { items: NotificationRecord[]; 
+/**
+ * Number of notifications matching the filters, across all pages.
+ */
+total: number; offset: number; limit: number }
+/**
+ * Filters and paging for [`list`]. Every filter is optional.
+ */
+export type NotificationQuery = { offset: number; 
+/**
+ * Defaults to [`DEFAULT_PAGE_SIZE`], capped at [`MAX_PAGE_SIZE`].
+ */
+limit: number | null; category: string | null; status: DeliveryStatus | null; since: string | null; until: string | null }
+/**
+ * A notification in the history.
+ */
+export type NotificationRecord = ({ title: string; body: string | null; 
+/**
+ * Groups related notifications, in the history and on platforms that
+ * thread them.
+ */
+category?: string | null; 
+/**
+ * Shown even during quiet hours.
+ */
+urgent?: boolean }) & { id: number; status: DeliveryStatus; error: string | null; digestId: number | null; createdAt: string }
+export type NotificationSettings = { 
+/**
+ * Daily do-not-disturb window, in local time.
+ */
+quietHours: QuietHours | null; 
+/**
+ * Do not disturb until turned off: notifications are deferred as during
+ * quiet hours.
+ */
+paused: boolean; 
+/**
+ * Preferences by category; categories without an entry use the
+ * defaults.
+ */
+categories: Partial<{ [key in string]: CategoryPreferences }> }
+export type OpenDialogOptions = { title: string | null; filters: FileFilter[]; 
+/**
+ * Folder the dialog starts in.
+ */
+directory: string | null; multiple: boolean }
+export type OsInfo = { name: string; family: string; arch: string }
+export type OutputLine = { stream: OutputStream; line: string; at: string }
+export type OutputStream = "stdout" | "stderr"
+/**
+ * How a notification competes for the user's attention.
+ */
+export type Priority = 
+/**
+ * Dropped rather than deferred during quiet hours.
+ */
+"low" | "normal" | 
+/**
+ * Shown even during quiet hours, like an urgent notification.
+ */
+"high"
+/**
+ * Progress of a run, streamed while it happens.
+ */
+export type ProcessEvent = 
+/**
+ * Sent first; `id` is what [`ProcessRunner::cancel`] takes.
+ */
+{ event: "started"; id: number; pid: number | null } | 
+/**
+ * A line of output, without its line ending. Invalid UTF-8 is replaced.
+ */
+{ event: "stdout"; line: string } | { event: "stderr"; line: string }
+/**
+ * How a run ended.
+ */
+export type ProcessExit = { id: number; 
+/**
+ * `None` if the process was killed by a signal.
+ */
+code: number | null; success: boolean }
+/**
+ * A managed process, as of when it was looked up.
+ */
+export type ProcessInfo = { 
+/**
+ * Unique among managed processes; chosen by whoever spawned it.
+ */
+name: string; 
+/**
+ * The allowlisted command and its arguments.
+ */
+command: string; args: string[]; pid: number | null; state: ProcessState; 
+/**
+ * `None` while running, or if the process was killed by a signal.
+ */
+exitCode: number | null; startedAt: string; exitedAt: string | null; 
+/**
+ * Seconds from start until now, or until exit.
+ */
+uptimeSecs: number; restarts: number }
+export type ProcessState = "running" | 
+/**
+ * Exited on its own; see `exit_code`.
+ */
+"exited" | 
+/**
+ * Stopped by [`ProcessManager::kill`], a restart or app exit.
+ */
+"killed"
+/**
+ * A daily do-not-disturb window, which may span midnight. `start == end`
+ * is an empty window.
+ */
+export type QuietHours = { start: string; end: string; mode?: QuietMode }
+/**
+ * What happens to non-urgent notifications during quiet hours.
+ */
+export type QuietMode = 
+/**
+ * Hold them for a digest once quiet hours end.
+ */
+"defer" | 
+/**
+ * Drop them; they are still recorded in the history.
+ */
+"suppress"
+export type RecentFile = { path: string; openedAt: string }
+export type SaveDialogOptions = { title: string | null; filters: FileFilter[]; directory: string | null; 
+/**
+ * Suggested file name.
+ */
+fileName: string | null }
+/**
+ * A notification waiting to be shown by the scheduler.
+ */
+export type ScheduledNotification = ({ title: string; body: string | null; 
+/**
+ * Groups related notifications, in the history and on platforms that
+ * thread them.
+ */
+category?: string | null; 
+/**
+ * Shown even during quiet hours.
+ */
+urgent?: boolean }) & { id: number; trigger: Trigger; nextRun: string; createdAt: string; error: string | null }
+export type Settings = { 
+/**
+ * Overrides the OS locale when set.
+ */
+locale: string | null; theme: Theme; history: HistorySettings; logging: LogSettings; notifications: NotificationSettings; 
+/**
+ * Closing the main window hides it in the tray instead of quitting.
+ */
+closeToTray: boolean }
+/**
+ * What a streaming command sends over its channel: any number of batches,
+ * then exactly one of `done` or `error`.
+ */
+export type StreamMessage<T> = 
+/**
+ * The next items, in order.
+ */
+{ kind: "batch"; items: T[] } | 
+/**
+ * Every item was sent; `count` is how many.
+ */
+{ kind: "done"; count: number } | 
+/**
+ * The stream failed or was cancelled. Items already sent stand.
+ */
+{ kind: "error"; error: AppError }
+export type TAURI_CHANNEL<TSend> = null
+export type Theme = "system" | "light" | "dark"
+/**
+ * When a scheduled notification fires.
+ */
+export type Trigger = 
+/**
+ * Once, at `at`.
+ */
+{ kind: "at"; at: string } | 
+/**
+ * Once, `seconds` after being scheduled.
+ */
+{ kind: "after"; seconds: number } | 
+/**
+ * Every time a [`Cron`] expression matches, in local time unless `utc`
+ * is set.
+ */
+{ kind: "cron"; expression: string; utc?: boolean }
+/**
+ * A window opened through the [`WindowManager`].
+ */
+export type WindowInfo = { label: string; role: WindowRole; 
+/**
+ * The document shown by a document window.
+ */
+document: number | null }
+/**
+ * What a window is for, which decides how it is created and which app
+ * commands it may call. Plugin permissions are granted per role by the
+ * files in `capabilities/`.
+ */
+export type WindowRole = "main" | "document" | "settings" | "about"
+
+/** tauri-specta globals **/
+
+import {
+	invoke as TAURI_INVOKE,
+	Channel as TAURI_CHANNEL,
+} from "@tauri-apps/api/core";
+import * as TAURI_API_EVENT from "@tauri-apps/api/event";
+import { type WebviewWindow as __WebviewWindow__ } from "@tauri-apps/api/webviewWindow";
+
+type __EventObj__<T> = {
+	listen: (
+		cb: TAURI_API_EVENT.EventCallback<T>,
+	) => ReturnType<typeof TAURI_API_EVENT.listen<T>>;
+	once: (
+		cb: TAURI_API_EVENT.EventCallback<T>,
+	) => ReturnType<typeof TAURI_API_EVENT.once<T>>;
+	emit: null extends T
+		? (payload?: T) => ReturnType<typeof TAURI_API_EVENT.emit>
+		: (payload: T) => ReturnType<typeof TAURI_API_EVENT.emit>;
+};
+
+export type Result<T, E> =
+	| { status: "ok"; data: T }
+	| { status: "error"; error: E };
+
+function __makeEvents__<T extends Record<string, any>>(
+	mappings: Record<keyof T, string>,
+) {
+	return new Proxy(
+		{} as unknown as {
+			[K in keyof T]: __EventObj__<T[K]> & {
+				(handle: __WebviewWindow__): __EventObj__<T[K]>;
+			};
+		},
+		{
+			get: (_, event) => {
+				const name = mappings[event as keyof T];
+
+				return new Proxy((() => {}) as any, {
+					apply: (_, __, [window]: [__WebviewWindow__]) => ({
+						listen: (arg: any) => window.listen(name, arg),
+						once: (arg: any) => window.once(name, arg),
+						emit: (arg: any) => window.emit(name, arg),
+					}),
+					get: (_, command: keyof __EventObj__<any>) => {
+						switch (command) {
+							case "listen":
+								return (arg: any) => TAURI_API_EVENT.listen(name, arg);
+							case "once":
+								return (arg: any) => TAURI_API_EVENT.once(name, arg);
+							case "emit":
+								return (arg: any) => TAURI_API_EVENT.emit(name, arg);
+						}
+					},
+				});
+			},
+		},
+	);
+}
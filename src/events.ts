@@ -0,0 +1,192 @@
+// From src-tauri/src/events.rs; regenerate with `cargo run --bin export_types`.
+/* eslint-disable */
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+// This file has been generated by Specta. DO NOT EDIT.
+
+/**
+ * Everything the backend tells the frontend about. Each variant is emitted
+ * under its own name, with its payload wrapped in an [`Envelope`].
+ */
+export type AppEvent = 
+/**
+ * The settings after an update.
+ */
+{ event: "settings-changed"; payload: Settings } | { event: "notifications-changed"; payload: { 
+/**
+ * Delivered notifications the user has not seen yet.
+ */
+unread: number } } | { event: "recent-files-changed" } | 
+/**
+ * A document was opened or closed, or gained or lost unsaved changes.
+ */
+{ event: "documents-changed" } | 
+/**
+ * A managed process started, exited or was killed.
+ */
+{ event: "process-status"; payload: ProcessInfo }
+
+/**
+ * What the user chose for one category of notifications.
+ */
+export type CategoryPreferences = { 
+/**
+ * Disabled categories are recorded in the history but never shown.
+ */
+enabled: boolean; 
+/**
+ * Play the platform's notification sound.
+ */
+sound: boolean; priority: Priority }
+
+/**
+ * How much greeting history to keep.
+ */
+export type HistorySettings = { maxEntries: number; 
+/**
+ * Entries older than this are pruned; `None` keeps them regardless of age.
+ */
+maxAgeDays: number | null }
+
+/**
+ * Ordered from most to least verbose.
+ */
+export type LogLevel = "trace" | "debug" | "info" | "warn" | "error"
+
+/**
+ * Log verbosity, stored in the settings file. `RUST_LOG`, when set, takes
+ * precedence.
+ */
+export type LogSettings = { level: LogLevel; 
+/**
+ * Per-target overrides, e.g. `{ "tauri_app_lib::db": "debug" }`.
+ */
+modules: Partial<{ [key in string]: LogLevel }> }
+
+export type NotificationSettings = { 
+/**
+ * Daily do-not-disturb window, in local time.
+ */
+quietHours: QuietHours | null; 
+/**
+ * Do not disturb until turned off: notifications are deferred as during
+ * quiet hours.
+ */
+paused: boolean; 
+/**
+ * Preferences by category; categories without an entry use the
+ * defaults.
+ */
+categories: Partial<{ [key in string]: CategoryPreferences }> }
+
+/**
+ * How a notification competes for the user's attention.
+ */
+export type Priority = 
+/**
+ * Dropped rather than deferred during quiet hours.
+ */
+"low" | "normal" | 
+/**
+ * Shown even during quiet hours, like an urgent notification.
+ */
+"high"
+
+/**
+ * A managed process, as of when it was looked up.
+ */
+export type ProcessInfo = { 
+/**
+ * Unique among managed processes; chosen by whoever spawned it.
+ */
+name: string; 
+/**
+ * The allowlisted command and its arguments.
+ */
+command: string; args: string[]; pid: number | null; state: ProcessState; 
+/**
+ * `None` while running, or if the process was killed by a signal.
+ */
+exitCode: number | null; startedAt: string; exitedAt: string | null; 
+/**
+ * Seconds from start until now, or until exit.
+ */
+uptimeSecs: number; restarts: number }
+
+export type ProcessState = "running" | 
+/**
+ * Exited on its own; see `exit_code`.
+ */
+"exited" | 
+/**
+ * Stopped by [`ProcessManager::kill`], a restart or app exit.
+ */
+"killed"
+
+/**
+ * A daily do-not-disturb window, which may span midnight. `start == end`
+ * is an empty window.
+ */
+export type QuietHours = { start: string; end: string; mode?: QuietMode }
+
+/**
+ * What happens to non-urgent notifications during quiet hours.
+ */
+export type QuietMode = 
+/**
+ * Hold them for a digest once quiet hours end.
+ */
+"defer" | 
+/**
+ * Drop them; they are still recorded in the history.
+ */
+"suppress"
+
+export type Settings = { 
+/**
+ * Overrides the OS locale when set.
+ */
+locale: string | null; theme: Theme; history: HistorySettings; logging: LogSettings; notifications: NotificationSettings; 
+/**
+ * Closing the main window hides it in the tray instead of quitting.
+ */
+closeToTray: boolean }
+
+export type Theme = "system" | "light" | "dark"
+
+/** The payload version each event was generated with. */
+export const EVENT_VERSIONS = {
+  "settings-changed": 1,
+  "notifications-changed": 1,
+  "recent-files-changed": 1,
+  "documents-changed": 1,
+  "process-status": 1,
+} as const;
+
+export type AppEventName = AppEvent["event"];
+
+export type AppEventPayload<E extends AppEventName> =
+  Extract<AppEvent, { event: E }> extends { payload: infer P } ? P : null;
+
+/** What the backend emits: a payload and the version of its shape. */
+export type Envelope<E extends AppEventName> = {
+  version: number;
+  payload: AppEventPayload<E>;
+};
+
+/**
+ * Calls `handler` with the payload of every `event` the backend emits.
+ * Payloads of another version than this file was generated with are
+ * dropped with a warning rather than misread.
+ */
+export function onAppEvent<E extends AppEventName>(
+  event: E,
+  handler: (payload: AppEventPayload<E>) => void,
+): Promise<UnlistenFn> {
+  return listen<Envelope<E>>(event, ({ payload }) => {
+    if (payload.version !== EVENT_VERSIONS[event]) {
+      console.warn(`ignoring ${event} v${payload.version}; expected v${EVENT_VERSIONS[event]}`);
+      return;
+    }
+    handler(payload.payload);
+  });
+}
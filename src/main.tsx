@@ -1,9 +1,53 @@
 import React from "react";
 import ReactDOM from "react-dom/client";
+import About from "./About";
 import App from "./App";
+import { commands } from "./bindings";
+import Settings from "./Settings";
+
+// Forward console output to the backend log files; see `log_from_frontend`
+// in src-tauri/src/logging.rs, which rejects empty messages, ones longer
+// than this many characters and ones with control characters other than
+// line breaks and tabs, or with bidirectional controls.
+const MAX_MESSAGE_CHARS = 8192;
+// Terminal colour and cursor sequences, e.g. from libraries that style
+// their console output.
+const ANSI_ESCAPES = /\x1b\[[0-?]*[ -/]*[@-~]/g;
+// The characters `TextRule::check` in src-tauri/src/validation.rs rejects.
+const REJECTED_CHARS = /[\x00-\x08\x0b\x0c\x0e-\x1f\x7f-\x9f\u061c\u200e\u200f\u202a-\u202e\u2066-\u2069]/g;
+const levels = { debug: "debug", log: "info", info: "info", warn: "warn", error: "error" } as const;
+for (const [method, level] of Object.entries(levels)) {
+  const original = console[method as keyof typeof levels].bind(console);
+  console[method as keyof typeof levels] = (...args: unknown[]) => {
+    original(...args);
+    const message = args
+      .map((arg) => (arg instanceof Error ? (arg.stack ?? arg.message) : String(arg)))
+      .join(" ")
+      .replace(ANSI_ESCAPES, "")
+      .replace(REJECTED_CHARS, "");
+    if (message.trim() === "") {
+      return;
+    }
+    // `length` counts UTF-16 units, so only longer strings can be over.
+    const truncated =
+      message.length > MAX_MESSAGE_CHARS
+        ? Array.from(message).slice(0, MAX_MESSAGE_CHARS).join("")
+        : message;
+    commands.logFromFrontend(level, truncated, null).catch(() => {});
+  };
+}
+
+// Every window loads this page; the settings and about windows open it with
+// their own route, see `RoleDefaults::page` in src-tauri/src/windows/mod.rs,
+// and get a view that only calls the commands they may.
+const views: Record<string, () => React.JSX.Element> = {
+  "#/settings": Settings,
+  "#/about": About,
+};
+const View = views[window.location.hash] ?? App;
 
 ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
   <React.StrictMode>
-    <App />
+    <View />
   </React.StrictMode>,
 );